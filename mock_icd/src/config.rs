@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde_yaml;
+use vkinfo::vk;
+use vkinfo::vulkan;
+
+/// Environment variable naming the YAML file that describes what the mock driver exposes.
+pub const CONFIG_ENV: &str = "VKINFO_MOCK_ICD_CONFIG";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Reported by `vkEnumerateInstanceVersion`, 1.0 if not given. A 1.0 driver rejects
+    /// instances asking for a newer version, like real 1.0 implementations may.
+    pub instance_version: Option<vk::Version>,
+    pub layers: Vec<Layer>,
+    pub extensions: Vec<vk::ExtensionProperties>,
+    pub physical_devices: Vec<PhysicalDevice>,
+    /// Reported by `vkEnumeratePhysicalDeviceGroups`; one group per device if empty.
+    pub device_groups: Vec<DeviceGroup>,
+    /// Commands that return the given error instead of doing their work.
+    pub failures: BTreeMap<String, vk::Result>,
+    /// Commands `vkGetInstanceProcAddr` pretends not to know.
+    pub missing_functions: Vec<String>,
+    /// Added to the count reported by the first count query of each listed enumeration in
+    /// this process, as if elements were plugged in or removed before the second call.
+    pub count_drift: BTreeMap<String, i32>,
+    /// File that object creation and destruction commands append their names to.
+    pub call_log: Option<PathBuf>,
+    /// File `vkCreateInstance` writes the `CreateInfo` it received to.
+    pub create_info_log: Option<PathBuf>,
+}
+
+/// What `vkCreateInstance` was asked for, as written to `create_info_log`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreateInfo {
+    pub application_name: Option<String>,
+    pub application_version: u32,
+    pub engine_name: Option<String>,
+    pub engine_version: u32,
+    pub api_version: u32,
+    pub layers: Vec<String>,
+    pub extensions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DeviceGroup {
+    /// Indices into `Config::physical_devices`.
+    pub physical_devices: Vec<usize>,
+    pub subset_allocation: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Layer {
+    pub properties: vk::LayerProperties,
+    pub extensions: Vec<vk::ExtensionProperties>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDevice {
+    pub properties: vk::PhysicalDeviceProperties,
+    pub features: vk::PhysicalDeviceFeatures,
+    /// Written to the matching structures in the `p_next` chain of
+    /// `vkGetPhysicalDeviceFeatures2` and `vkGetPhysicalDeviceProperties2`, zeroed if not given.
+    pub vulkan_11_features: Option<vk::PhysicalDeviceVulkan11Features>,
+    pub vulkan_12_features: Option<vk::PhysicalDeviceVulkan12Features>,
+    pub vulkan_13_features: Option<vk::PhysicalDeviceVulkan13Features>,
+    pub vulkan_14_features: Option<vk::PhysicalDeviceVulkan14Features>,
+    /// Only written if the `p_next` chain asks for it, which vkinfo does if the device lists
+    /// the extension. The same goes for `pci_bus_info` and `drm_properties`.
+    pub driver_properties: Option<vk::PhysicalDeviceDriverProperties>,
+    pub pci_bus_info: Option<vk::PhysicalDevicePciBusInfoPropertiesExt>,
+    pub drm_properties: Option<vk::PhysicalDeviceDrmPropertiesExt>,
+    pub id_properties: Option<vk::PhysicalDeviceIdProperties>,
+    pub vulkan_11_properties: Option<vk::PhysicalDeviceVulkan11Properties>,
+    pub vulkan_12_properties: Option<vk::PhysicalDeviceVulkan12Properties>,
+    pub vulkan_13_properties: Option<vk::PhysicalDeviceVulkan13Properties>,
+    pub vulkan_14_properties: Option<vk::PhysicalDeviceVulkan14Properties>,
+    pub copy_src_layouts: Vec<vk::ImageLayout>,
+    pub copy_dst_layouts: Vec<vk::ImageLayout>,
+    pub queue_families: Vec<QueueFamily>,
+    /// Answers for the VK_KHR_surface queries, whatever the surface. Without it the device
+    /// has no formats or present modes and no queue family can present.
+    pub surface: Option<SurfaceSupport>,
+    /// Answers for VK_KHR_display.
+    pub displays: Vec<Display>,
+    pub display_planes: Vec<DisplayPlane>,
+    /// Answers for the VK_KHR_video_queue queries; profiles not listed are unsupported.
+    pub video_profiles: Vec<VideoProfileSupport>,
+    pub memory_types: Vec<vk::MemoryType>,
+    pub memory_heaps: Vec<vk::MemoryHeap>,
+    /// Answers for VK_EXT_memory_budget: each call of `vkGetPhysicalDeviceMemoryProperties2`
+    /// on the same instance takes the next entry, and the last one repeats.
+    pub memory_budgets: Vec<MemoryBudget>,
+    /// Formats not listed have no features.
+    pub formats: Vec<FormatSupport>,
+    /// Answers for `vkGetPhysicalDeviceImageFormatProperties`; the first matching entry
+    /// wins, and without one the combination is unsupported.
+    pub image_formats: Vec<ImageFormatSupport>,
+    /// Answers for `vkGetPhysicalDeviceSparseImageFormatProperties`, whatever the image type,
+    /// usage and tiling.
+    pub sparse_formats: Vec<SparseFormatSupport>,
+    /// Answers for `vkGetPhysicalDeviceExternalBufferProperties`, whatever the usage; handle
+    /// types not listed have no features. The same goes for semaphores and fences.
+    pub external_buffers: Vec<ExternalMemorySupport>,
+    pub external_semaphores: Vec<ExternalSemaphoreSupport>,
+    pub external_fences: Vec<ExternalFenceSupport>,
+    /// Answers for `vkGetPhysicalDeviceToolProperties` and its VK_EXT_tooling_info alias.
+    pub tools: Vec<vk::PhysicalDeviceToolProperties>,
+    /// Hides `tools` from `vkGetPhysicalDeviceToolPropertiesEXT` while no device has
+    /// VK_EXT_tooling_info enabled, like layers that only hook created devices.
+    pub tools_need_device: bool,
+    pub extensions: Vec<vk::ExtensionProperties>,
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormatSupport {
+    pub format: vk::Format,
+    #[serde(default)]
+    pub properties: vk::FormatProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageFormatSupport {
+    pub format: vk::Format,
+    /// Matches any image type if not given.
+    #[serde(default)]
+    pub image_type: Option<vk::ImageType>,
+    /// Matches any tiling if not given.
+    #[serde(default)]
+    pub tiling: Option<vk::ImageTiling>,
+    /// Usages the entry covers; requests for other usages do not match. Any usage if not given.
+    #[serde(default)]
+    pub usage: Option<vk::ImageUsageFlags>,
+    #[serde(default)]
+    pub properties: vk::ImageFormatProperties,
+    /// Handle types `vkGetPhysicalDeviceImageFormatProperties2` accepts for the entry; any
+    /// other makes the combination unsupported.
+    #[serde(default)]
+    pub external_memory: Vec<ExternalMemorySupport>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalMemorySupport {
+    pub handle_type: vk::ExternalMemoryHandleTypeFlags,
+    #[serde(flatten)]
+    pub properties: vk::ExternalMemoryProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalSemaphoreSupport {
+    pub handle_type: vk::ExternalSemaphoreHandleTypeFlags,
+    #[serde(flatten)]
+    pub properties: vk::ExternalSemaphoreProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalFenceSupport {
+    pub handle_type: vk::ExternalFenceHandleTypeFlags,
+    #[serde(flatten)]
+    pub properties: vk::ExternalFenceProperties,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QueueFamily {
+    #[serde(flatten)]
+    pub properties: vk::QueueFamilyProperties,
+    /// Written to the matching structures in the `p_next` chain of
+    /// `vkGetPhysicalDeviceQueueFamilyProperties2`.
+    pub global_priorities: Vec<vk::QueueGlobalPriorityKhr>,
+    pub checkpoint_execution_stage_mask: vk::PipelineStageFlags,
+    pub video_codec_operations: vk::VideoCodecOperationFlagsKhr,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SurfaceSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKhr,
+    pub formats: Vec<vk::SurfaceFormatKhr>,
+    pub present_modes: Vec<vk::PresentModeKhr>,
+    /// One entry per queue family; families without one cannot present.
+    pub queue_family_support: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Display {
+    pub name: Option<String>,
+    pub physical_dimensions: vk::Extend2D,
+    pub physical_resolution: vk::Extend2D,
+    pub supported_transforms: vk::SurfaceTransformFlagsKhr,
+    pub plane_reorder_possible: bool,
+    pub persistent_content: bool,
+    pub modes: Vec<vk::DisplayModeParametersKhr>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DisplayPlane {
+    /// Indices into `PhysicalDevice::displays`.
+    pub current_display: Option<usize>,
+    pub current_stack_index: u32,
+    pub supported_displays: Vec<usize>,
+    /// The same for every mode.
+    pub capabilities: vk::DisplayPlaneCapabilitiesKhr,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct VideoProfileSupport {
+    pub profile: vulkan::VideoProfile,
+    pub capabilities: vulkan::VideoCapabilities,
+    /// Reported for decode output and encode input usage.
+    pub picture_formats: Vec<vk::Format>,
+    /// Reported for DPB usage.
+    pub dpb_formats: Vec<vk::Format>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MemoryBudget {
+    /// One entry per heap.
+    pub heap_usage: Vec<vk::DeviceSize>,
+    pub heap_budget: Vec<vk::DeviceSize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SparseFormatSupport {
+    pub format: vk::Format,
+    pub samples: vk::SampleCountFlags,
+    #[serde(default)]
+    pub properties: Vec<vk::SparseImageFormatProperties>,
+}
+
+impl ImageFormatSupport {
+    pub fn matches(&self, format: vk::Format, image_type: vk::ImageType, tiling: vk::ImageTiling, usage: vk::ImageUsageFlags) -> bool {
+        self.format == format
+            && self.image_type.is_none_or(|expected| expected == image_type)
+            && self.tiling.is_none_or(|expected| expected == tiling)
+            && self.usage.is_none_or(|supported| supported.contains(usage))
+    }
+}
+
+impl PhysicalDevice {
+    pub fn video_profile(&self, profile: &vulkan::VideoProfile) -> Option<&VideoProfileSupport> {
+        self.video_profiles.iter().find(|support| support.profile == *profile)
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.properties.layer_name.to_string_lossy() == name)
+    }
+}
+
+impl Config {
+    /// Reads the configuration named by `VKINFO_MOCK_ICD_CONFIG`. Without it the driver
+    /// exposes no layers, extensions or devices.
+    pub fn load() -> Result<Config, String> {
+        let path = match env::var_os(CONFIG_ENV) {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("{}: {}", path.to_string_lossy(), err))?;
+        serde_yaml::from_str(&contents)
+            .map_err(|err| format!("{}: {}", path.to_string_lossy(), err))
+    }
+
+    pub fn instance_version(&self) -> vk::Version {
+        self.instance_version.unwrap_or_else(|| vk::Version::from(vk::API_VERSION_1_0))
+    }
+
+    /// The error `function` has been configured to fail with, if any.
+    pub fn failure(&self, function: &str) -> Option<vk::Result> {
+        self.failures.get(function).cloned()
+    }
+
+    /// Appends `function` to the `call_log`, if one is configured.
+    pub fn log_call(&self, function: &str) {
+        if let Some(ref path) = self.call_log {
+            let mut file = fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+            writeln!(file, "{}", function).unwrap();
+        }
+    }
+
+    /// Writes `create_info` to the `create_info_log`, if one is configured.
+    pub fn log_create_info(&self, create_info: &CreateInfo) {
+        if let Some(ref path) = self.create_info_log {
+            fs::write(path, serde_yaml::to_string(create_info).unwrap()).unwrap();
+        }
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.properties.layer_name.to_string_lossy() == name)
+    }
+}
@@ -0,0 +1,960 @@
+//! A fake Vulkan driver for testing vkinfo without a GPU.
+//!
+//! The library exports `vk_icdGetInstanceProcAddr` so the Khronos loader can use it as an
+//! ICD, and `vkGetInstanceProcAddr` so it can also be passed to `vkinfo --loader` directly.
+//! What it reports is read from the YAML file named by `VKINFO_MOCK_ICD_CONFIG`, see
+//! `config::Config`.
+
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_yaml;
+extern crate vkinfo;
+
+pub mod config;
+
+use std::cmp;
+use std::collections::BTreeSet;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::os::raw::c_void;
+use std::process;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use config::{Config, CreateInfo};
+use vkinfo::vk;
+use vkinfo::vulkan::VideoProfile;
+
+/// Enumerations whose `count_drift` has already been applied.
+static DRIFTED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Value the loader expects in the first word of every dispatchable handle.
+const ICD_LOADER_MAGIC: usize = 0x01CD_C0DE;
+
+/// Highest loader/ICD interface version implemented here.
+const LOADER_ICD_INTERFACE_VERSION: u32 = 2;
+
+#[repr(C)]
+struct MockInstance {
+    loader_data: usize,
+    config: Config,
+    physical_devices: Vec<MockPhysicalDevice>,
+    /// Handle of the next surface `vkCreateHeadlessSurfaceEXT` creates.
+    next_surface: AtomicU64,
+}
+
+#[repr(C)]
+struct MockPhysicalDevice {
+    loader_data: usize,
+    config: config::PhysicalDevice,
+    /// The configuration of the whole driver, for `failures` and `count_drift`.
+    driver: Config,
+    /// Entries of `memory_budgets` handed out so far.
+    memory_budget_calls: AtomicUsize,
+    /// The names of `config.displays`, kept alive for `vkGetPhysicalDeviceDisplayPropertiesKHR`.
+    display_names: Vec<Option<CString>>,
+    /// Devices alive with VK_EXT_tooling_info enabled.
+    tooling_devices: AtomicUsize,
+}
+
+#[repr(C)]
+struct MockDevice {
+    loader_data: usize,
+    physical_device: *const MockPhysicalDevice,
+    tooling_info: bool,
+}
+
+unsafe fn instance<'a>(instance: vk::Instance) -> &'a MockInstance {
+    &*(instance as *const MockInstance)
+}
+
+unsafe fn physical_device<'a>(physical_device: vk::PhysicalDevice) -> &'a MockPhysicalDevice {
+    &*(physical_device as *const MockPhysicalDevice)
+}
+
+fn load_config() -> Option<Config> {
+    match Config::load() {
+        Ok(config) => Some(config),
+        Err(err) => {
+            eprintln!("vkinfo-mock-icd: {}", err);
+            None
+        }
+    }
+}
+
+/// Stands in for the validation layers: a structure passed with the wrong `s_type` is invalid
+/// usage, so the mock stops the process rather than let it go unnoticed.
+unsafe fn expect_structure_type(function: &str, s_type: &vk::Raw<vk::StructureType>, expected: vk::StructureType) {
+    if s_type.get() != expected {
+        eprintln!("vkinfo-mock-icd: {} called with s_type {:?} instead of {:?}", function, s_type.get(), expected);
+        process::abort();
+    }
+}
+
+/// Implements the usual two-call enumeration: report the count when `out` is null,
+/// otherwise write up to `*count` elements and return `Incomplete` if that was not all.
+unsafe fn write_array<T: Clone>(items: &[T], count: *mut u32, out: *mut T) -> vk::Raw<vk::Result> {
+    if out.is_null() {
+        *count = items.len() as u32;
+        return vk::Result::Success.into();
+    }
+
+    let written = cmp::min(*count as usize, items.len());
+    for (i, item) in items[..written].iter().enumerate() {
+        ptr::write(out.add(i), item.clone());
+    }
+    *count = written as u32;
+
+    if written < items.len() {
+        vk::Result::Incomplete.into()
+    } else {
+        vk::Result::Success.into()
+    }
+}
+
+/// The structures in a `p_next` chain, in order.
+unsafe fn chain(p_next: *mut c_void) -> Vec<*mut vk::BaseOutStructure> {
+    let mut structures = Vec::new();
+    let mut next = p_next as *mut vk::BaseOutStructure;
+    while !next.is_null() {
+        structures.push(next);
+        next = (*next).p_next;
+    }
+    structures
+}
+
+/// Overwrites `structure` with `value`, or zeroes if not configured, keeping its `p_next`.
+unsafe fn write_structure<T: vk::Structure + Clone>(structure: *mut vk::BaseOutStructure, value: &Option<T>) {
+    let p_next = (*structure).p_next;
+    ptr::write(structure as *mut T, value.clone().unwrap_or_default());
+    (*structure).p_next = p_next;
+}
+
+/// `write_array` for `function`, applying its configured `count_drift` once.
+unsafe fn enumerate<T: Clone>(config: &Config, function: &str, items: &[T], count: *mut u32, out: *mut T) -> vk::Raw<vk::Result> {
+    if out.is_null() {
+        if let Some(&drift) = config.count_drift.get(function) {
+            if DRIFTED.lock().unwrap().insert(function.to_string()) {
+                *count = cmp::max(items.len() as i64 + i64::from(drift), 0) as u32;
+                return vk::Result::Success.into();
+            }
+        }
+    }
+    write_array(items, count, out)
+}
+
+unsafe fn string(p: *const c_char) -> Option<String> {
+    if p.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(p).to_string_lossy().into_owned())
+    }
+}
+
+unsafe fn strings(count: u32, p: *const *const c_char) -> Vec<String> {
+    (0..count as usize).filter_map(|i| string(*p.add(i))).collect()
+}
+
+unsafe fn read_create_info(create_info: &vk::InstanceCreateInfo) -> CreateInfo {
+    let mut result = CreateInfo {
+        layers: strings(create_info.enabled_layer_count, create_info.pp_enabled_layer_names),
+        extensions: strings(create_info.enabled_extension_count, create_info.pp_enabled_extension_names),
+        ..CreateInfo::default()
+    };
+    if let Some(application_info) = create_info.p_application_info.as_ref() {
+        result.application_name = string(application_info.p_application_name);
+        result.application_version = application_info.application_version;
+        result.engine_name = string(application_info.p_engine_name);
+        result.engine_version = application_info.engine_version;
+        result.api_version = application_info.api_version;
+    }
+    result
+}
+
+unsafe extern "system" fn create_instance(p_create_info: *const vk::InstanceCreateInfo, _allocator: *const vk::AllocationCallbacks, p_instance: *mut vk::Instance) -> vk::Raw<vk::Result> {
+    let config = match load_config() {
+        Some(config) => config,
+        None => return vk::Result::ErrorInitializationFailed.into(),
+    };
+    if let Some(result) = config.failure("vkCreateInstance") {
+        return result.into();
+    }
+
+    let create_info = read_create_info(&*p_create_info);
+    let requested = vk::Version { patch: 0, ..vk::Version::from(create_info.api_version) };
+    if config.instance_version() < vk::Version::from(vk::API_VERSION_1_1) && requested > vk::Version::from(vk::API_VERSION_1_0) {
+        return vk::Result::ErrorIncompatibleDriver.into();
+    }
+    if create_info.layers.iter().any(|name| config.layer(name).is_none()) {
+        return vk::Result::ErrorLayerNotPresent.into();
+    }
+    let known_extension = |name: &String| {
+        config.extensions.iter()
+            .chain(create_info.layers.iter().flat_map(|layer| &config.layer(layer).unwrap().extensions))
+            .any(|extension| extension.extension_name.to_string_lossy() == *name)
+    };
+    if !create_info.extensions.iter().all(known_extension) {
+        return vk::Result::ErrorExtensionNotPresent.into();
+    }
+    config.log_create_info(&create_info);
+    config.log_call("vkCreateInstance");
+
+    let physical_devices = config.physical_devices.iter()
+        .map(|physical_device| MockPhysicalDevice {
+            loader_data: ICD_LOADER_MAGIC,
+            config: physical_device.clone(),
+            driver: config.clone(),
+            memory_budget_calls: AtomicUsize::new(0),
+            display_names: physical_device.displays.iter()
+                .map(|display| display.name.as_ref().map(|name| CString::new(name.as_str()).unwrap()))
+                .collect(),
+            tooling_devices: AtomicUsize::new(0),
+        })
+        .collect();
+
+    let instance = Box::new(MockInstance {
+        loader_data: ICD_LOADER_MAGIC,
+        config,
+        physical_devices,
+        next_surface: AtomicU64::new(1),
+    });
+    *p_instance = Box::into_raw(instance) as vk::Instance;
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn destroy_instance(instance: vk::Instance, _allocator: *const vk::AllocationCallbacks) {
+    if !instance.is_null() {
+        let instance = Box::from_raw(instance as *mut MockInstance);
+        instance.config.log_call("vkDestroyInstance");
+    }
+}
+
+unsafe extern "system" fn enumerate_instance_version(p_api_version: *mut u32) -> vk::Raw<vk::Result> {
+    let config = match load_config() {
+        Some(config) => config,
+        None => return vk::Result::ErrorInitializationFailed.into(),
+    };
+    if let Some(result) = config.failure("vkEnumerateInstanceVersion") {
+        return result.into();
+    }
+
+    *p_api_version = config.instance_version().into();
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn enumerate_instance_extension_properties(p_layer_name: *const c_char, count: *mut u32, properties: *mut vk::ExtensionProperties) -> vk::Raw<vk::Result> {
+    let config = match load_config() {
+        Some(config) => config,
+        None => return vk::Result::ErrorInitializationFailed.into(),
+    };
+    if let Some(result) = config.failure("vkEnumerateInstanceExtensionProperties") {
+        return result.into();
+    }
+
+    if p_layer_name.is_null() {
+        return enumerate(&config, "vkEnumerateInstanceExtensionProperties", &config.extensions, count, properties);
+    }
+
+    let layer_name = CStr::from_ptr(p_layer_name).to_string_lossy();
+    match config.layer(&layer_name) {
+        Some(layer) => enumerate(&config, "vkEnumerateInstanceExtensionProperties", &layer.extensions, count, properties),
+        None => vk::Result::ErrorLayerNotPresent.into(),
+    }
+}
+
+unsafe extern "system" fn enumerate_instance_layer_properties(count: *mut u32, properties: *mut vk::LayerProperties) -> vk::Raw<vk::Result> {
+    let config = match load_config() {
+        Some(config) => config,
+        None => return vk::Result::ErrorInitializationFailed.into(),
+    };
+    if let Some(result) = config.failure("vkEnumerateInstanceLayerProperties") {
+        return result.into();
+    }
+
+    let layers: Vec<vk::LayerProperties> = config.layers.iter().map(|layer| layer.properties.clone()).collect();
+    enumerate(&config, "vkEnumerateInstanceLayerProperties", &layers, count, properties)
+}
+
+unsafe extern "system" fn enumerate_physical_devices(p_instance: vk::Instance, count: *mut u32, physical_devices: *mut vk::PhysicalDevice) -> vk::Raw<vk::Result> {
+    let instance = instance(p_instance);
+    if let Some(result) = instance.config.failure("vkEnumeratePhysicalDevices") {
+        return result.into();
+    }
+
+    let handles: Vec<vk::PhysicalDevice> = instance.physical_devices.iter()
+        .map(|physical_device| physical_device as *const MockPhysicalDevice as vk::PhysicalDevice)
+        .collect();
+    enumerate(&instance.config, "vkEnumeratePhysicalDevices", &handles, count, physical_devices)
+}
+
+unsafe extern "system" fn enumerate_physical_device_groups(p_instance: vk::Instance, count: *mut u32, groups: *mut vk::PhysicalDeviceGroupProperties) -> vk::Raw<vk::Result> {
+    let instance = instance(p_instance);
+    if let Some(result) = instance.config.failure("vkEnumeratePhysicalDeviceGroups") {
+        return result.into();
+    }
+
+    let handle = |index: usize| &instance.physical_devices[index] as *const MockPhysicalDevice as vk::PhysicalDevice;
+    let mut properties = Vec::new();
+    if instance.config.device_groups.is_empty() {
+        for index in 0..instance.physical_devices.len() {
+            let mut group = vk::PhysicalDeviceGroupProperties { physical_device_count: 1, ..Default::default() };
+            group.physical_devices[0] = handle(index);
+            properties.push(group);
+        }
+    } else {
+        for config in &instance.config.device_groups {
+            let mut group = vk::PhysicalDeviceGroupProperties::default();
+            for (slot, &index) in config.physical_devices.iter().take(vk::MAX_DEVICE_GROUP_SIZE).enumerate() {
+                group.physical_devices[slot] = handle(index);
+                group.physical_device_count += 1;
+            }
+            group.subset_allocation = config.subset_allocation as vk::Bool32;
+            properties.push(group);
+        }
+    }
+    enumerate(&instance.config, "vkEnumeratePhysicalDeviceGroups", &properties, count, groups)
+}
+
+unsafe extern "system" fn get_physical_device_features(p_physical_device: vk::PhysicalDevice, features: *mut vk::PhysicalDeviceFeatures) {
+    ptr::write(features, physical_device(p_physical_device).config.features.clone());
+}
+
+unsafe extern "system" fn get_physical_device_properties(p_physical_device: vk::PhysicalDevice, properties: *mut vk::PhysicalDeviceProperties) {
+    ptr::write(properties, physical_device(p_physical_device).config.properties.clone());
+}
+
+unsafe extern "system" fn get_physical_device_features2(p_physical_device: vk::PhysicalDevice, features: *mut vk::PhysicalDeviceFeatures2) {
+    let config = &physical_device(p_physical_device).config;
+    (*features).features = config.features.clone();
+    for structure in chain((*features).p_next) {
+        match (*structure).s_type.get() {
+            vk::StructureType::PhysicalDeviceVulkan11Features => write_structure(structure, &config.vulkan_11_features),
+            vk::StructureType::PhysicalDeviceVulkan12Features => write_structure(structure, &config.vulkan_12_features),
+            vk::StructureType::PhysicalDeviceVulkan13Features => write_structure(structure, &config.vulkan_13_features),
+            vk::StructureType::PhysicalDeviceVulkan14Features => write_structure(structure, &config.vulkan_14_features),
+            _ => write_vulkan_11_features(structure, &config.vulkan_11_features.clone().unwrap_or_default()),
+        }
+    }
+}
+
+/// Fills the structures a 1.1 driver reports its 1.1 features in from the combined ones.
+unsafe fn write_vulkan_11_features(structure: *mut vk::BaseOutStructure, vulkan_11: &vk::PhysicalDeviceVulkan11Features) {
+    match (*structure).s_type.get() {
+        vk::StructureType::PhysicalDevice16BitStorageFeatures => {
+            let features = &mut *(structure as *mut vk::PhysicalDevice16BitStorageFeatures);
+            features.storage_buffer16_bit_access = vulkan_11.storage_buffer16_bit_access;
+            features.uniform_and_storage_buffer16_bit_access = vulkan_11.uniform_and_storage_buffer16_bit_access;
+            features.storage_push_constant16 = vulkan_11.storage_push_constant16;
+            features.storage_input_output16 = vulkan_11.storage_input_output16;
+        }
+        vk::StructureType::PhysicalDeviceMultiviewFeatures => {
+            let features = &mut *(structure as *mut vk::PhysicalDeviceMultiviewFeatures);
+            features.multiview = vulkan_11.multiview;
+            features.multiview_geometry_shader = vulkan_11.multiview_geometry_shader;
+            features.multiview_tessellation_shader = vulkan_11.multiview_tessellation_shader;
+        }
+        vk::StructureType::PhysicalDeviceVariablePointersFeatures => {
+            let features = &mut *(structure as *mut vk::PhysicalDeviceVariablePointersFeatures);
+            features.variable_pointers_storage_buffer = vulkan_11.variable_pointers_storage_buffer;
+            features.variable_pointers = vulkan_11.variable_pointers;
+        }
+        vk::StructureType::PhysicalDeviceProtectedMemoryFeatures => {
+            (*(structure as *mut vk::PhysicalDeviceProtectedMemoryFeatures)).protected_memory = vulkan_11.protected_memory;
+        }
+        vk::StructureType::PhysicalDeviceSamplerYcbcrConversionFeatures => {
+            (*(structure as *mut vk::PhysicalDeviceSamplerYcbcrConversionFeatures)).sampler_ycbcr_conversion = vulkan_11.sampler_ycbcr_conversion;
+        }
+        vk::StructureType::PhysicalDeviceShaderDrawParametersFeatures => {
+            (*(structure as *mut vk::PhysicalDeviceShaderDrawParametersFeatures)).shader_draw_parameters = vulkan_11.shader_draw_parameters;
+        }
+        _ => {}
+    }
+}
+
+/// Fills the structures a 1.1 driver reports its 1.1 properties in, apart from the IDs.
+unsafe fn write_vulkan_11_properties(structure: *mut vk::BaseOutStructure, vulkan_11: &vk::PhysicalDeviceVulkan11Properties) {
+    match (*structure).s_type.get() {
+        vk::StructureType::PhysicalDeviceSubgroupProperties => {
+            let properties = &mut *(structure as *mut vk::PhysicalDeviceSubgroupProperties);
+            properties.subgroup_size = vulkan_11.subgroup_size;
+            properties.supported_stages = vulkan_11.subgroup_supported_stages;
+            properties.supported_operations = vulkan_11.subgroup_supported_operations;
+            properties.quad_operations_in_all_stages = vulkan_11.subgroup_quad_operations_in_all_stages;
+        }
+        vk::StructureType::PhysicalDevicePointClippingProperties => {
+            (*(structure as *mut vk::PhysicalDevicePointClippingProperties)).point_clipping_behavior = vulkan_11.point_clipping_behavior;
+        }
+        vk::StructureType::PhysicalDeviceMultiviewProperties => {
+            let properties = &mut *(structure as *mut vk::PhysicalDeviceMultiviewProperties);
+            properties.max_multiview_view_count = vulkan_11.max_multiview_view_count;
+            properties.max_multiview_instance_index = vulkan_11.max_multiview_instance_index;
+        }
+        vk::StructureType::PhysicalDeviceProtectedMemoryProperties => {
+            (*(structure as *mut vk::PhysicalDeviceProtectedMemoryProperties)).protected_no_fault = vulkan_11.protected_no_fault;
+        }
+        vk::StructureType::PhysicalDeviceMaintenance3Properties => {
+            let properties = &mut *(structure as *mut vk::PhysicalDeviceMaintenance3Properties);
+            properties.max_per_set_descriptors = vulkan_11.max_per_set_descriptors;
+            properties.max_memory_allocation_size = vulkan_11.max_memory_allocation_size;
+        }
+        _ => {}
+    }
+}
+
+unsafe extern "system" fn get_physical_device_properties2(p_physical_device: vk::PhysicalDevice, properties: *mut vk::PhysicalDeviceProperties2) {
+    let config = &physical_device(p_physical_device).config;
+    (*properties).properties = config.properties.clone();
+    for structure in chain((*properties).p_next) {
+        match (*structure).s_type.get() {
+            vk::StructureType::PhysicalDeviceDriverProperties => write_structure(structure, &config.driver_properties),
+            vk::StructureType::PhysicalDevicePciBusInfoPropertiesExt => write_structure(structure, &config.pci_bus_info),
+            vk::StructureType::PhysicalDeviceDrmPropertiesExt => write_structure(structure, &config.drm_properties),
+            vk::StructureType::PhysicalDeviceIdProperties => write_structure(structure, &config.id_properties),
+            vk::StructureType::PhysicalDeviceVulkan11Properties => write_structure(structure, &config.vulkan_11_properties),
+            vk::StructureType::PhysicalDeviceVulkan12Properties => write_structure(structure, &config.vulkan_12_properties),
+            vk::StructureType::PhysicalDeviceVulkan13Properties => write_structure(structure, &config.vulkan_13_properties),
+            vk::StructureType::PhysicalDeviceVulkan14Properties => {
+                let vulkan_14 = structure as *mut vk::PhysicalDeviceVulkan14Properties;
+                let (src_count, p_copy_src_layouts) = ((*vulkan_14).copy_src_layout_count, (*vulkan_14).p_copy_src_layouts);
+                let (dst_count, p_copy_dst_layouts) = ((*vulkan_14).copy_dst_layout_count, (*vulkan_14).p_copy_dst_layouts);
+                write_structure(structure, &config.vulkan_14_properties);
+
+                let vulkan_14 = &mut *vulkan_14;
+                vulkan_14.p_copy_src_layouts = p_copy_src_layouts;
+                vulkan_14.p_copy_dst_layouts = p_copy_dst_layouts;
+                vulkan_14.copy_src_layout_count = src_count;
+                vulkan_14.copy_dst_layout_count = dst_count;
+                let copy_src_layouts: Vec<vk::Raw<vk::ImageLayout>> = config.copy_src_layouts.iter().map(|&layout| layout.into()).collect();
+                let copy_dst_layouts: Vec<vk::Raw<vk::ImageLayout>> = config.copy_dst_layouts.iter().map(|&layout| layout.into()).collect();
+                write_array(&copy_src_layouts, &mut vulkan_14.copy_src_layout_count, p_copy_src_layouts);
+                write_array(&copy_dst_layouts, &mut vulkan_14.copy_dst_layout_count, p_copy_dst_layouts);
+            }
+            _ => write_vulkan_11_properties(structure, &config.vulkan_11_properties.clone().unwrap_or_default()),
+        }
+    }
+}
+
+unsafe extern "system" fn get_physical_device_queue_family_properties(p_physical_device: vk::PhysicalDevice, count: *mut u32, properties: *mut vk::QueueFamilyProperties) {
+    let queue_families: Vec<vk::QueueFamilyProperties> = physical_device(p_physical_device).config.queue_families.iter().map(|family| family.properties.clone()).collect();
+    write_array(&queue_families, count, properties);
+}
+
+unsafe extern "system" fn get_physical_device_queue_family_properties2(p_physical_device: vk::PhysicalDevice, count: *mut u32, properties: *mut vk::QueueFamilyProperties2) {
+    let queue_families = &physical_device(p_physical_device).config.queue_families;
+    if properties.is_null() {
+        *count = queue_families.len() as u32;
+        return;
+    }
+
+    let written = cmp::min(*count as usize, queue_families.len());
+    for (index, family) in queue_families[..written].iter().enumerate() {
+        let element = properties.add(index);
+        (*element).queue_family_properties = family.properties.clone();
+        for structure in chain((*element).p_next) {
+            match (*structure).s_type.get() {
+                vk::StructureType::QueueFamilyGlobalPriorityPropertiesKhr => write_structure(structure, &Some(vk::QueueFamilyGlobalPriorityPropertiesKhr::new(&family.global_priorities))),
+                vk::StructureType::QueueFamilyCheckpointPropertiesNv => write_structure(structure, &Some(vk::QueueFamilyCheckpointPropertiesNv {
+                    checkpoint_execution_stage_mask: family.checkpoint_execution_stage_mask,
+                    ..Default::default()
+                })),
+                vk::StructureType::QueueFamilyVideoPropertiesKhr => write_structure(structure, &Some(vk::QueueFamilyVideoPropertiesKhr {
+                    video_codec_operations: family.video_codec_operations,
+                    ..Default::default()
+                })),
+                _ => {}
+            }
+        }
+    }
+    *count = written as u32;
+}
+
+unsafe extern "system" fn get_physical_device_memory_properties(p_physical_device: vk::PhysicalDevice, properties: *mut vk::PhysicalDeviceMemoryProperties) {
+    let config = &physical_device(p_physical_device).config;
+    ptr::write(properties, vk::PhysicalDeviceMemoryProperties::new(&config.memory_types, &config.memory_heaps));
+}
+
+unsafe extern "system" fn get_physical_device_memory_properties2(p_physical_device: vk::PhysicalDevice, properties: *mut vk::PhysicalDeviceMemoryProperties2) {
+    expect_structure_type("vkGetPhysicalDeviceMemoryProperties2", &(*properties).s_type, vk::StructureType::PhysicalDeviceMemoryProperties2);
+    let physical_device = physical_device(p_physical_device);
+    let config = &physical_device.config;
+    (*properties).memory_properties = vk::PhysicalDeviceMemoryProperties::new(&config.memory_types, &config.memory_heaps);
+    for structure in chain((*properties).p_next) {
+        if (*structure).s_type == vk::StructureType::PhysicalDeviceMemoryBudgetPropertiesExt {
+            let call = physical_device.memory_budget_calls.fetch_add(1, Ordering::SeqCst);
+            let mut budget = vk::PhysicalDeviceMemoryBudgetPropertiesExt::default();
+            if let Some(sample) = config.memory_budgets.get(call).or(config.memory_budgets.last()) {
+                for (heap, &usage) in sample.heap_usage.iter().take(vk::MAX_MEMORY_HEAPS).enumerate() {
+                    budget.heap_usage[heap] = usage;
+                }
+                for (heap, &size) in sample.heap_budget.iter().take(vk::MAX_MEMORY_HEAPS).enumerate() {
+                    budget.heap_budget[heap] = size;
+                }
+            }
+            write_structure(structure, &Some(budget));
+        }
+    }
+}
+
+unsafe extern "system" fn create_headless_surface(p_instance: vk::Instance, _create_info: *const vk::HeadlessSurfaceCreateInfoExt, _allocator: *const vk::AllocationCallbacks, p_surface: *mut vk::SurfaceKhr) -> vk::Raw<vk::Result> {
+    let instance = instance(p_instance);
+    if let Some(result) = instance.config.failure("vkCreateHeadlessSurfaceEXT") {
+        return result.into();
+    }
+    instance.config.log_call("vkCreateHeadlessSurfaceEXT");
+    *p_surface = instance.next_surface.fetch_add(1, Ordering::SeqCst);
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn destroy_surface(p_instance: vk::Instance, surface: vk::SurfaceKhr, _allocator: *const vk::AllocationCallbacks) {
+    if surface != vk::NULL_HANDLE {
+        instance(p_instance).config.log_call("vkDestroySurfaceKHR");
+    }
+}
+
+unsafe extern "system" fn get_physical_device_surface_support(p_physical_device: vk::PhysicalDevice, queue_family_index: u32, _surface: vk::SurfaceKhr, p_supported: *mut vk::Bool32) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceSurfaceSupportKHR") {
+        return result.into();
+    }
+    let supported = physical_device.config.surface.as_ref()
+        .and_then(|surface| surface.queue_family_support.get(queue_family_index as usize).cloned())
+        .unwrap_or(false);
+    *p_supported = supported as vk::Bool32;
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn get_physical_device_surface_capabilities(p_physical_device: vk::PhysicalDevice, _surface: vk::SurfaceKhr, capabilities: *mut vk::SurfaceCapabilitiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceSurfaceCapabilitiesKHR") {
+        return result.into();
+    }
+    ptr::write(capabilities, physical_device.config.surface.as_ref().map(|surface| surface.capabilities).unwrap_or_default());
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn get_physical_device_surface_formats(p_physical_device: vk::PhysicalDevice, _surface: vk::SurfaceKhr, count: *mut u32, formats: *mut vk::SurfaceFormatKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let surface_formats = physical_device.config.surface.as_ref().map(|surface| surface.formats.clone()).unwrap_or_default();
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceSurfaceFormatsKHR", &surface_formats, count, formats)
+}
+
+unsafe extern "system" fn get_physical_device_surface_present_modes(p_physical_device: vk::PhysicalDevice, _surface: vk::SurfaceKhr, count: *mut u32, present_modes: *mut vk::Raw<vk::PresentModeKhr>) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let modes: Vec<vk::Raw<vk::PresentModeKhr>> = physical_device.config.surface.iter()
+        .flat_map(|surface| surface.present_modes.iter().map(|&mode| vk::Raw::from(mode)))
+        .collect();
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceSurfacePresentModesKHR", &modes, count, present_modes)
+}
+
+/// Display handles are the display's index plus one, so that none is `NULL_HANDLE`.
+fn display_handle(index: usize) -> vk::DisplayKhr {
+    index as u64 + 1
+}
+
+/// Mode handles carry the display's handle in their upper half and the mode's in the lower.
+fn display_mode_handle(display: usize, mode: usize) -> vk::DisplayModeKhr {
+    (display_handle(display) << 32) | (mode as u64 + 1)
+}
+
+unsafe extern "system" fn get_physical_device_display_properties(p_physical_device: vk::PhysicalDevice, count: *mut u32, properties: *mut vk::DisplayPropertiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceDisplayPropertiesKHR") {
+        return result.into();
+    }
+    let displays: Vec<vk::DisplayPropertiesKhr> = physical_device.config.displays.iter().zip(&physical_device.display_names).enumerate()
+        .map(|(index, (display, name))| vk::DisplayPropertiesKhr {
+            display: display_handle(index),
+            display_name: name.as_ref().map_or(ptr::null(), |name| name.as_ptr()),
+            physical_dimensions: display.physical_dimensions,
+            physical_resolution: display.physical_resolution,
+            supported_transforms: display.supported_transforms,
+            plane_reorder_possible: display.plane_reorder_possible as vk::Bool32,
+            persistent_content: display.persistent_content as vk::Bool32,
+        })
+        .collect();
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceDisplayPropertiesKHR", &displays, count, properties)
+}
+
+unsafe extern "system" fn get_physical_device_display_plane_properties(p_physical_device: vk::PhysicalDevice, count: *mut u32, properties: *mut vk::DisplayPlanePropertiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let planes: Vec<vk::DisplayPlanePropertiesKhr> = physical_device.config.display_planes.iter()
+        .map(|plane| vk::DisplayPlanePropertiesKhr {
+            current_display: plane.current_display.map_or(vk::NULL_HANDLE, display_handle),
+            current_stack_index: plane.current_stack_index,
+        })
+        .collect();
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceDisplayPlanePropertiesKHR", &planes, count, properties)
+}
+
+unsafe extern "system" fn get_display_plane_supported_displays(p_physical_device: vk::PhysicalDevice, plane_index: u32, count: *mut u32, displays: *mut vk::DisplayKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let supported: Vec<vk::DisplayKhr> = match physical_device.config.display_planes.get(plane_index as usize) {
+        Some(plane) => plane.supported_displays.iter().map(|&display| display_handle(display)).collect(),
+        None => return vk::Result::ErrorUnknown.into(),
+    };
+    enumerate(&physical_device.driver, "vkGetDisplayPlaneSupportedDisplaysKHR", &supported, count, displays)
+}
+
+unsafe extern "system" fn get_display_mode_properties(p_physical_device: vk::PhysicalDevice, display: vk::DisplayKhr, count: *mut u32, properties: *mut vk::DisplayModePropertiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let index = (display as usize).wrapping_sub(1);
+    let modes: Vec<vk::DisplayModePropertiesKhr> = match physical_device.config.displays.get(index) {
+        Some(config) => config.modes.iter().enumerate()
+            .map(|(mode, &parameters)| vk::DisplayModePropertiesKhr { display_mode: display_mode_handle(index, mode), parameters })
+            .collect(),
+        None => return vk::Result::ErrorUnknown.into(),
+    };
+    enumerate(&physical_device.driver, "vkGetDisplayModePropertiesKHR", &modes, count, properties)
+}
+
+unsafe extern "system" fn get_display_plane_capabilities(p_physical_device: vk::PhysicalDevice, _mode: vk::DisplayModeKhr, plane_index: u32, capabilities: *mut vk::DisplayPlaneCapabilitiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetDisplayPlaneCapabilitiesKHR") {
+        return result.into();
+    }
+    match physical_device.config.display_planes.get(plane_index as usize) {
+        Some(plane) => {
+            ptr::write(capabilities, plane.capabilities);
+            vk::Result::Success.into()
+        }
+        None => vk::Result::ErrorUnknown.into(),
+    }
+}
+
+/// `profile` and the codec profile structure in its chain as one `VideoProfile`.
+unsafe fn read_video_profile(profile: *const vk::VideoProfileInfoKhr) -> VideoProfile {
+    let mut std_profile = 0;
+    for structure in chain((*profile).p_next as *mut c_void) {
+        std_profile = match (*structure).s_type.get() {
+            vk::StructureType::VideoDecodeH264ProfileInfoKhr => (*(structure as *const vk::VideoDecodeH264ProfileInfoKhr)).std_profile_idc,
+            vk::StructureType::VideoDecodeH265ProfileInfoKhr => (*(structure as *const vk::VideoDecodeH265ProfileInfoKhr)).std_profile_idc,
+            vk::StructureType::VideoDecodeAv1ProfileInfoKhr => (*(structure as *const vk::VideoDecodeAv1ProfileInfoKhr)).std_profile,
+            vk::StructureType::VideoDecodeVp9ProfileInfoKhr => (*(structure as *const vk::VideoDecodeVp9ProfileInfoKhr)).std_profile,
+            vk::StructureType::VideoEncodeH264ProfileInfoKhr => (*(structure as *const vk::VideoEncodeH264ProfileInfoKhr)).std_profile_idc,
+            vk::StructureType::VideoEncodeH265ProfileInfoKhr => (*(structure as *const vk::VideoEncodeH265ProfileInfoKhr)).std_profile_idc,
+            vk::StructureType::VideoEncodeAv1ProfileInfoKhr => (*(structure as *const vk::VideoEncodeAv1ProfileInfoKhr)).std_profile,
+            _ => continue,
+        };
+    }
+    VideoProfile {
+        operation: (*profile).video_codec_operation,
+        std_profile,
+        chroma_subsampling: (*profile).chroma_subsampling,
+        luma_bit_depth: (*profile).luma_bit_depth,
+        chroma_bit_depth: (*profile).chroma_bit_depth,
+    }
+}
+
+unsafe extern "system" fn get_physical_device_video_capabilities(p_physical_device: vk::PhysicalDevice, profile: *const vk::VideoProfileInfoKhr, capabilities: *mut vk::VideoCapabilitiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceVideoCapabilitiesKHR") {
+        return result.into();
+    }
+    let support = match physical_device.config.video_profile(&read_video_profile(profile)) {
+        Some(support) => &support.capabilities,
+        None => return vk::Result::ErrorVideoProfileCodecNotSupportedKhr.into(),
+    };
+
+    let p_next = (*capabilities).p_next;
+    write_structure(capabilities as *mut vk::BaseOutStructure, &Some(support.capabilities.clone()));
+    let max_level = support.max_level;
+    for structure in chain(p_next) {
+        match (*structure).s_type.get() {
+            vk::StructureType::VideoDecodeCapabilitiesKhr => write_structure(structure, &support.decode),
+            vk::StructureType::VideoEncodeCapabilitiesKhr => write_structure(structure, &support.encode),
+            vk::StructureType::VideoDecodeH264CapabilitiesKhr => write_structure(structure, &Some(vk::VideoDecodeH264CapabilitiesKhr { max_level_idc: max_level, ..Default::default() })),
+            vk::StructureType::VideoDecodeH265CapabilitiesKhr => write_structure(structure, &Some(vk::VideoDecodeH265CapabilitiesKhr { max_level_idc: max_level, ..Default::default() })),
+            vk::StructureType::VideoDecodeAv1CapabilitiesKhr => write_structure(structure, &Some(vk::VideoDecodeAv1CapabilitiesKhr { max_level, ..Default::default() })),
+            vk::StructureType::VideoDecodeVp9CapabilitiesKhr => write_structure(structure, &Some(vk::VideoDecodeVp9CapabilitiesKhr { max_level, ..Default::default() })),
+            vk::StructureType::VideoEncodeH264CapabilitiesKhr => write_structure(structure, &Some(vk::VideoEncodeH264CapabilitiesKhr { max_level_idc: max_level, ..Default::default() })),
+            vk::StructureType::VideoEncodeH265CapabilitiesKhr => write_structure(structure, &Some(vk::VideoEncodeH265CapabilitiesKhr { max_level_idc: max_level, ..Default::default() })),
+            vk::StructureType::VideoEncodeAv1CapabilitiesKhr => write_structure(structure, &Some(vk::VideoEncodeAv1CapabilitiesKhr { max_level, ..Default::default() })),
+            _ => {}
+        }
+    }
+    vk::Result::Success.into()
+}
+
+/// Reports the configured formats of the first profile in the `VideoProfileListInfoKhr`.
+unsafe extern "system" fn get_physical_device_video_format_properties(p_physical_device: vk::PhysicalDevice, format_info: *const vk::PhysicalDeviceVideoFormatInfoKhr, count: *mut u32, properties: *mut vk::VideoFormatPropertiesKhr) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let profile = chain((*format_info).p_next as *mut c_void).into_iter()
+        .find(|&structure| (*structure).s_type == vk::StructureType::VideoProfileListInfoKhr)
+        .map(|structure| &*(structure as *const vk::VideoProfileListInfoKhr))
+        .filter(|list| list.profile_count > 0)
+        .map(|list| read_video_profile(list.p_profiles));
+    let support = match profile.as_ref().and_then(|profile| physical_device.config.video_profile(profile)) {
+        Some(support) => support,
+        None => return vk::Result::ErrorVideoProfileCodecNotSupportedKhr.into(),
+    };
+
+    let usage = (*format_info).image_usage;
+    let formats = if usage.intersects(vk::ImageUsageFlags::VideoDecodeDpbBitKhr | vk::ImageUsageFlags::VideoEncodeDpbBitKhr) {
+        &support.dpb_formats
+    } else {
+        &support.picture_formats
+    };
+    let formats: Vec<vk::VideoFormatPropertiesKhr> = formats.iter().map(|&format| vk::VideoFormatPropertiesKhr {
+        format: format.into(),
+        image_type: vk::ImageType::Type2d.into(),
+        image_tiling: vk::ImageTiling::Optimal.into(),
+        image_usage_flags: usage,
+        ..Default::default()
+    }).collect();
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceVideoFormatPropertiesKHR", &formats, count, properties)
+}
+
+unsafe extern "system" fn get_physical_device_format_properties(p_physical_device: vk::PhysicalDevice, format: vk::Raw<vk::Format>, properties: *mut vk::FormatProperties) {
+    let config = &physical_device(p_physical_device).config;
+    let supported = config.formats.iter().find(|support| support.format == format.get());
+    ptr::write(properties, supported.map(|support| support.properties).unwrap_or_default());
+}
+
+unsafe extern "system" fn get_physical_device_image_format_properties(p_physical_device: vk::PhysicalDevice, format: vk::Raw<vk::Format>, image_type: vk::Raw<vk::ImageType>, tiling: vk::Raw<vk::ImageTiling>, usage: vk::ImageUsageFlags, _flags: vk::ImageCreateFlags, properties: *mut vk::ImageFormatProperties) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceImageFormatProperties") {
+        return result.into();
+    }
+
+    let supported = physical_device.config.image_formats.iter()
+        .find(|support| support.matches(format.get(), image_type.get(), tiling.get(), usage));
+    match supported {
+        Some(support) => {
+            ptr::write(properties, support.properties);
+            vk::Result::Success.into()
+        }
+        None => vk::Result::ErrorFormatNotSupported.into(),
+    }
+}
+
+/// Like `vkGetPhysicalDeviceImageFormatProperties`, and answers for external memory if the
+/// chain asks about a handle type.
+unsafe extern "system" fn get_physical_device_image_format_properties2(p_physical_device: vk::PhysicalDevice, format_info: *const vk::PhysicalDeviceImageFormatInfo2, properties: *mut vk::ImageFormatProperties2) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceImageFormatProperties2") {
+        return result.into();
+    }
+
+    let info = &*format_info;
+    let support = match physical_device.config.image_formats.iter().find(|support| support.matches(info.format.get(), info.image_type.get(), info.tiling.get(), info.usage)) {
+        Some(support) => support,
+        None => return vk::Result::ErrorFormatNotSupported.into(),
+    };
+    let handle_type = chain(info.p_next as *mut c_void).into_iter()
+        .find(|&structure| (*structure).s_type == vk::StructureType::PhysicalDeviceExternalImageFormatInfo)
+        .map(|structure| (*(structure as *const vk::PhysicalDeviceExternalImageFormatInfo)).handle_type)
+        .filter(|handle_type| !handle_type.is_empty());
+    let external = match handle_type {
+        Some(handle_type) => match support.external_memory.iter().find(|external| external.handle_type == handle_type) {
+            Some(external) => Some(external.properties),
+            None => return vk::Result::ErrorFormatNotSupported.into(),
+        },
+        None => None,
+    };
+
+    (*properties).image_format_properties = support.properties;
+    for structure in chain((*properties).p_next) {
+        if (*structure).s_type == vk::StructureType::ExternalImageFormatProperties {
+            (*(structure as *mut vk::ExternalImageFormatProperties)).external_memory_properties = external.unwrap_or_default();
+        }
+    }
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn get_physical_device_external_buffer_properties(p_physical_device: vk::PhysicalDevice, info: *const vk::PhysicalDeviceExternalBufferInfo, properties: *mut vk::ExternalBufferProperties) {
+    let handle_type = (*info).handle_type;
+    (*properties).external_memory_properties = physical_device(p_physical_device).config.external_buffers.iter()
+        .find(|support| support.handle_type == handle_type)
+        .map_or_else(Default::default, |support| support.properties);
+}
+
+unsafe extern "system" fn get_physical_device_external_semaphore_properties(p_physical_device: vk::PhysicalDevice, info: *const vk::PhysicalDeviceExternalSemaphoreInfo, properties: *mut vk::ExternalSemaphoreProperties) {
+    let handle_type = (*info).handle_type;
+    let support = physical_device(p_physical_device).config.external_semaphores.iter()
+        .find(|support| support.handle_type == handle_type)
+        .map(|support| support.properties.clone());
+    write_structure(properties as *mut vk::BaseOutStructure, &support);
+}
+
+unsafe extern "system" fn get_physical_device_external_fence_properties(p_physical_device: vk::PhysicalDevice, info: *const vk::PhysicalDeviceExternalFenceInfo, properties: *mut vk::ExternalFenceProperties) {
+    let handle_type = (*info).handle_type;
+    let support = physical_device(p_physical_device).config.external_fences.iter()
+        .find(|support| support.handle_type == handle_type)
+        .map(|support| support.properties.clone());
+    write_structure(properties as *mut vk::BaseOutStructure, &support);
+}
+
+unsafe extern "system" fn create_device(p_physical_device: vk::PhysicalDevice, p_create_info: *const vk::DeviceCreateInfo, _allocator: *const vk::AllocationCallbacks, p_device: *mut vk::Device) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let driver = &physical_device.driver;
+    if let Some(result) = driver.failure("vkCreateDevice") {
+        return result.into();
+    }
+
+    let create_info = &*p_create_info;
+    let extensions = strings(create_info.enabled_extension_count, create_info.pp_enabled_extension_names);
+    let known_extension = |name: &String| physical_device.config.extensions.iter().any(|extension| extension.extension_name.to_string_lossy() == *name);
+    if !extensions.iter().all(known_extension) {
+        return vk::Result::ErrorExtensionNotPresent.into();
+    }
+    driver.log_call("vkCreateDevice");
+
+    let tooling_info = extensions.iter().any(|name| name == "VK_EXT_tooling_info");
+    if tooling_info {
+        physical_device.tooling_devices.fetch_add(1, Ordering::SeqCst);
+    }
+    let device = Box::new(MockDevice {
+        loader_data: ICD_LOADER_MAGIC,
+        physical_device,
+        tooling_info,
+    });
+    *p_device = Box::into_raw(device) as vk::Device;
+    vk::Result::Success.into()
+}
+
+unsafe extern "system" fn destroy_device(device: vk::Device, _allocator: *const vk::AllocationCallbacks) {
+    if !device.is_null() {
+        let device = Box::from_raw(device as *mut MockDevice);
+        let physical_device = &*device.physical_device;
+        if device.tooling_info {
+            physical_device.tooling_devices.fetch_sub(1, Ordering::SeqCst);
+        }
+        physical_device.driver.log_call("vkDestroyDevice");
+    }
+}
+
+unsafe extern "system" fn get_physical_device_tool_properties(p_physical_device: vk::PhysicalDevice, count: *mut u32, tools: *mut vk::PhysicalDeviceToolProperties) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceToolProperties") {
+        return result.into();
+    }
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceToolProperties", &physical_device.config.tools, count, tools)
+}
+
+unsafe extern "system" fn get_physical_device_tool_properties_ext(p_physical_device: vk::PhysicalDevice, count: *mut u32, tools: *mut vk::PhysicalDeviceToolProperties) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    if let Some(result) = physical_device.driver.failure("vkGetPhysicalDeviceToolPropertiesEXT") {
+        return result.into();
+    }
+    let config = &physical_device.config;
+    let hidden = config.tools_need_device && physical_device.tooling_devices.load(Ordering::SeqCst) == 0;
+    enumerate(&physical_device.driver, "vkGetPhysicalDeviceToolPropertiesEXT", if hidden { &[] } else { &config.tools }, count, tools)
+}
+
+unsafe extern "system" fn get_physical_device_sparse_image_format_properties(p_physical_device: vk::PhysicalDevice, format: vk::Raw<vk::Format>, _image_type: vk::Raw<vk::ImageType>, samples: vk::SampleCountFlags, _usage: vk::ImageUsageFlags, _tiling: vk::Raw<vk::ImageTiling>, count: *mut u32, properties: *mut vk::SparseImageFormatProperties) {
+    let supported = physical_device(p_physical_device).config.sparse_formats.iter()
+        .find(|support| support.format == format.get() && support.samples == samples);
+    write_array(supported.map_or(&[][..], |support| &support.properties), count, properties);
+}
+
+unsafe extern "system" fn enumerate_device_extension_properties(p_physical_device: vk::PhysicalDevice, p_layer_name: *const c_char, count: *mut u32, properties: *mut vk::ExtensionProperties) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let driver = &physical_device.driver;
+    if let Some(result) = driver.failure("vkEnumerateDeviceExtensionProperties") {
+        return result.into();
+    }
+
+    match string(p_layer_name) {
+        None => enumerate(driver, "vkEnumerateDeviceExtensionProperties", &physical_device.config.extensions, count, properties),
+        Some(name) => match physical_device.config.layer(&name) {
+            Some(layer) => enumerate(driver, "vkEnumerateDeviceExtensionProperties", &layer.extensions, count, properties),
+            None => vk::Result::ErrorLayerNotPresent.into(),
+        },
+    }
+}
+
+unsafe extern "system" fn enumerate_device_layer_properties(p_physical_device: vk::PhysicalDevice, count: *mut u32, properties: *mut vk::LayerProperties) -> vk::Raw<vk::Result> {
+    let physical_device = physical_device(p_physical_device);
+    let driver = &physical_device.driver;
+    if let Some(result) = driver.failure("vkEnumerateDeviceLayerProperties") {
+        return result.into();
+    }
+
+    let layers: Vec<vk::LayerProperties> = physical_device.config.layers.iter().map(|layer| layer.properties.clone()).collect();
+    enumerate(driver, "vkEnumerateDeviceLayerProperties", &layers, count, properties)
+}
+
+/// Resolves a Vulkan command to the mock implementation, or null if it is not implemented.
+///
+/// # Safety
+///
+/// `p_name` must point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "system" fn vkGetInstanceProcAddr(_instance: vk::Instance, p_name: *const c_char) -> *const c_void {
+    let name = CStr::from_ptr(p_name).to_bytes();
+
+    if let Some(config) = load_config() {
+        if config.missing_functions.iter().any(|missing| missing.as_bytes() == name) {
+            return ptr::null();
+        }
+    }
+
+    match name {
+        b"vkGetInstanceProcAddr" => vkGetInstanceProcAddr as *const c_void,
+        b"vkCreateInstance" => create_instance as *const c_void,
+        b"vkDestroyInstance" => destroy_instance as *const c_void,
+        b"vkEnumerateInstanceExtensionProperties" => enumerate_instance_extension_properties as *const c_void,
+        b"vkEnumerateInstanceLayerProperties" => enumerate_instance_layer_properties as *const c_void,
+        b"vkEnumerateInstanceVersion" => enumerate_instance_version as *const c_void,
+        b"vkEnumeratePhysicalDevices" => enumerate_physical_devices as *const c_void,
+        b"vkEnumeratePhysicalDeviceGroups" => enumerate_physical_device_groups as *const c_void,
+        b"vkGetPhysicalDeviceFeatures" => get_physical_device_features as *const c_void,
+        b"vkGetPhysicalDeviceProperties" => get_physical_device_properties as *const c_void,
+        b"vkGetPhysicalDeviceFeatures2" => get_physical_device_features2 as *const c_void,
+        b"vkGetPhysicalDeviceProperties2" => get_physical_device_properties2 as *const c_void,
+        b"vkGetPhysicalDeviceQueueFamilyProperties" => get_physical_device_queue_family_properties as *const c_void,
+        b"vkGetPhysicalDeviceQueueFamilyProperties2" => get_physical_device_queue_family_properties2 as *const c_void,
+        b"vkGetPhysicalDeviceMemoryProperties" => get_physical_device_memory_properties as *const c_void,
+        b"vkGetPhysicalDeviceMemoryProperties2" => get_physical_device_memory_properties2 as *const c_void,
+        b"vkGetPhysicalDeviceFormatProperties" => get_physical_device_format_properties as *const c_void,
+        b"vkGetPhysicalDeviceImageFormatProperties" => get_physical_device_image_format_properties as *const c_void,
+        b"vkGetPhysicalDeviceImageFormatProperties2" => get_physical_device_image_format_properties2 as *const c_void,
+        b"vkGetPhysicalDeviceExternalBufferProperties" => get_physical_device_external_buffer_properties as *const c_void,
+        b"vkGetPhysicalDeviceExternalSemaphoreProperties" => get_physical_device_external_semaphore_properties as *const c_void,
+        b"vkGetPhysicalDeviceExternalFenceProperties" => get_physical_device_external_fence_properties as *const c_void,
+        b"vkCreateDevice" => create_device as *const c_void,
+        b"vkDestroyDevice" => destroy_device as *const c_void,
+        b"vkGetPhysicalDeviceToolProperties" => get_physical_device_tool_properties as *const c_void,
+        b"vkGetPhysicalDeviceToolPropertiesEXT" => get_physical_device_tool_properties_ext as *const c_void,
+        b"vkGetPhysicalDeviceSparseImageFormatProperties" => get_physical_device_sparse_image_format_properties as *const c_void,
+        b"vkEnumerateDeviceExtensionProperties" => enumerate_device_extension_properties as *const c_void,
+        b"vkEnumerateDeviceLayerProperties" => enumerate_device_layer_properties as *const c_void,
+        b"vkCreateHeadlessSurfaceEXT" => create_headless_surface as *const c_void,
+        b"vkDestroySurfaceKHR" => destroy_surface as *const c_void,
+        b"vkGetPhysicalDeviceSurfaceSupportKHR" => get_physical_device_surface_support as *const c_void,
+        b"vkGetPhysicalDeviceSurfaceCapabilitiesKHR" => get_physical_device_surface_capabilities as *const c_void,
+        b"vkGetPhysicalDeviceSurfaceFormatsKHR" => get_physical_device_surface_formats as *const c_void,
+        b"vkGetPhysicalDeviceSurfacePresentModesKHR" => get_physical_device_surface_present_modes as *const c_void,
+        b"vkGetPhysicalDeviceDisplayPropertiesKHR" => get_physical_device_display_properties as *const c_void,
+        b"vkGetPhysicalDeviceDisplayPlanePropertiesKHR" => get_physical_device_display_plane_properties as *const c_void,
+        b"vkGetDisplayPlaneSupportedDisplaysKHR" => get_display_plane_supported_displays as *const c_void,
+        b"vkGetDisplayModePropertiesKHR" => get_display_mode_properties as *const c_void,
+        b"vkGetDisplayPlaneCapabilitiesKHR" => get_display_plane_capabilities as *const c_void,
+        b"vkGetPhysicalDeviceVideoCapabilitiesKHR" => get_physical_device_video_capabilities as *const c_void,
+        b"vkGetPhysicalDeviceVideoFormatPropertiesKHR" => get_physical_device_video_format_properties as *const c_void,
+        _ => ptr::null(),
+    }
+}
+
+/// Entry point used by the Khronos loader.
+///
+/// # Safety
+///
+/// `p_name` must point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "system" fn vk_icdGetInstanceProcAddr(instance: vk::Instance, p_name: *const c_char) -> *const c_void {
+    vkGetInstanceProcAddr(instance, p_name)
+}
+
+/// Agrees on the loader/ICD interface version with the Khronos loader.
+///
+/// # Safety
+///
+/// `p_version` must point to a valid `u32`.
+#[no_mangle]
+pub unsafe extern "system" fn vk_icdNegotiateLoaderICDInterfaceVersion(p_version: *mut u32) -> vk::Raw<vk::Result> {
+    *p_version = cmp::min(*p_version, LOADER_ICD_INTERFACE_VERSION);
+    vk::Result::Success.into()
+}
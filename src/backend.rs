@@ -0,0 +1,554 @@
+use serde_json;
+use serde_yaml;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use report::{self, Report};
+use sysfs::{DeviceLocation, Sysfs};
+use video;
+use vk;
+use vulkan::{Entry, Error, Features2, Instance, PhysicalDevice, Properties2, QueueFamilyProperties2, Result, Surface, VideoProfile};
+
+/// A source of the information shown in a report. Physical devices are addressed by their
+/// index, which must be below `physical_device_count`.
+pub trait Backend {
+    fn enumerate_instance_version(&self) -> Result<vk::Version>;
+    fn enumerate_instance_layer_properties(&self) -> Result<Vec<vk::LayerProperties>>;
+    fn enumerate_instance_extension_properties(&self, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>>;
+    fn physical_device_count(&self) -> Result<usize>;
+    fn enumerate_physical_device_groups(&self) -> Result<Vec<report::DeviceGroup>>;
+    fn get_physical_device_features(&self, physical_device: usize) -> vk::PhysicalDeviceFeatures;
+    fn get_physical_device_properties(&self, physical_device: usize) -> vk::PhysicalDeviceProperties;
+    fn get_physical_device_features2(&self, physical_device: usize) -> Features2;
+    fn get_physical_device_properties2(&self, physical_device: usize) -> Properties2;
+    fn get_physical_device_queue_family_properties(&self, physical_device: usize) -> Vec<vk::QueueFamilyProperties>;
+    fn get_physical_device_queue_family_properties2(&self, physical_device: usize) -> Vec<QueueFamilyProperties2>;
+    /// Capabilities, formats, present modes and per queue family present support for a
+    /// headless surface, or `None` if there is no such surface to ask about.
+    fn get_physical_device_surface_support(&self, physical_device: usize) -> Result<Option<report::SurfaceSupport>>;
+    /// Displays, their modes and the planes that can show them, or `None` without VK_KHR_display.
+    fn get_physical_device_display_support(&self, physical_device: usize) -> Result<Option<report::DisplaySupport>>;
+    /// The standard video profiles the device supports, or `None` without VK_KHR_video_queue.
+    fn get_physical_device_video_profiles(&self, physical_device: usize) -> Result<Option<Vec<report::VideoProfileSupport>>>;
+    /// Export and import support of every known external handle type, or `None` before 1.1.
+    fn get_physical_device_external_handle_support(&self, physical_device: usize) -> Result<Option<report::ExternalHandleSupport>>;
+    /// The active tools, or `None` before 1.3 without VK_EXT_tooling_info.
+    fn get_physical_device_tool_properties(&self, physical_device: usize) -> Result<Option<Vec<vk::PhysicalDeviceToolProperties>>>;
+    /// PCI address, kernel driver and DRM nodes, if the device reports its PCI bus or DRM nodes.
+    fn get_physical_device_location(&self, physical_device: usize) -> Option<DeviceLocation>;
+    fn get_physical_device_memory_properties(&self, physical_device: usize) -> vk::PhysicalDeviceMemoryProperties;
+    fn get_physical_device_format_properties(&self, physical_device: usize, format: vk::Format) -> vk::FormatProperties;
+    fn get_physical_device_sparse_image_format_properties(&self, physical_device: usize, format: vk::Format, image_type: vk::ImageType, samples: vk::SampleCountFlags, usage: vk::ImageUsageFlags, tiling: vk::ImageTiling) -> Vec<vk::SparseImageFormatProperties>;
+    fn enumerate_device_layer_properties(&self, physical_device: usize) -> Result<Vec<vk::LayerProperties>>;
+    fn enumerate_device_extension_properties(&self, physical_device: usize, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>>;
+}
+
+/// Queries a Vulkan driver through the loader.
+pub struct Live<'a> {
+    entry: &'a Entry,
+    instance: &'a Instance,
+    physical_devices: Vec<PhysicalDevice<'a>>,
+    sysfs: Sysfs,
+    /// `None` without VK_EXT_headless_surface, the error if creating the surface failed.
+    surface: Option<Result<Surface<'a>>>,
+}
+
+impl<'a> Live<'a> {
+    /// Creates a headless surface to query if the instance has VK_EXT_headless_surface enabled.
+    /// If the surface cannot be created, every device's surface query returns why.
+    pub fn new(entry: &'a Entry, instance: &'a Instance) -> Result<Live<'a>> {
+        let surface = if instance.is_extension_enabled("VK_EXT_headless_surface") {
+            Some(instance.create_headless_surface())
+        } else {
+            None
+        };
+
+        Ok(Live {
+            entry,
+            instance,
+            physical_devices: instance.enumerate_physical_devices()?,
+            sysfs: Sysfs::default(),
+            surface,
+        })
+    }
+
+    /// Looks devices up in `sysfs` instead of `/sys`.
+    pub fn with_sysfs(mut self, sysfs: Sysfs) -> Live<'a> {
+        self.sysfs = sysfs;
+        self
+    }
+
+    /// The handle types in `table` that `physical_device` can be asked about.
+    fn handle_types<T: Copy>(&self, physical_device: PhysicalDevice, table: &[(T, Option<&str>)]) -> Vec<T> {
+        table.iter()
+            .filter(|&&(_, extension)| extension.is_none_or(|extension| self.instance.has_device_extension(physical_device, extension)))
+            .map(|&(handle_type, _)| handle_type)
+            .collect()
+    }
+
+    fn surface_support(&self, physical_device: PhysicalDevice, surface: &Surface) -> Result<report::SurfaceSupport> {
+        let queue_family_count = self.instance.get_physical_device_queue_family_properties(physical_device).len() as u32;
+        let queue_family_support = (0..queue_family_count)
+            .map(|index| self.instance.get_physical_device_surface_support(physical_device, index, surface))
+            .collect::<Result<Vec<bool>>>()?;
+
+        Ok(report::SurfaceSupport {
+            capabilities: self.instance.get_physical_device_surface_capabilities(physical_device, surface)?,
+            formats: self.instance.get_physical_device_surface_formats(physical_device, surface)?,
+            present_modes: self.instance.get_physical_device_surface_present_modes(physical_device, surface)?,
+            queue_family_support,
+        })
+    }
+
+    fn display_support(&self, physical_device: PhysicalDevice) -> Result<report::DisplaySupport> {
+        let display_properties = self.instance.get_physical_device_display_properties(physical_device)?;
+        let display_index = |display: vk::DisplayKhr| display_properties.iter().position(|properties| properties.display == display);
+
+        let mut planes = Vec::new();
+        let mut supported_displays = Vec::new();
+        for (index, plane) in self.instance.get_physical_device_display_plane_properties(physical_device)?.into_iter().enumerate() {
+            let displays = self.instance.get_display_plane_supported_displays(physical_device, index as u32)?;
+            planes.push(report::DisplayPlane {
+                current_display: display_index(plane.current_display),
+                current_stack_index: plane.current_stack_index,
+                supported_displays: displays.iter().filter_map(|&display| display_index(display)).collect(),
+            });
+            supported_displays.push(displays);
+        }
+
+        let mut displays = Vec::new();
+        for properties in &display_properties {
+            let mut modes = Vec::new();
+            for mode in self.instance.get_display_mode_properties(physical_device, properties.display)? {
+                let mut plane_capabilities = Vec::new();
+                for (plane, displays) in supported_displays.iter().enumerate() {
+                    if !displays.contains(&properties.display) {
+                        continue;
+                    }
+                    let capabilities = self.instance.get_display_plane_capabilities(physical_device, mode.display_mode, plane as u32)?;
+                    plane_capabilities.push(report::PlaneCapabilities { plane: plane as u32, capabilities });
+                }
+                modes.push(report::DisplayMode { parameters: mode.parameters, planes: plane_capabilities });
+            }
+            displays.push(report::Display {
+                name: properties.display_name.clone(),
+                physical_dimensions: properties.physical_dimensions,
+                physical_resolution: properties.physical_resolution,
+                supported_transforms: properties.supported_transforms,
+                plane_reorder_possible: properties.plane_reorder_possible,
+                persistent_content: properties.persistent_content,
+                modes,
+            });
+        }
+
+        Ok(report::DisplaySupport { displays, planes })
+    }
+
+    /// The distinct formats the device reports for `profile` and `usage`.
+    fn video_formats(&self, physical_device: PhysicalDevice, profile: &VideoProfile, usage: vk::ImageUsageFlags) -> Result<Vec<vk::Format>> {
+        let mut formats = Vec::new();
+        for properties in self.instance.get_physical_device_video_format_properties(physical_device, profile, usage)? {
+            if !formats.contains(&properties.format.get()) {
+                formats.push(properties.format.get());
+            }
+        }
+        Ok(formats)
+    }
+}
+
+impl<'a> Backend for Live<'a> {
+    fn enumerate_instance_version(&self) -> Result<vk::Version> {
+        self.entry.enumerate_instance_version()
+    }
+
+    fn enumerate_instance_layer_properties(&self) -> Result<Vec<vk::LayerProperties>> {
+        self.entry.enumerate_instance_layer_properties()
+    }
+
+    fn enumerate_instance_extension_properties(&self, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        self.entry.enumerate_instance_extension_properties(layer_name)
+    }
+
+    fn physical_device_count(&self) -> Result<usize> {
+        Ok(self.physical_devices.len())
+    }
+
+    /// Devices the driver groups but did not enumerate on their own are left out.
+    fn enumerate_physical_device_groups(&self) -> Result<Vec<report::DeviceGroup>> {
+        Ok(self.instance.enumerate_physical_device_groups()?.into_iter().map(|group| report::DeviceGroup {
+            physical_devices: group.physical_devices.iter()
+                .filter_map(|member| self.physical_devices.iter().position(|physical_device| physical_device.handle() == member.handle()))
+                .map(|index| report::GroupMember {
+                    index,
+                    device_name: self.instance.get_physical_device_properties(self.physical_devices[index]).device_name.to_string_lossy(),
+                })
+                .collect(),
+            subset_allocation: group.subset_allocation,
+        }).collect())
+    }
+
+    fn get_physical_device_features(&self, physical_device: usize) -> vk::PhysicalDeviceFeatures {
+        self.instance.get_physical_device_features(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_properties(&self, physical_device: usize) -> vk::PhysicalDeviceProperties {
+        self.instance.get_physical_device_properties(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_features2(&self, physical_device: usize) -> Features2 {
+        self.instance.get_physical_device_features2(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_properties2(&self, physical_device: usize) -> Properties2 {
+        self.instance.get_physical_device_properties2(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_queue_family_properties(&self, physical_device: usize) -> Vec<vk::QueueFamilyProperties> {
+        self.instance.get_physical_device_queue_family_properties(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_queue_family_properties2(&self, physical_device: usize) -> Vec<QueueFamilyProperties2> {
+        self.instance.get_physical_device_queue_family_properties2(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_surface_support(&self, physical_device: usize) -> Result<Option<report::SurfaceSupport>> {
+        match self.surface {
+            Some(Ok(ref surface)) => self.surface_support(self.physical_devices[physical_device], surface).map(Some),
+            Some(Err(ref err)) => Err(err.clone()),
+            None => Ok(None),
+        }
+    }
+
+    fn get_physical_device_display_support(&self, physical_device: usize) -> Result<Option<report::DisplaySupport>> {
+        if !self.instance.is_extension_enabled("VK_KHR_display") {
+            return Ok(None);
+        }
+        self.display_support(self.physical_devices[physical_device]).map(Some)
+    }
+
+    /// Walks every standard profile of the codec operations the queue families offer.
+    fn get_physical_device_video_profiles(&self, physical_device: usize) -> Result<Option<Vec<report::VideoProfileSupport>>> {
+        let physical_device = self.physical_devices[physical_device];
+        if !self.instance.has_device_extension(physical_device, "VK_KHR_video_queue") {
+            return Ok(None);
+        }
+
+        let queue_families = self.instance.get_physical_device_queue_family_properties2(physical_device);
+        let operations = queue_families.iter()
+            .filter_map(|family| family.video_codec_operations)
+            .fold(vk::VideoCodecOperationFlagsKhr::empty(), |operations, family| operations | family);
+
+        let mut profiles = Vec::new();
+        for profile in video::standard_profiles(operations) {
+            let capabilities = match self.instance.get_physical_device_video_capabilities(physical_device, &profile)? {
+                Some(capabilities) => capabilities,
+                None => continue,
+            };
+            let (picture_usage, dpb_usage) = if profile.is_decode() {
+                (vk::ImageUsageFlags::VideoDecodeDstBitKhr, vk::ImageUsageFlags::VideoDecodeDpbBitKhr)
+            } else {
+                (vk::ImageUsageFlags::VideoEncodeSrcBitKhr, vk::ImageUsageFlags::VideoEncodeDpbBitKhr)
+            };
+            profiles.push(report::VideoProfileSupport {
+                picture_formats: self.video_formats(physical_device, &profile, picture_usage)?,
+                dpb_formats: self.video_formats(physical_device, &profile, dpb_usage)?,
+                queue_families: queue_families.iter().enumerate()
+                    .filter(|&(_, family)| family.video_codec_operations.is_some_and(|operations| operations.contains(profile.operation)))
+                    .map(|(index, _)| index as u32)
+                    .collect(),
+                profile,
+                capabilities,
+            });
+        }
+        Ok(Some(profiles))
+    }
+
+    fn get_physical_device_external_handle_support(&self, physical_device: usize) -> Result<Option<report::ExternalHandleSupport>> {
+        let physical_device = self.physical_devices[physical_device];
+        if self.instance.device_api_version(physical_device) < vk::API_VERSION_1_1 {
+            return Ok(None);
+        }
+
+        // A handle type the driver fails on is kept without properties rather than
+        // failing the report, so it reads as unknown instead of unsupported.
+        let mut support = report::ExternalHandleSupport::default();
+        for handle_type in self.handle_types(physical_device, EXTERNAL_MEMORY_HANDLE_TYPES) {
+            support.buffers.push(report::ExternalMemorySupport {
+                handle_type,
+                properties: self.instance.get_physical_device_external_buffer_properties(physical_device, vk::BufferCreateFlags::empty(), report::EXTERNAL_BUFFER_USAGE, handle_type).ok(),
+            });
+            support.images.push(report::ExternalMemorySupport {
+                handle_type,
+                properties: self.instance.get_physical_device_external_image_format_properties(physical_device, report::EXTERNAL_IMAGE_FORMAT, report::EXTERNAL_IMAGE_TYPE, report::EXTERNAL_IMAGE_TILING, report::EXTERNAL_IMAGE_USAGE, vk::ImageCreateFlags::empty(), handle_type).ok().map(Option::unwrap_or_default),
+            });
+        }
+        for handle_type in self.handle_types(physical_device, EXTERNAL_SEMAPHORE_HANDLE_TYPES) {
+            support.semaphores.push(report::ExternalSemaphoreSupport {
+                handle_type,
+                properties: self.instance.get_physical_device_external_semaphore_properties(physical_device, handle_type).ok(),
+            });
+        }
+        for handle_type in self.handle_types(physical_device, EXTERNAL_FENCE_HANDLE_TYPES) {
+            support.fences.push(report::ExternalFenceSupport {
+                handle_type,
+                properties: self.instance.get_physical_device_external_fence_properties(physical_device, handle_type).ok(),
+            });
+        }
+        Ok(Some(support))
+    }
+
+    fn get_physical_device_tool_properties(&self, physical_device: usize) -> Result<Option<Vec<vk::PhysicalDeviceToolProperties>>> {
+        let physical_device = self.physical_devices[physical_device];
+        if self.instance.device_api_version(physical_device) >= vk::API_VERSION_1_3 {
+            return self.instance.get_physical_device_tool_properties(physical_device).map(Some);
+        }
+
+        if !self.instance.has_device_extension(physical_device, "VK_EXT_tooling_info") {
+            return Ok(None);
+        }
+        // The extension is only usable once it is enabled on a device, and layers that
+        // implement it may not report themselves before then.
+        let _device = self.instance.create_device(physical_device, &["VK_EXT_tooling_info"])?;
+        self.instance.get_physical_device_tool_properties(physical_device).map(Some)
+    }
+
+    fn get_physical_device_location(&self, physical_device: usize) -> Option<DeviceLocation> {
+        let (pci_bus_info, drm) = self.instance.get_physical_device_bus_properties(self.physical_devices[physical_device]);
+        self.sysfs.locate(pci_bus_info.as_ref(), drm.as_ref())
+    }
+
+    fn get_physical_device_memory_properties(&self, physical_device: usize) -> vk::PhysicalDeviceMemoryProperties {
+        self.instance.get_physical_device_memory_properties(self.physical_devices[physical_device])
+    }
+
+    fn get_physical_device_format_properties(&self, physical_device: usize, format: vk::Format) -> vk::FormatProperties {
+        self.instance.get_physical_device_format_properties(self.physical_devices[physical_device], format)
+    }
+
+    fn get_physical_device_sparse_image_format_properties(&self, physical_device: usize, format: vk::Format, image_type: vk::ImageType, samples: vk::SampleCountFlags, usage: vk::ImageUsageFlags, tiling: vk::ImageTiling) -> Vec<vk::SparseImageFormatProperties> {
+        self.instance.get_physical_device_sparse_image_format_properties(self.physical_devices[physical_device], format, image_type, samples, usage, tiling)
+    }
+
+    fn enumerate_device_layer_properties(&self, physical_device: usize) -> Result<Vec<vk::LayerProperties>> {
+        self.instance.enumerate_device_layer_properties(self.physical_devices[physical_device])
+    }
+
+    fn enumerate_device_extension_properties(&self, physical_device: usize, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        self.instance.enumerate_device_extension_properties(self.physical_devices[physical_device], layer_name)
+    }
+}
+
+/// The external handle types vkinfo queries, with the device extension that defines each
+/// one; `None` for the types core Vulkan 1.1 defines, which every 1.1 implementation can
+/// be asked about.
+const EXTERNAL_MEMORY_HANDLE_TYPES: &[(vk::ExternalMemoryHandleTypeFlags, Option<&str>)] = &[
+    (vk::ExternalMemoryHandleTypeFlags::OpaqueFdBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::OpaqueWin32Bit, None),
+    (vk::ExternalMemoryHandleTypeFlags::OpaqueWin32KmtBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::D3d11TextureBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::D3d11TextureKmtBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::D3d12HeapBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::D3d12ResourceBit, None),
+    (vk::ExternalMemoryHandleTypeFlags::HostAllocationBitExt, Some("VK_EXT_external_memory_host")),
+    (vk::ExternalMemoryHandleTypeFlags::HostMappedForeignMemoryBitExt, Some("VK_EXT_external_memory_host")),
+    (vk::ExternalMemoryHandleTypeFlags::DmaBufBitExt, Some("VK_EXT_external_memory_dma_buf")),
+    (vk::ExternalMemoryHandleTypeFlags::AndroidHardwareBufferBitAndroid, Some("VK_ANDROID_external_memory_android_hardware_buffer")),
+    (vk::ExternalMemoryHandleTypeFlags::ZirconVmoBitFuchsia, Some("VK_FUCHSIA_external_memory")),
+    (vk::ExternalMemoryHandleTypeFlags::RdmaAddressBitNv, Some("VK_NV_external_memory_rdma")),
+    (vk::ExternalMemoryHandleTypeFlags::ScreenBufferBitQnx, Some("VK_QNX_external_memory_screen_buffer")),
+];
+
+const EXTERNAL_SEMAPHORE_HANDLE_TYPES: &[(vk::ExternalSemaphoreHandleTypeFlags, Option<&str>)] = &[
+    (vk::ExternalSemaphoreHandleTypeFlags::OpaqueFdBit, None),
+    (vk::ExternalSemaphoreHandleTypeFlags::OpaqueWin32Bit, None),
+    (vk::ExternalSemaphoreHandleTypeFlags::OpaqueWin32KmtBit, None),
+    (vk::ExternalSemaphoreHandleTypeFlags::D3d12FenceBit, None),
+    (vk::ExternalSemaphoreHandleTypeFlags::SyncFdBit, None),
+    (vk::ExternalSemaphoreHandleTypeFlags::ZirconEventBitFuchsia, Some("VK_FUCHSIA_external_semaphore")),
+];
+
+const EXTERNAL_FENCE_HANDLE_TYPES: &[(vk::ExternalFenceHandleTypeFlags, Option<&str>)] = &[
+    (vk::ExternalFenceHandleTypeFlags::OpaqueFdBit, None),
+    (vk::ExternalFenceHandleTypeFlags::OpaqueWin32Bit, None),
+    (vk::ExternalFenceHandleTypeFlags::OpaqueWin32KmtBit, None),
+    (vk::ExternalFenceHandleTypeFlags::SyncFdBit, None),
+];
+
+/// Answers queries from a previously saved report.
+pub struct Replay {
+    report: Report,
+}
+
+impl Replay {
+    pub fn new(report: Report) -> Replay {
+        Replay {
+            report,
+        }
+    }
+
+    /// Reads a report saved with `--format json` or `--format yaml`. Files ending in `.json`
+    /// are parsed as JSON, everything else as YAML.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Replay> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let report = if path.extension().is_some_and(|extension| extension == "json") {
+            serde_json::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))?
+        } else {
+            serde_yaml::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))?
+        };
+        Ok(Replay::new(report))
+    }
+}
+
+/// `section` of `physical_device`, or the error querying it failed with when the report was
+/// saved.
+fn saved_section<T: Clone>(physical_device: &report::PhysicalDevice, name: &str, section: &Option<T>) -> Result<Option<T>> {
+    match physical_device.errors.get(name) {
+        Some(message) => Err(Error::Saved(message.clone())),
+        None => Ok(section.clone()),
+    }
+}
+
+impl Backend for Replay {
+    fn enumerate_instance_version(&self) -> Result<vk::Version> {
+        Ok(self.report.instance_version)
+    }
+
+    fn enumerate_instance_layer_properties(&self) -> Result<Vec<vk::LayerProperties>> {
+        Ok(self.report.layers.iter().map(|layer| layer.properties.clone()).collect())
+    }
+
+    fn enumerate_instance_extension_properties(&self, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        match layer_name {
+            None => Ok(self.report.extensions.clone()),
+            Some(name) => self.report.layer(name)
+                .map(|layer| layer.extensions.clone())
+                .ok_or(Error::Call { function: "vkEnumerateInstanceExtensionProperties", result: vk::Result::ErrorLayerNotPresent }),
+        }
+    }
+
+    fn physical_device_count(&self) -> Result<usize> {
+        Ok(self.report.physical_devices.len())
+    }
+
+    /// Reports saved before groups were recorded get one group per device.
+    fn enumerate_physical_device_groups(&self) -> Result<Vec<report::DeviceGroup>> {
+        if !self.report.device_groups.is_empty() || self.report.physical_devices.is_empty() {
+            return Ok(self.report.device_groups.clone());
+        }
+        Ok(self.report.physical_devices.iter().enumerate().map(|(index, physical_device)| report::DeviceGroup {
+            physical_devices: vec![report::GroupMember { index, device_name: physical_device.properties.device_name.to_string_lossy() }],
+            subset_allocation: false,
+        }).collect())
+    }
+
+    fn get_physical_device_features(&self, physical_device: usize) -> vk::PhysicalDeviceFeatures {
+        self.report.physical_devices[physical_device].features.clone()
+    }
+
+    fn get_physical_device_properties(&self, physical_device: usize) -> vk::PhysicalDeviceProperties {
+        self.report.physical_devices[physical_device].properties.clone()
+    }
+
+    fn get_physical_device_features2(&self, physical_device: usize) -> Features2 {
+        let physical_device = &self.report.physical_devices[physical_device];
+        Features2 {
+            features: physical_device.features.clone(),
+            vulkan_11: physical_device.vulkan_11_features.clone(),
+            vulkan_12: physical_device.vulkan_12_features.clone(),
+            vulkan_13: physical_device.vulkan_13_features.clone(),
+            vulkan_14: physical_device.vulkan_14_features.clone(),
+        }
+    }
+
+    fn get_physical_device_properties2(&self, physical_device: usize) -> Properties2 {
+        let physical_device = &self.report.physical_devices[physical_device];
+        Properties2 {
+            properties: physical_device.properties.clone(),
+            driver: physical_device.driver_properties.clone(),
+            pci_bus_info: physical_device.pci_bus_info.clone(),
+            drm: physical_device.drm_properties.clone(),
+            id: physical_device.id_properties.clone(),
+            vulkan_11: physical_device.vulkan_11_properties.clone(),
+            vulkan_12: physical_device.vulkan_12_properties.clone(),
+            vulkan_13: physical_device.vulkan_13_properties.clone(),
+            vulkan_14: physical_device.vulkan_14_properties.clone(),
+            copy_src_layouts: physical_device.copy_src_layouts.clone(),
+            copy_dst_layouts: physical_device.copy_dst_layouts.clone(),
+        }
+    }
+
+    fn get_physical_device_queue_family_properties(&self, physical_device: usize) -> Vec<vk::QueueFamilyProperties> {
+        self.report.physical_devices[physical_device].queue_families.iter().map(|family| family.properties.clone()).collect()
+    }
+
+    fn get_physical_device_queue_family_properties2(&self, physical_device: usize) -> Vec<QueueFamilyProperties2> {
+        self.report.physical_devices[physical_device].queue_families.clone()
+    }
+
+    fn get_physical_device_surface_support(&self, physical_device: usize) -> Result<Option<report::SurfaceSupport>> {
+        let physical_device = &self.report.physical_devices[physical_device];
+        saved_section(physical_device, "surface", &physical_device.surface)
+    }
+
+    fn get_physical_device_display_support(&self, physical_device: usize) -> Result<Option<report::DisplaySupport>> {
+        let physical_device = &self.report.physical_devices[physical_device];
+        saved_section(physical_device, "displays", &physical_device.displays)
+    }
+
+    fn get_physical_device_video_profiles(&self, physical_device: usize) -> Result<Option<Vec<report::VideoProfileSupport>>> {
+        let physical_device = &self.report.physical_devices[physical_device];
+        saved_section(physical_device, "video_profiles", &physical_device.video_profiles)
+    }
+
+    fn get_physical_device_external_handle_support(&self, physical_device: usize) -> Result<Option<report::ExternalHandleSupport>> {
+        Ok(self.report.physical_devices[physical_device].external_handles.clone())
+    }
+
+    fn get_physical_device_tool_properties(&self, physical_device: usize) -> Result<Option<Vec<vk::PhysicalDeviceToolProperties>>> {
+        let physical_device = &self.report.physical_devices[physical_device];
+        saved_section(physical_device, "tools", &physical_device.tools)
+    }
+
+    /// The location on the machine the report was saved on.
+    fn get_physical_device_location(&self, physical_device: usize) -> Option<DeviceLocation> {
+        self.report.physical_devices[physical_device].location.clone()
+    }
+
+    fn get_physical_device_memory_properties(&self, physical_device: usize) -> vk::PhysicalDeviceMemoryProperties {
+        let physical_device = &self.report.physical_devices[physical_device];
+        vk::PhysicalDeviceMemoryProperties::new(&physical_device.memory_types, &physical_device.memory_heaps)
+    }
+
+    /// Formats missing from the report are not supported at all.
+    fn get_physical_device_format_properties(&self, physical_device: usize, format: vk::Format) -> vk::FormatProperties {
+        self.report.physical_devices[physical_device].format(format).cloned().unwrap_or_default()
+    }
+
+    /// Only the parameters `report::sparse_formats` asks about are recorded; anything else
+    /// is not supported.
+    fn get_physical_device_sparse_image_format_properties(&self, physical_device: usize, format: vk::Format, image_type: vk::ImageType, samples: vk::SampleCountFlags, usage: vk::ImageUsageFlags, tiling: vk::ImageTiling) -> Vec<vk::SparseImageFormatProperties> {
+        if image_type != report::SPARSE_IMAGE_TYPE || usage != report::SPARSE_USAGE || tiling != report::SPARSE_TILING {
+            return Vec::new();
+        }
+        self.report.physical_devices[physical_device].sparse_formats.iter()
+            .find(|support| support.format == format && support.samples == samples)
+            .map(|support| support.properties.clone())
+            .unwrap_or_default()
+    }
+
+    fn enumerate_device_layer_properties(&self, physical_device: usize) -> Result<Vec<vk::LayerProperties>> {
+        Ok(self.report.physical_devices[physical_device].layers.iter().map(|layer| layer.properties.clone()).collect())
+    }
+
+    fn enumerate_device_extension_properties(&self, physical_device: usize, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        let physical_device = &self.report.physical_devices[physical_device];
+        match layer_name {
+            None => Ok(physical_device.extensions.clone()),
+            Some(name) => physical_device.layer(name)
+                .map(|layer| layer.extensions.clone())
+                .ok_or(Error::Call { function: "vkEnumerateDeviceExtensionProperties", result: vk::Result::ErrorLayerNotPresent }),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Decoding of `PhysicalDeviceProperties::driver_version`, whose layout is up to the vendor.
+
+use vk;
+
+const VENDOR_ID_AMD: u32 = 0x1002;
+const VENDOR_ID_IMAGINATION: u32 = 0x1010;
+const VENDOR_ID_NVIDIA: u32 = 0x10de;
+const VENDOR_ID_ARM: u32 = 0x13b5;
+const VENDOR_ID_QUALCOMM: u32 = 0x5143;
+const VENDOR_ID_INTEL: u32 = 0x8086;
+const VENDOR_ID_MESA: u32 = 0x10005;
+
+/// Who wrote the driver, named after the driver ID if there is one and the vendor otherwise.
+pub fn driver_vendor(vendor_id: u32, driver_id: Option<vk::DriverId>) -> Option<&'static str> {
+    use vk::DriverId::*;
+
+    match driver_id {
+        Some(AmdProprietary) => Some("AMD"),
+        Some(AmdOpenSource) => Some("AMDVLK"),
+        Some(MesaRadv) | Some(IntelOpenSourceMesa) | Some(MesaLlvmpipe) | Some(MesaTurnip) | Some(MesaV3dv) | Some(MesaPanvk) | Some(MesaVenus) | Some(MesaDozen) | Some(MesaNvk) | Some(ImaginationOpenSourceMesa) | Some(MesaHoneykrisp) => Some("Mesa"),
+        Some(NvidiaProprietary) => Some("NVIDIA"),
+        Some(IntelProprietaryWindows) => Some("Intel"),
+        Some(ImaginationProprietary) => Some("Imagination"),
+        Some(QualcommProprietary) => Some("Qualcomm"),
+        Some(ArmProprietary) => Some("Arm"),
+        Some(GoogleSwiftshader) => Some("SwiftShader"),
+        Some(GgpProprietary) => Some("GGP"),
+        Some(BroadcomProprietary) => Some("Broadcom"),
+        Some(Moltenvk) => Some("MoltenVK"),
+        Some(CoreaviProprietary) => Some("CoreAVI"),
+        Some(JuiceProprietary) => Some("Juice"),
+        Some(VerisiliconProprietary) => Some("VeriSilicon"),
+        Some(SamsungProprietary) => Some("Samsung"),
+        Some(VulkanScEmulationOnCpu) => Some("Vulkan SC Emulation"),
+        Some(Unknown(_)) | None => match vendor_id {
+            VENDOR_ID_AMD => Some("AMD"),
+            VENDOR_ID_IMAGINATION => Some("Imagination"),
+            VENDOR_ID_NVIDIA => Some("NVIDIA"),
+            VENDOR_ID_ARM => Some("Arm"),
+            VENDOR_ID_QUALCOMM => Some("Qualcomm"),
+            VENDOR_ID_INTEL => Some("Intel"),
+            VENDOR_ID_MESA => Some("Mesa"),
+            _ => None,
+        },
+    }
+}
+
+/// `driver_version` as a dotted string. NVIDIA packs it as 10.8.8.6 bits and Intel's Windows
+/// driver as 18.14 bits; everyone else uses the layout of Vulkan versions. Without a driver
+/// ID, Intel's layout is assumed for Intel devices only when vkinfo itself runs on Windows.
+pub fn decode_driver_version(vendor_id: u32, driver_id: Option<vk::DriverId>, driver_version: u32) -> String {
+    let driver_id = driver_id.filter(|driver_id| !matches!(*driver_id, vk::DriverId::Unknown(_)));
+    let nvidia = match driver_id {
+        Some(driver_id) => driver_id == vk::DriverId::NvidiaProprietary,
+        None => vendor_id == VENDOR_ID_NVIDIA,
+    };
+    let intel_windows = match driver_id {
+        Some(driver_id) => driver_id == vk::DriverId::IntelProprietaryWindows,
+        None => vendor_id == VENDOR_ID_INTEL && cfg!(windows),
+    };
+
+    if nvidia {
+        let tertiary = driver_version & 0x3f;
+        let version = format!("{}.{}.{}", driver_version >> 22, (driver_version >> 14) & 0xff, (driver_version >> 6) & 0xff);
+        if tertiary == 0 { version } else { format!("{}.{}", version, tertiary) }
+    } else if intel_windows {
+        format!("{}.{}", driver_version >> 14, driver_version & 0x3fff)
+    } else {
+        format!("{}.{}.{}", vk::api_version_major(driver_version), vk::api_version_minor(driver_version), vk::api_version_patch(driver_version))
+    }
+}
+
+/// `decode_driver_version` prefixed with `driver_vendor`, e.g. "Mesa 24.2.3".
+pub fn describe_driver_version(vendor_id: u32, driver_id: Option<vk::DriverId>, driver_version: u32) -> String {
+    let version = decode_driver_version(vendor_id, driver_id, driver_version);
+    match driver_vendor(vendor_id, driver_id) {
+        Some(vendor) => format!("{} {}", vendor, version),
+        None => version,
+    }
+}
@@ -0,0 +1,119 @@
+//! The `--image-formats` mode, which asks each device about every combination of the chosen
+//! formats, image types, tilings and usages.
+
+use serde_json;
+use serde_yaml;
+use std::error::Error;
+
+use format_size;
+use options::{Format, Options};
+use vkinfo::vk;
+use vkinfo::vulkan::{Instance, PhysicalDevice};
+
+#[derive(Debug, Serialize)]
+struct Combination {
+    device: String,
+    format: vk::Format,
+    image_type: vk::ImageType,
+    tiling: vk::ImageTiling,
+    usage: vk::ImageUsageFlags,
+    /// `None` if the driver answered `ErrorFormatNotSupported`.
+    properties: Option<vk::ImageFormatProperties>,
+}
+
+/// The formats to try with `tiling`: those given on the command line, or every format the
+/// device reports any features for with that tiling.
+fn formats(instance: &Instance, physical_device: PhysicalDevice, tiling: vk::ImageTiling, options: &Options) -> Vec<vk::Format> {
+    if !options.vk_formats.is_empty() {
+        return options.vk_formats.clone();
+    }
+
+    vk::Format::VALUES.iter()
+        .cloned()
+        .filter(|&format| format != vk::Format::Undefined)
+        .filter(|&format| {
+            let properties = instance.get_physical_device_format_properties(physical_device, format);
+            match tiling {
+                vk::ImageTiling::Linear => !properties.linear_tiling_features.is_empty(),
+                _ => !properties.optimal_tiling_features.is_empty(),
+            }
+        })
+        .collect()
+}
+
+fn sweep(instance: &Instance, options: &Options) -> Result<Vec<Combination>, Box<dyn Error>> {
+    let image_types = if options.image_types.is_empty() {
+        vec![vk::ImageType::Type1d, vk::ImageType::Type2d, vk::ImageType::Type3d]
+    } else {
+        options.image_types.clone()
+    };
+    let tilings = if options.tilings.is_empty() {
+        vec![vk::ImageTiling::Optimal, vk::ImageTiling::Linear]
+    } else {
+        options.tilings.clone()
+    };
+    let usages = if options.usages.is_empty() {
+        vec![vk::ImageUsageFlags::SampledBit]
+    } else {
+        options.usages.clone()
+    };
+
+    let mut combinations = Vec::new();
+    for physical_device in instance.enumerate_physical_devices()? {
+        let device = instance.get_physical_device_properties(physical_device).device_name.to_string_lossy();
+        for &tiling in &tilings {
+            for format in formats(instance, physical_device, tiling, options) {
+                for &image_type in &image_types {
+                    for &usage in &usages {
+                        let properties = instance.get_physical_device_image_format_properties(physical_device, format, image_type, tiling, usage, vk::ImageCreateFlags::empty())?;
+                        combinations.push(Combination {
+                            device: device.clone(),
+                            format,
+                            image_type,
+                            tiling,
+                            usage,
+                            properties,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(combinations)
+}
+
+fn describe(properties: &Option<vk::ImageFormatProperties>) -> String {
+    match *properties {
+        None => "unsupported".to_string(),
+        Some(ref properties) => format!(
+            "max extent {}x{}x{}, {} mip levels, {} array layers, samples {:?}, max resource size {}",
+            properties.max_extent.width,
+            properties.max_extent.height,
+            properties.max_extent.depth,
+            properties.max_mip_levels,
+            properties.max_array_layers,
+            properties.sample_counts,
+            format_size(properties.max_resource_size)),
+    }
+}
+
+pub fn print(instance: &Instance, options: &Options) -> Result<(), Box<dyn Error>> {
+    let combinations = sweep(instance, options)?;
+
+    match options.format {
+        Format::Text => {
+            let mut device = None;
+            for combination in &combinations {
+                if device != Some(&combination.device) {
+                    println!("\nImage format properties of {}", combination.device);
+                    device = Some(&combination.device);
+                }
+                println!("  {:?} {:?} {:?} {:?}: {}", combination.format, combination.image_type, combination.tiling, combination.usage, describe(&combination.properties));
+            }
+        }
+        Format::Yaml => println!("{}", serde_yaml::to_string(&combinations)?),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&combinations)?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate bitflags;
+extern crate libloading as lib;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+extern crate serde_yaml;
+
+pub mod backend;
+pub mod driver;
+pub mod report;
+pub mod sysfs;
+pub mod video;
+pub mod vk;
+pub mod vulkan;
@@ -1,57 +1,537 @@
 #[macro_use]
-extern crate bitflags;
-extern crate libloading as lib;
-#[macro_use]
 extern crate serde_derive;
 extern crate serde;
+extern crate serde_json;
 extern crate serde_yaml;
+extern crate vkinfo;
+
+use serde::Serialize;
+use std::error::Error;
+use std::process;
+
+mod image_formats;
+mod options;
+mod watch;
+
+use options::{Format, Options};
+use vkinfo::backend::{Backend, Live, Replay};
+use vkinfo::driver;
+use vkinfo::report::{self, Report};
+use vkinfo::sysfs::Sysfs;
+use vkinfo::video;
+use vkinfo::vk;
+use vkinfo::vulkan;
 
-use std::os::raw::c_char;
-use std::ptr;
+fn print_device(backend: &dyn Backend, instance_layers: &[vk::LayerProperties], physical_device: usize) -> Result<(), Box<dyn Error>> {
+    let properties = backend.get_physical_device_properties2(physical_device);
+    println!("\nDevice {}: {}", physical_device, properties.properties.device_name.to_string_lossy());
 
-mod vk;
-mod vulkan;
+    let features = backend.get_physical_device_features2(physical_device);
+    println!("\nFeatures\n{}", serde_yaml::to_string(&features.features)?);
+    print_version_block("Vulkan 1.1 Features", &features.vulkan_11)?;
+    print_version_block("Vulkan 1.2 Features", &features.vulkan_12)?;
+    print_version_block("Vulkan 1.3 Features", &features.vulkan_13)?;
+    print_version_block("Vulkan 1.4 Features", &features.vulkan_14)?;
+    println!("\nProperties\n{}", serde_yaml::to_string(&properties.properties)?);
+    print_version_block("Vulkan 1.1 Properties", &properties.vulkan_11)?;
+    print_version_block("Vulkan 1.2 Properties", &properties.vulkan_12)?;
+    print_version_block("Vulkan 1.3 Properties", &properties.vulkan_13)?;
+    print_version_block("Vulkan 1.4 Properties", &properties.vulkan_14)?;
+    if properties.vulkan_14.is_some() {
+        println!("copy_src_layouts: {:?}\ncopy_dst_layouts: {:?}", properties.copy_src_layouts, properties.copy_dst_layouts);
+    }
+    print_driver(&properties);
+    if let Some(location) = backend.get_physical_device_location(physical_device) {
+        println!("\nLocation");
+        println!("  PCI address: {}", location.pci_address.as_deref().unwrap_or("unknown"));
+        println!("  Kernel driver: {}", location.kernel_driver.as_deref().unwrap_or("unknown"));
+        println!("  Primary node: {}", location.primary_node.as_deref().unwrap_or("none"));
+        println!("  Render node: {}", location.render_node.as_deref().unwrap_or("none"));
+    }
+    print_queue_families(&backend.get_physical_device_queue_family_properties2(physical_device));
+    print_section("Headless Surface", backend.get_physical_device_surface_support(physical_device), print_surface);
+    print_section("Displays", backend.get_physical_device_display_support(physical_device), print_displays);
+    print_section("Video Profiles", backend.get_physical_device_video_profiles(physical_device), |profiles| print_video_profiles(profiles));
+    if let Some(handles) = backend.get_physical_device_external_handle_support(physical_device)? {
+        print_external_handles(&handles);
+    }
+    print_section("Active Tools", backend.get_physical_device_tool_properties(physical_device), |tools| print_tools(tools, instance_layers));
 
-fn enumerate_extension_for_layer(functions: &vulkan::Entry, layer_name: *const c_char) -> vulkan::Result<()> {
-    let extensions = functions.enumerate_instance_extension_properties(layer_name)?;
+    let memory_properties = backend.get_physical_device_memory_properties(physical_device);
+    println!("\nMemory Heaps");
+    for (index, heap) in memory_properties.memory_heaps().iter().enumerate() {
+        println!("  {}: {} {:?}", index, format_size(heap.size), heap.flags);
+    }
+    println!("\nMemory Types");
+    for (index, memory_type) in memory_properties.memory_types().iter().enumerate() {
+        println!("  {}: heap {} {:?}", index, memory_type.heap_index, memory_type.property_flags);
+    }
+
+    let formats = report::supported_formats(backend, physical_device);
+    println!("\nFound {} supported formats: ", formats.len());
+    for support in &formats {
+        println!("  {:?}", support.format);
+        println!("    linear: {:?}", support.properties.linear_tiling_features);
+        println!("    optimal: {:?}", support.properties.optimal_tiling_features);
+        println!("    buffer: {:?}", support.properties.buffer_features);
+    }
+
+    let sparse_formats = report::sparse_formats(backend, physical_device, &formats);
+    println!("\nFound {} sparse format/sample count pairs: ", sparse_formats.len());
+    for support in &sparse_formats {
+        println!("  {:?} {:?}", support.format, support.samples);
+        for properties in &support.properties {
+            let granularity = &properties.image_granularity;
+            println!("    aspect {:?}, granularity {}x{}x{}, flags {:?}", properties.aspect_mask, granularity.width, granularity.height, granularity.depth, properties.flags);
+        }
+    }
+
+    let extensions = backend.enumerate_device_extension_properties(physical_device, None)?;
+    println!("\nFound {} device extensions: ", extensions.len());
     for extension in &extensions {
-        println!("{}", serde_yaml::to_string(extension).unwrap());
+        println!("{}", serde_yaml::to_string(extension)?);
     }
+
+    let layers = backend.enumerate_device_layer_properties(physical_device)?;
+    println!("\nFound {} device layers: ", layers.len());
+    for layer in &layers {
+        println!("{}", serde_yaml::to_string(layer)?);
+        for extension in backend.enumerate_device_extension_properties(physical_device, Some(&layer.layer_name.to_string_lossy()))? {
+            println!("{}", serde_yaml::to_string(&extension)?);
+        }
+    }
+
     Ok(())
 }
 
-fn main() {
-    println!("Loading library");
+fn print_driver(properties: &vulkan::Properties2) {
+    let driver_id = properties.driver.as_ref().map(|driver| driver.driver_id.get());
+    println!("\nDriver");
+    println!("  API version: {}", vk::Version::from(properties.properties.api_version));
+    println!("  Driver version: {}", driver::describe_driver_version(properties.properties.vendor_id, driver_id, properties.properties.driver_version));
+    if let Some(ref driver) = properties.driver {
+        println!("  Driver ID: {:?}", driver.driver_id);
+        println!("  Driver name: {}", driver.driver_name.to_string_lossy());
+        println!("  Driver info: {}", driver.driver_info.to_string_lossy());
+        println!("  Conformance version: {}", driver.conformance_version);
+    }
+    if let Some(ref id) = properties.id {
+        println!("  Device UUID: {}", format_uuid(&id.device_uuid));
+        println!("  Driver UUID: {}", format_uuid(&id.driver_uuid));
+        if id.device_luid_valid != 0 {
+            println!("  Device LUID: {}, node mask {:#x}", format_hex(&id.device_luid), id.device_node_mask);
+        } else {
+            println!("  Device LUID: none");
+        }
+    }
+}
+
+/// Prints each queue family with what the device's extensions add to it. Flags are printed
+/// by name, with bits vkinfo does not know as a hex number at the end.
+fn print_queue_families(queue_families: &[vulkan::QueueFamilyProperties2]) {
+    println!("\nQueue Families");
+    for (index, family) in queue_families.iter().enumerate() {
+        let properties = &family.properties;
+        let granularity = &properties.min_image_transfer_granularity;
+        println!("  {}: {} queues, {:?}", index, properties.queue_count, properties.queue_flags);
+        println!("    Timestamp valid bits: {}", properties.timestamp_valid_bits);
+        println!("    Min image transfer granularity: {}x{}x{}", granularity.width, granularity.height, granularity.depth);
+        if let Some(ref priorities) = family.global_priorities {
+            println!("    Global priorities: {:?}", priorities);
+        }
+        if let Some(stages) = family.checkpoint_execution_stage_mask {
+            println!("    Checkpoint execution stages: {:?}", stages);
+        }
+        if let Some(operations) = family.video_codec_operations {
+            println!("    Video codec operations: {:?}", operations);
+        }
+    }
+}
+
+/// Prints a device's section with `print`, or why it is missing if the driver failed the
+/// queries behind it.
+fn print_section<T, F: FnOnce(&T)>(title: &str, section: vulkan::Result<Option<T>>, print: F) {
+    match section {
+        Ok(Some(section)) => print(&section),
+        Ok(None) => {}
+        Err(err) => println!("\n{}\n  unavailable: {}", title, err),
+    }
+}
+
+fn print_surface(surface: &report::SurfaceSupport) {
+    let capabilities = &surface.capabilities;
+    let extent = |extent: &vk::Extend2D| format!("{}x{}", extent.width, extent.height);
+
+    println!("\nHeadless Surface");
+    if capabilities.max_image_count == 0 {
+        println!("  Image count: {} to unlimited", capabilities.min_image_count);
+    } else {
+        println!("  Image count: {} to {}", capabilities.min_image_count, capabilities.max_image_count);
+    }
+    if capabilities.current_extent.width == u32::MAX && capabilities.current_extent.height == u32::MAX {
+        println!("  Current extent: set by the swapchain");
+    } else {
+        println!("  Current extent: {}", extent(&capabilities.current_extent));
+    }
+    println!("  Image extent: {} to {}", extent(&capabilities.min_image_extent), extent(&capabilities.max_image_extent));
+    println!("  Max image array layers: {}", capabilities.max_image_array_layers);
+    println!("  Supported transforms: {:?}", capabilities.supported_transforms);
+    println!("  Current transform: {:?}", capabilities.current_transform);
+    println!("  Supported composite alpha: {:?}", capabilities.supported_composite_alpha);
+    println!("  Supported usage: {:?}", capabilities.supported_usage_flags);
+    println!("  Formats:");
+    for format in &surface.formats {
+        println!("    {:?} {:?}", format.format.get(), format.color_space.get());
+    }
+    println!("  Present modes: {:?}", surface.present_modes);
+    for (index, supported) in surface.queue_family_support.iter().enumerate() {
+        println!("  Queue family {} can present: {}", index, supported);
+    }
+}
+
+fn print_displays(support: &report::DisplaySupport) {
+    let extent = |extent: &vk::Extend2D| format!("{}x{}", extent.width, extent.height);
+    let position = |offset: &vk::Offset2D| format!("({}, {})", offset.x, offset.y);
+
+    println!("\nFound {} displays: ", support.displays.len());
+    for (index, display) in support.displays.iter().enumerate() {
+        println!("  Display {}: {}", index, display.name.as_deref().unwrap_or("unnamed"));
+        println!("    Physical size: {} mm, resolution {}", extent(&display.physical_dimensions), extent(&display.physical_resolution));
+        println!("    Supported transforms: {:?}", display.supported_transforms);
+        println!("    Plane reorder possible: {}, persistent content: {}", display.plane_reorder_possible, display.persistent_content);
+        for (mode_index, mode) in display.modes.iter().enumerate() {
+            let parameters = &mode.parameters;
+            println!("    Mode {}: {} @ {:.3} Hz", mode_index, extent(&parameters.visible_region), f64::from(parameters.refresh_rate) / 1000.0);
+            for plane in &mode.planes {
+                let capabilities = &plane.capabilities;
+                println!("      Plane {}: alpha {:?}", plane.plane, capabilities.supported_alpha);
+                println!("        source position {} to {}, extent {} to {}", position(&capabilities.min_src_position), position(&capabilities.max_src_position), extent(&capabilities.min_src_extent), extent(&capabilities.max_src_extent));
+                println!("        destination position {} to {}, extent {} to {}", position(&capabilities.min_dst_position), position(&capabilities.max_dst_position), extent(&capabilities.min_dst_extent), extent(&capabilities.max_dst_extent));
+            }
+        }
+    }
+
+    println!("\nFound {} display planes: ", support.planes.len());
+    for (index, plane) in support.planes.iter().enumerate() {
+        let current = plane.current_display.map_or_else(|| "none".to_string(), |display| display.to_string());
+        println!("  Plane {}: current display {}, stack index {}, displays {:?}", index, current, plane.current_stack_index, plane.supported_displays);
+    }
+}
+
+/// One row per supported profile, chroma subsampling and bit depth.
+fn print_video_profiles(profiles: &[report::VideoProfileSupport]) {
+    let formats = |formats: &[vk::Format]| formats.iter().map(|format| format!("{:?}", format)).collect::<Vec<String>>().join(", ");
+
+    println!("\nVideo Profiles");
+    if profiles.is_empty() {
+        println!("  none");
+        return;
+    }
+    println!("  {:<13} {:<24} {:<6} {:<4} {:<11} {:<4} {:<4} {:<7} Formats", "Codec", "Profile", "Chroma", "Bits", "Max extent", "DPB", "Refs", "Queues");
+    for support in profiles {
+        let profile = &support.profile;
+        let capabilities = &support.capabilities.capabilities;
+        let mut picture_formats = formats(&support.picture_formats);
+        if support.dpb_formats != support.picture_formats {
+            picture_formats = format!("{} (DPB: {})", picture_formats, formats(&support.dpb_formats));
+        }
+        println!("  {:<13} {:<24} {:<6} {:<4} {:<11} {:<4} {:<4} {:<7} {}",
+            video::operation_name(profile.operation).map_or_else(|| format!("{:?}", profile.operation), str::to_string),
+            video::profile_name(profile.operation, profile.std_profile).map_or_else(|| profile.std_profile.to_string(), str::to_string),
+            video::chroma_subsampling_name(profile.chroma_subsampling).map_or_else(|| format!("{:?}", profile.chroma_subsampling), str::to_string),
+            video::bit_depth(profile.luma_bit_depth).map_or_else(|| format!("{:?}", profile.luma_bit_depth), |bits| bits.to_string()),
+            format!("{}x{}", capabilities.max_coded_extent.width, capabilities.max_coded_extent.height),
+            capabilities.max_dpb_slots,
+            capabilities.max_active_reference_pictures,
+            support.queue_families.iter().map(|family| family.to_string()).collect::<Vec<String>>().join(","),
+            picture_formats);
+    }
+}
+
+/// One row per handle type, with what can be done with it for each kind of object; `-`
+/// where the handle type does not apply to that kind of object.
+fn print_external_handles(support: &report::ExternalHandleSupport) {
+    let mut handle_types: Vec<String> = Vec::new();
+    let names = support.buffers.iter().map(|entry| format!("{:?}", entry.handle_type))
+        .chain(support.semaphores.iter().map(|entry| format!("{:?}", entry.handle_type)))
+        .chain(support.fences.iter().map(|entry| format!("{:?}", entry.handle_type)));
+    for name in names {
+        if !handle_types.contains(&name) {
+            handle_types.push(name);
+        }
+    }
+
+    let memory = |entries: &[report::ExternalMemorySupport], name: &str| {
+        entries.iter().find(|entry| format!("{:?}", entry.handle_type) == name).map_or_else(|| "-".to_string(), |entry| {
+            let features = match entry.properties {
+                Some(ref properties) => properties.external_memory_features,
+                None => return "unknown".to_string(),
+            };
+            external_features(&[
+                (features.contains(vk::ExternalMemoryFeatureFlags::ExportableBit), "export"),
+                (features.contains(vk::ExternalMemoryFeatureFlags::ImportableBit), "import"),
+                (features.contains(vk::ExternalMemoryFeatureFlags::DedicatedOnlyBit), "dedicated"),
+            ])
+        })
+    };
+
+    println!("\nExternal Handles");
+    println!("  {:<32} {:<26} {:<26} {:<15} Fence", "Handle type", "Buffer", "Image", "Semaphore");
+    for name in &handle_types {
+        let semaphore = support.semaphores.iter().find(|entry| format!("{:?}", entry.handle_type) == *name).map_or_else(|| "-".to_string(), |entry| {
+            let features = match entry.properties {
+                Some(ref properties) => properties.external_semaphore_features,
+                None => return "unknown".to_string(),
+            };
+            external_features(&[
+                (features.contains(vk::ExternalSemaphoreFeatureFlags::ExportableBit), "export"),
+                (features.contains(vk::ExternalSemaphoreFeatureFlags::ImportableBit), "import"),
+            ])
+        });
+        let fence = support.fences.iter().find(|entry| format!("{:?}", entry.handle_type) == *name).map_or_else(|| "-".to_string(), |entry| {
+            let features = match entry.properties {
+                Some(ref properties) => properties.external_fence_features,
+                None => return "unknown".to_string(),
+            };
+            external_features(&[
+                (features.contains(vk::ExternalFenceFeatureFlags::ExportableBit), "export"),
+                (features.contains(vk::ExternalFenceFeatureFlags::ImportableBit), "import"),
+            ])
+        });
+        println!("  {:<32} {:<26} {:<26} {:<15} {}", name, memory(&support.buffers, name), memory(&support.images, name), semaphore, fence);
+    }
+}
+
+/// Each tool with whether the layer behind it is one of the instance layers; a tool whose
+/// layer is missing there was injected some other way.
+fn print_tools(tools: &[vk::PhysicalDeviceToolProperties], instance_layers: &[vk::LayerProperties]) {
+    println!("\nActive Tools");
+    if tools.is_empty() {
+        println!("  none");
+        return;
+    }
+    for tool in tools {
+        println!("  {} {}", tool.name.to_string_lossy(), tool.version.to_string_lossy());
+        println!("    Purposes: {:?}", tool.purposes);
+        println!("    Description: {}", tool.description.to_string_lossy());
+        let layer = tool.layer.to_string_lossy();
+        if layer.is_empty() {
+            println!("    Layer: none");
+        } else if instance_layers.iter().any(|instance_layer| instance_layer.layer_name.to_string_lossy() == layer) {
+            println!("    Layer: {} (instance layer)", layer);
+        } else {
+            println!("    Layer: {} (not among the instance layers)", layer);
+        }
+    }
+}
+
+/// E.g. `export, import`, or `no` if nothing applies.
+fn external_features(features: &[(bool, &str)]) -> String {
+    let names: Vec<&str> = features.iter().filter(|&&(supported, _)| supported).map(|&(_, name)| name).collect();
+    if names.is_empty() {
+        "no".to_string()
+    } else {
+        names.join(", ")
+    }
+}
 
-    let entry = vulkan::Entry::new().unwrap();
+/// Prints the features or properties of a newer Vulkan version, if the device has them.
+fn print_version_block<T: Serialize>(title: &str, block: &Option<T>) -> Result<(), Box<dyn Error>> {
+    if let Some(ref block) = *block {
+        println!("\n{}\n{}", title, serde_yaml::to_string(block)?);
+    }
+    Ok(())
+}
 
-    println!("Enumerating layers");
+fn enumerate_extension_for_layer(backend: &dyn Backend, layer_name: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let extensions = backend.enumerate_instance_extension_properties(layer_name)?;
+    for extension in &extensions {
+        println!("{}", serde_yaml::to_string(extension)?);
+    }
+    Ok(())
+}
 
-    let layers = entry.enumerate_instance_layer_properties().unwrap();
+/// Formats a byte count with the largest binary unit that keeps it at or above 1.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+fn format_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The usual 8-4-4-4-12 form.
+fn format_uuid(uuid: &[u8; 16]) -> String {
+    format!("{}-{}-{}-{}-{}", format_hex(&uuid[..4]), format_hex(&uuid[4..6]), format_hex(&uuid[6..8]), format_hex(&uuid[8..10]), format_hex(&uuid[10..]))
+}
+
+fn print_text(backend: &dyn Backend) -> Result<(), Box<dyn Error>> {
+    println!("Instance version: {}", backend.enumerate_instance_version()?);
+
+    println!("\nEnumerating layers");
+
+    let layers = backend.enumerate_instance_layer_properties()?;
 
     println!("\nFound {} layers: ", layers.len());
 
-    enumerate_extension_for_layer(&entry, ptr::null()).unwrap();
+    enumerate_extension_for_layer(backend, None)?;
 
     for layer in &layers {
-        println!("{}", serde_yaml::to_string(layer).unwrap());
-        enumerate_extension_for_layer(&entry, layer.layer_name.0.as_ptr()).unwrap();
+        println!("{}", serde_yaml::to_string(layer)?);
+        enumerate_extension_for_layer(backend, Some(&layer.layer_name.to_string_lossy()))?;
+    }
+
+    let physical_device_count = backend.physical_device_count()?;
+
+    println!("\nFound {} devices: ", physical_device_count);
+
+    let groups = backend.enumerate_physical_device_groups()?;
+    println!("\nFound {} device groups: ", groups.len());
+
+    let mut printed = vec![false; physical_device_count];
+    for (index, group) in groups.iter().enumerate() {
+        println!("\nDevice group {}: subset allocation {}", index, group.subset_allocation);
+        for member in &group.physical_devices {
+            println!("  {}: {}", member.index, member.device_name);
+        }
+        for member in group.physical_devices.iter().filter(|member| member.index < physical_device_count) {
+            print_device(backend, &layers, member.index)?;
+            printed[member.index] = true;
+        }
+    }
+
+    // Devices no group mentions.
+    for physical_device in (0..physical_device_count).filter(|&physical_device| !printed[physical_device]) {
+        print_device(backend, &layers, physical_device)?;
+    }
+
+    Ok(())
+}
+
+fn load_entry(options: &Options) -> Result<vulkan::Entry, Box<dyn Error>> {
+    let verbose = options.format == Format::Text;
+
+    if verbose {
+        println!("Loading library");
+    }
+
+    let entry = match options.loader {
+        Some(ref path) => vulkan::Entry::with_loader(path),
+        None => vulkan::Entry::new(),
+    }?;
+
+    if verbose {
+        for failure in entry.loader_failures() {
+            println!("Skipped {}", failure);
+        }
+        println!("Loaded {}", entry.loader_path().display());
     }
 
-    let instance = entry.create_instance().unwrap();
+    Ok(entry)
+}
 
-    let physical_devices = instance.enumerate_physical_devices().unwrap();
+/// Sets of instance extensions vkinfo enables on its own when the loader offers all of a set:
+/// one to report surface support without a window, one to list directly driven displays.
+const REPORTED_EXTENSIONS: &[&[&str]] = &[
+    &["VK_KHR_surface", "VK_EXT_headless_surface"],
+    &["VK_KHR_surface", "VK_KHR_display"],
+];
 
-    println!("\nFound {} devices: ", physical_devices.len());
+fn instance_builder(entry: &vulkan::Entry, options: &Options) -> Result<vulkan::InstanceBuilder, Box<dyn Error>> {
+    let own_version = vk::make_api_version(
+        0,
+        env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+        env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+        env!("CARGO_PKG_VERSION_PATCH").parse().unwrap());
 
-    for physical_device in physical_devices {
-        let features = instance.get_physical_device_features(physical_device);
-        println!("\nFeatures\n{}", serde_yaml::to_string(&features).unwrap());
-        let properties = instance.get_physical_device_properties(physical_device);
-        println!("\nProperties\n{}", serde_yaml::to_string(&properties).unwrap());
-        let queue_family_properties = instance.get_physical_device_queue_family_properties(physical_device);
-        println!("\nQueue Family Properties\n{}", serde_yaml::to_string(&queue_family_properties).unwrap());
+    let mut builder = vulkan::InstanceBuilder::new()
+        .application_name(options.application_name.clone().unwrap_or_else(|| "vkinfo".to_string()))
+        .application_version(options.application_version.unwrap_or(own_version))
+        .engine_name(options.engine_name.clone().unwrap_or_default())
+        .engine_version(options.engine_version.unwrap_or(0))
+        .api_version(options.api_version.unwrap_or(vk::API_VERSION_1_4));
+    for layer in &options.layers {
+        builder = builder.layer(layer.as_str());
+    }
+    for extension in &options.extensions {
+        builder = builder.extension(extension.as_str());
     }
 
-    instance.destroy_instance();
+    let available = entry.enumerate_instance_extension_properties(None)?;
+    let is_available = |name: &str| available.iter().any(|extension| extension.extension_name.to_string_lossy() == name);
+    let mut enabled = options.extensions.clone();
+    for &set in REPORTED_EXTENSIONS.iter().filter(|set| set.iter().all(|&name| is_available(name))) {
+        for &name in set {
+            if !enabled.iter().any(|extension| extension == name) {
+                builder = builder.extension(name);
+                enabled.push(name.to_string());
+            }
+        }
+    }
+    Ok(builder)
+}
+
+fn print(backend: &dyn Backend, options: &Options) -> Result<(), Box<dyn Error>> {
+    match options.format {
+        Format::Text => print_text(backend)?,
+        Format::Yaml => println!("{}", serde_yaml::to_string(&Report::gather(backend)?)?),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&Report::gather(backend)?)?),
+    }
+
+    Ok(())
+}
+
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    if let Some(ref path) = options.replay {
+        if options.format == Format::Text {
+            println!("Replaying {}", path.display());
+        }
+        return print(&Replay::from_file(path)?, options);
+    }
+
+    let entry = load_entry(options)?;
+    let instance = instance_builder(&entry, options)?.build(&entry)?;
+
+    if options.image_formats {
+        return image_formats::print(&instance, options);
+    }
+    if options.watch {
+        return watch::watch(&instance, options);
+    }
+
+    let mut backend = Live::new(&entry, &instance)?;
+    if let Some(ref root) = options.sysfs_root {
+        backend = backend.with_sysfs(Sysfs::new(root.as_path()));
+    }
+    print(&backend, options)
+}
+
+fn main() {
+    let options = match Options::from_env() {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("vkinfo: {}\n\n{}", err, options::USAGE);
+            process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", options::USAGE);
+        return;
+    }
+
+    if let Err(err) = run(&options) {
+        eprintln!("vkinfo: {}", err);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,255 @@
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use vkinfo::vk;
+
+/// Environment variable naming the Vulkan loader to use when `--loader` is not given.
+pub const LOADER_ENV: &str = "VKINFO_LOADER";
+
+pub const USAGE: &str = "Usage: vkinfo [OPTIONS]
+
+Options:
+    --loader <PATH>             Vulkan loader library to load (default:
+                                $VKINFO_LOADER, then the platform's usual
+                                loader names)
+    --replay <FILE>             Print a report saved with --format yaml or json
+                                instead of querying the driver
+    --format <FORMAT>           Output format: text (default), yaml or json
+    --api-version <VERSION>     Highest Vulkan version to request for the
+                                instance, e.g. 1.3 (default: the newest both
+                                vkinfo and the loader support)
+    --layer <NAME>              Enable an instance layer (repeatable)
+    --extension <NAME>          Enable an instance extension (repeatable)
+    --app-name <NAME>           Application name passed to the driver
+    --app-version <VERSION>     Application version passed to the driver
+    --engine-name <NAME>        Engine name passed to the driver
+    --engine-version <VERSION>  Engine version passed to the driver
+    --sysfs-root <PATH>         Where sysfs is mounted, for matching devices
+                                to PCI slots and DRM nodes (default: /sys)
+    --image-formats             Print vkGetPhysicalDeviceImageFormatProperties for
+                                every combination of the formats, image types,
+                                tilings and usages below instead of the report
+    --vk-format <FORMAT>        Format to sweep, e.g. R8G8B8A8_UNORM (repeatable,
+                                default: every format with features for the
+                                tiling)
+    --image-type <TYPE>         1d, 2d or 3d (repeatable, default: all)
+    --tiling <TILING>           optimal or linear (repeatable, default: both)
+    --usage <USAGE>[,<USAGE>]   Image usage to sweep, combined with commas:
+                                transfer-src, transfer-dst, sampled, storage,
+                                color-attachment, depth-stencil-attachment,
+                                transient-attachment or input-attachment
+                                (repeatable, default: sampled)
+    --watch                     Poll the memory budget (VK_EXT_memory_budget)
+                                of one device instead of printing the report;
+                                --format json prints one JSON object per line
+    --device <INDEX>            Device to watch (default: 0)
+    --interval <SECONDS>        Time between two samples (default: 1)
+    --samples <COUNT>           Stop after this many samples (default: never)
+    -h, --help                  Print this help
+
+Versions are given as MAJOR.MINOR[.PATCH] or as a raw integer.";
+
+const USAGES: &[(&str, vk::ImageUsageFlags)] = &[
+    ("transfer-src", vk::ImageUsageFlags::TransferSrcBit),
+    ("transfer-dst", vk::ImageUsageFlags::TransferDstBit),
+    ("sampled", vk::ImageUsageFlags::SampledBit),
+    ("storage", vk::ImageUsageFlags::StorageBit),
+    ("color-attachment", vk::ImageUsageFlags::ColorAttachmentBit),
+    ("depth-stencil-attachment", vk::ImageUsageFlags::DepthStencilAttachmentBit),
+    ("transient-attachment", vk::ImageUsageFlags::TransientAttachmentBit),
+    ("input-attachment", vk::ImageUsageFlags::InputAttachmentBit),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Yaml,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub loader: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub format: Format,
+    pub api_version: Option<u32>,
+    pub layers: Vec<String>,
+    pub extensions: Vec<String>,
+    pub application_name: Option<String>,
+    pub application_version: Option<u32>,
+    pub engine_name: Option<String>,
+    pub engine_version: Option<u32>,
+    pub sysfs_root: Option<PathBuf>,
+    pub image_formats: bool,
+    pub vk_formats: Vec<vk::Format>,
+    pub image_types: Vec<vk::ImageType>,
+    pub tilings: Vec<vk::ImageTiling>,
+    pub usages: Vec<vk::ImageUsageFlags>,
+    pub watch: bool,
+    pub device: usize,
+    pub interval: Duration,
+    pub samples: Option<u64>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            loader: None,
+            replay: None,
+            format: Format::default(),
+            api_version: None,
+            layers: Vec::new(),
+            extensions: Vec::new(),
+            application_name: None,
+            application_version: None,
+            engine_name: None,
+            engine_version: None,
+            sysfs_root: None,
+            image_formats: false,
+            vk_formats: Vec::new(),
+            image_types: Vec::new(),
+            tilings: Vec::new(),
+            usages: Vec::new(),
+            watch: false,
+            device: 0,
+            interval: Duration::from_secs(1),
+            samples: None,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    pub fn from_env() -> Result<Options, String> {
+        let mut options = Options::parse(env::args().skip(1))?;
+        if options.loader.is_none() {
+            options.loader = env::var_os(LOADER_ENV).filter(|path| !path.is_empty()).map(PathBuf::from);
+        }
+        Ok(options)
+    }
+
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--loader" => options.loader = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--replay" => options.replay = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--format" => options.format = match value(&arg, args.next())?.as_str() {
+                    "text" => Format::Text,
+                    "yaml" => Format::Yaml,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format '{}'", other)),
+                },
+                "--api-version" => options.api_version = Some(version(&arg, args.next())?),
+                "--layer" => options.layers.push(value(&arg, args.next())?),
+                "--extension" => options.extensions.push(value(&arg, args.next())?),
+                "--app-name" => options.application_name = Some(value(&arg, args.next())?),
+                "--app-version" => options.application_version = Some(version(&arg, args.next())?),
+                "--engine-name" => options.engine_name = Some(value(&arg, args.next())?),
+                "--engine-version" => options.engine_version = Some(version(&arg, args.next())?),
+                "--sysfs-root" => options.sysfs_root = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--image-formats" => options.image_formats = true,
+                "--vk-format" => options.vk_formats.push(vk_format(&arg, args.next())?),
+                "--image-type" => options.image_types.push(match value(&arg, args.next())?.as_str() {
+                    "1d" => vk::ImageType::Type1d,
+                    "2d" => vk::ImageType::Type2d,
+                    "3d" => vk::ImageType::Type3d,
+                    other => return Err(format!("unknown image type '{}'", other)),
+                }),
+                "--tiling" => options.tilings.push(match value(&arg, args.next())?.as_str() {
+                    "optimal" => vk::ImageTiling::Optimal,
+                    "linear" => vk::ImageTiling::Linear,
+                    other => return Err(format!("unknown tiling '{}'", other)),
+                }),
+                "--usage" => options.usages.push(usage(&arg, args.next())?),
+                "--watch" => options.watch = true,
+                "--device" => options.device = number(&arg, args.next())?,
+                "--interval" => options.interval = interval(&arg, args.next())?,
+                "--samples" => options.samples = Some(number(&arg, args.next())?),
+                "-h" | "--help" => options.help = true,
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+
+        if options.image_formats && options.replay.is_some() {
+            return Err("--image-formats needs a driver and cannot be used with --replay".to_string());
+        }
+        if options.watch && options.replay.is_some() {
+            return Err("--watch needs a driver and cannot be used with --replay".to_string());
+        }
+        if options.watch && options.image_formats {
+            return Err("--watch cannot be combined with --image-formats".to_string());
+        }
+        if options.watch && options.format == Format::Yaml {
+            return Err("--watch prints text or json".to_string());
+        }
+
+        Ok(options)
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("missing value for '{}'", flag))
+}
+
+fn number<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = self::value(flag, value)?;
+    value.parse().map_err(|_| format!("invalid number '{}' for '{}'", value, flag))
+}
+
+/// Parses a positive number of seconds, which may have a fractional part. Zero would poll
+/// the driver in a busy loop.
+fn interval(flag: &str, value: Option<String>) -> Result<Duration, String> {
+    let value = self::value(flag, value)?;
+    value.parse().ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|interval| !interval.is_zero())
+        .ok_or_else(|| format!("invalid interval '{}' for '{}'", value, flag))
+}
+
+/// Parses `MAJOR.MINOR[.PATCH]` into a Vulkan version number, or takes a plain integer as is.
+fn version(flag: &str, value: Option<String>) -> Result<u32, String> {
+    let value = self::value(flag, value)?;
+    let invalid = || format!("invalid version '{}' for '{}'", value, flag);
+
+    if !value.contains('.') {
+        return value.parse().map_err(|_| invalid());
+    }
+
+    let parts = value.split('.').map(|part| part.parse::<u32>()).collect::<Result<Vec<u32>, _>>().map_err(|_| invalid())?;
+    match parts[..] {
+        [major, minor] if major <= 0x7f && minor <= 0x3ff => Ok(vk::make_api_version(0, major, minor, 0)),
+        [major, minor, patch] if major <= 0x7f && minor <= 0x3ff && patch <= 0xfff => Ok(vk::make_api_version(0, major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Looks up a format by its Vulkan name, with or without the `VK_FORMAT_` prefix and in any case.
+fn vk_format(flag: &str, value: Option<String>) -> Result<vk::Format, String> {
+    let value = self::value(flag, value)?;
+    let upper = value.to_uppercase();
+    let name = upper.trim_start_matches("VK_FORMAT_").replace('_', "");
+
+    vk::Format::VALUES.iter()
+        .find(|format| format!("{:?}", format).to_uppercase() == name)
+        .cloned()
+        .ok_or_else(|| format!("unknown format '{}'", value))
+}
+
+fn usage(flag: &str, value: Option<String>) -> Result<vk::ImageUsageFlags, String> {
+    let value = self::value(flag, value)?;
+
+    let mut usage = vk::ImageUsageFlags::empty();
+    for name in value.split(',') {
+        match USAGES.iter().find(|&&(known, _)| known == name) {
+            Some(&(_, flag)) => usage |= flag,
+            None => return Err(format!("unknown usage '{}'", name)),
+        }
+    }
+    Ok(usage)
+}
@@ -0,0 +1,362 @@
+use backend::Backend;
+use std::collections::BTreeMap;
+use sysfs::DeviceLocation;
+use vk;
+use vulkan::{QueueFamilyProperties2, Result, VideoCapabilities, VideoProfile};
+
+/// Sparse image properties are gathered for 2D, optimally tiled, sampled images.
+pub const SPARSE_IMAGE_TYPE: vk::ImageType = vk::ImageType::Type2d;
+pub const SPARSE_USAGE: vk::ImageUsageFlags = vk::ImageUsageFlags::SampledBit;
+pub const SPARSE_TILING: vk::ImageTiling = vk::ImageTiling::Optimal;
+
+/// External memory support is gathered for transfer buffers and for 2D, optimally tiled,
+/// sampled RGBA8 images.
+pub const EXTERNAL_BUFFER_USAGE: vk::BufferUsageFlags = vk::BufferUsageFlags::TransferSrcBit.union(vk::BufferUsageFlags::TransferDstBit);
+pub const EXTERNAL_IMAGE_FORMAT: vk::Format = vk::Format::R8g8b8a8Unorm;
+pub const EXTERNAL_IMAGE_TYPE: vk::ImageType = vk::ImageType::Type2d;
+pub const EXTERNAL_IMAGE_TILING: vk::ImageTiling = vk::ImageTiling::Optimal;
+pub const EXTERNAL_IMAGE_USAGE: vk::ImageUsageFlags = vk::ImageUsageFlags::SampledBit.union(vk::ImageUsageFlags::TransferSrcBit).union(vk::ImageUsageFlags::TransferDstBit);
+
+const SAMPLE_COUNTS: [vk::SampleCountFlags; 7] = [
+    vk::SampleCountFlags::Count1Bit,
+    vk::SampleCountFlags::Count2Bit,
+    vk::SampleCountFlags::Count4Bit,
+    vk::SampleCountFlags::Count8Bit,
+    vk::SampleCountFlags::Count16Bit,
+    vk::SampleCountFlags::Count32Bit,
+    vk::SampleCountFlags::Count64Bit,
+];
+
+/// Everything vkinfo knows about an instance, in a form that can be saved and replayed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Report {
+    pub instance_version: vk::Version,
+    pub extensions: Vec<vk::ExtensionProperties>,
+    pub layers: Vec<Layer>,
+    pub physical_devices: Vec<PhysicalDevice>,
+    pub device_groups: Vec<DeviceGroup>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layer {
+    pub properties: vk::LayerProperties,
+    pub extensions: Vec<vk::ExtensionProperties>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceGroup {
+    pub physical_devices: Vec<GroupMember>,
+    /// Whether memory can be allocated on a subset of the devices only.
+    pub subset_allocation: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupMember {
+    /// Index into `Report::physical_devices`.
+    pub index: usize,
+    pub device_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDevice {
+    pub properties: vk::PhysicalDeviceProperties,
+    pub features: vk::PhysicalDeviceFeatures,
+    /// The features and properties of newer Vulkan versions are missing for versions the
+    /// device or the instance does not support.
+    pub vulkan_11_features: Option<vk::PhysicalDeviceVulkan11Features>,
+    pub vulkan_12_features: Option<vk::PhysicalDeviceVulkan12Features>,
+    pub vulkan_13_features: Option<vk::PhysicalDeviceVulkan13Features>,
+    pub vulkan_14_features: Option<vk::PhysicalDeviceVulkan14Features>,
+    pub driver_properties: Option<vk::PhysicalDeviceDriverProperties>,
+    pub pci_bus_info: Option<vk::PhysicalDevicePciBusInfoPropertiesExt>,
+    pub drm_properties: Option<vk::PhysicalDeviceDrmPropertiesExt>,
+    pub id_properties: Option<vk::PhysicalDeviceIdProperties>,
+    pub location: Option<DeviceLocation>,
+    pub vulkan_11_properties: Option<vk::PhysicalDeviceVulkan11Properties>,
+    pub vulkan_12_properties: Option<vk::PhysicalDeviceVulkan12Properties>,
+    pub vulkan_13_properties: Option<vk::PhysicalDeviceVulkan13Properties>,
+    pub vulkan_14_properties: Option<vk::PhysicalDeviceVulkan14Properties>,
+    pub copy_src_layouts: Vec<vk::ImageLayout>,
+    pub copy_dst_layouts: Vec<vk::ImageLayout>,
+    pub queue_families: Vec<QueueFamilyProperties2>,
+    /// What the device offers for a headless surface, if the instance could create one.
+    pub surface: Option<SurfaceSupport>,
+    /// What VK_KHR_display reports, if the instance has it enabled.
+    pub displays: Option<DisplaySupport>,
+    /// The standard video profiles the device supports, if it has VK_KHR_video_queue.
+    pub video_profiles: Option<Vec<VideoProfileSupport>>,
+    /// Which external memory, semaphore and fence handle types the device can export and
+    /// import, from Vulkan 1.1 on.
+    pub external_handles: Option<ExternalHandleSupport>,
+    /// Tools such as validation or capture layers active on the device, from Vulkan 1.3 or
+    /// VK_EXT_tooling_info.
+    pub tools: Option<Vec<vk::PhysicalDeviceToolProperties>>,
+    /// Why the driver could not answer for the sections above that are missing, by field
+    /// name such as `surface`.
+    pub errors: BTreeMap<String, String>,
+    pub memory_types: Vec<vk::MemoryType>,
+    pub memory_heaps: Vec<vk::MemoryHeap>,
+    /// Core formats with any support, see `supported_formats`.
+    pub formats: Vec<FormatSupport>,
+    /// See `sparse_formats`.
+    pub sparse_formats: Vec<SparseFormatSupport>,
+    pub extensions: Vec<vk::ExtensionProperties>,
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfaceSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKhr,
+    pub formats: Vec<vk::SurfaceFormatKhr>,
+    pub present_modes: Vec<vk::PresentModeKhr>,
+    /// Whether each queue family can present to the surface, in queue family order.
+    pub queue_family_support: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySupport {
+    pub displays: Vec<Display>,
+    /// In plane index order.
+    pub planes: Vec<DisplayPlane>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Display {
+    pub name: Option<String>,
+    /// In millimeters.
+    pub physical_dimensions: vk::Extend2D,
+    pub physical_resolution: vk::Extend2D,
+    pub supported_transforms: vk::SurfaceTransformFlagsKhr,
+    pub plane_reorder_possible: bool,
+    pub persistent_content: bool,
+    pub modes: Vec<DisplayMode>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayMode {
+    pub parameters: vk::DisplayModeParametersKhr,
+    /// The capabilities of each plane that can be shown on the mode's display.
+    pub planes: Vec<PlaneCapabilities>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaneCapabilities {
+    pub plane: u32,
+    pub capabilities: vk::DisplayPlaneCapabilitiesKhr,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayPlane {
+    /// Index into `DisplaySupport::displays` of the display the plane is in use on.
+    pub current_display: Option<usize>,
+    pub current_stack_index: u32,
+    /// Indices into `DisplaySupport::displays`.
+    pub supported_displays: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoProfileSupport {
+    pub profile: VideoProfile,
+    pub capabilities: VideoCapabilities,
+    /// Formats of decoded pictures or of pictures to encode.
+    pub picture_formats: Vec<vk::Format>,
+    /// Formats of reference pictures.
+    pub dpb_formats: Vec<vk::Format>,
+    /// Queue families that support the profile's codec operation.
+    pub queue_families: Vec<u32>,
+}
+
+/// One entry per handle type vkinfo knows, for each kind of object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalHandleSupport {
+    /// For buffers with `EXTERNAL_BUFFER_USAGE`.
+    pub buffers: Vec<ExternalMemorySupport>,
+    /// For images with the `EXTERNAL_IMAGE_*` parameters; no features if such images are
+    /// not supported at all.
+    pub images: Vec<ExternalMemorySupport>,
+    pub semaphores: Vec<ExternalSemaphoreSupport>,
+    pub fences: Vec<ExternalFenceSupport>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalMemorySupport {
+    pub handle_type: vk::ExternalMemoryHandleTypeFlags,
+    /// `None` if the query failed.
+    pub properties: Option<vk::ExternalMemoryProperties>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalSemaphoreSupport {
+    pub handle_type: vk::ExternalSemaphoreHandleTypeFlags,
+    /// `None` if the query failed.
+    pub properties: Option<vk::ExternalSemaphoreProperties>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalFenceSupport {
+    pub handle_type: vk::ExternalFenceHandleTypeFlags,
+    /// `None` if the query failed.
+    pub properties: Option<vk::ExternalFenceProperties>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatSupport {
+    pub format: vk::Format,
+    pub properties: vk::FormatProperties,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SparseFormatSupport {
+    pub format: vk::Format,
+    pub samples: vk::SampleCountFlags,
+    pub properties: Vec<vk::SparseImageFormatProperties>,
+}
+
+impl Report {
+    pub fn gather(backend: &dyn Backend) -> Result<Report> {
+        let instance_version = backend.enumerate_instance_version()?;
+        let extensions = backend.enumerate_instance_extension_properties(None)?;
+
+        let mut layers = Vec::new();
+        for properties in backend.enumerate_instance_layer_properties()? {
+            let extensions = backend.enumerate_instance_extension_properties(Some(&properties.layer_name.to_string_lossy()))?;
+            layers.push(Layer {
+                properties,
+                extensions,
+            });
+        }
+
+        let mut physical_devices = Vec::new();
+        for index in 0..backend.physical_device_count()? {
+            let mut layers = Vec::new();
+            for properties in backend.enumerate_device_layer_properties(index)? {
+                let extensions = backend.enumerate_device_extension_properties(index, Some(&properties.layer_name.to_string_lossy()))?;
+                layers.push(Layer {
+                    properties,
+                    extensions,
+                });
+            }
+
+            let memory_properties = backend.get_physical_device_memory_properties(index);
+            let formats = supported_formats(backend, index);
+            let sparse_formats = sparse_formats(backend, index, &formats);
+            let features = backend.get_physical_device_features2(index);
+            let properties = backend.get_physical_device_properties2(index);
+            let mut errors = BTreeMap::new();
+            let surface = section(&mut errors, "surface", backend.get_physical_device_surface_support(index));
+            let displays = section(&mut errors, "displays", backend.get_physical_device_display_support(index));
+            let video_profiles = section(&mut errors, "video_profiles", backend.get_physical_device_video_profiles(index));
+            let tools = section(&mut errors, "tools", backend.get_physical_device_tool_properties(index));
+
+            physical_devices.push(PhysicalDevice {
+                properties: properties.properties,
+                features: features.features,
+                vulkan_11_features: features.vulkan_11,
+                vulkan_12_features: features.vulkan_12,
+                vulkan_13_features: features.vulkan_13,
+                vulkan_14_features: features.vulkan_14,
+                driver_properties: properties.driver,
+                pci_bus_info: properties.pci_bus_info,
+                drm_properties: properties.drm,
+                id_properties: properties.id,
+                location: backend.get_physical_device_location(index),
+                vulkan_11_properties: properties.vulkan_11,
+                vulkan_12_properties: properties.vulkan_12,
+                vulkan_13_properties: properties.vulkan_13,
+                vulkan_14_properties: properties.vulkan_14,
+                copy_src_layouts: properties.copy_src_layouts,
+                copy_dst_layouts: properties.copy_dst_layouts,
+                queue_families: backend.get_physical_device_queue_family_properties2(index),
+                surface,
+                displays,
+                video_profiles,
+                external_handles: backend.get_physical_device_external_handle_support(index)?,
+                tools,
+                errors,
+                memory_types: memory_properties.memory_types().to_vec(),
+                memory_heaps: memory_properties.memory_heaps().to_vec(),
+                formats,
+                sparse_formats,
+                extensions: backend.enumerate_device_extension_properties(index, None)?,
+                layers,
+            });
+        }
+
+        Ok(Report {
+            instance_version,
+            extensions,
+            layers,
+            physical_devices,
+            device_groups: backend.enumerate_physical_device_groups()?,
+        })
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.properties.layer_name.to_string_lossy() == name)
+    }
+}
+
+impl PhysicalDevice {
+    pub fn format(&self, format: vk::Format) -> Option<&vk::FormatProperties> {
+        self.formats.iter().find(|support| support.format == format).map(|support| &support.properties)
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.properties.layer_name.to_string_lossy() == name)
+    }
+}
+
+/// The section `result` holds, or `None` with the error recorded in `errors` under `name`.
+fn section<T>(errors: &mut BTreeMap<String, String>, name: &str, result: Result<Option<T>>) -> Option<T> {
+    result.unwrap_or_else(|err| {
+        errors.insert(name.to_string(), err.to_string());
+        None
+    })
+}
+
+/// Every core format `physical_device` supports in some way, with its features.
+pub fn supported_formats(backend: &dyn Backend, physical_device: usize) -> Vec<FormatSupport> {
+    vk::Format::VALUES.iter()
+        .filter(|&&format| format != vk::Format::Undefined)
+        .map(|&format| FormatSupport { format, properties: backend.get_physical_device_format_properties(physical_device, format) })
+        .filter(|support| {
+            let properties = &support.properties;
+            !(properties.linear_tiling_features | properties.optimal_tiling_features | properties.buffer_features).is_empty()
+        })
+        .collect()
+}
+
+/// Sparse block shapes for each format in `formats` with optimal tiling features and each
+/// sample count, leaving out pairs the device has no sparse support for.
+pub fn sparse_formats(backend: &dyn Backend, physical_device: usize, formats: &[FormatSupport]) -> Vec<SparseFormatSupport> {
+    let mut sparse_formats = Vec::new();
+    for support in formats.iter().filter(|support| !support.properties.optimal_tiling_features.is_empty()) {
+        for &samples in &SAMPLE_COUNTS {
+            let properties = backend.get_physical_device_sparse_image_format_properties(physical_device, support.format, SPARSE_IMAGE_TYPE, samples, SPARSE_USAGE, SPARSE_TILING);
+            if !properties.is_empty() {
+                sparse_formats.push(SparseFormatSupport {
+                    format: support.format,
+                    samples,
+                    properties,
+                });
+            }
+        }
+    }
+    sparse_formats
+}
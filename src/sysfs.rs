@@ -0,0 +1,105 @@
+//! Matching physical devices to PCI slots and DRM nodes through Linux's sysfs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use vk;
+
+/// Where a physical device sits in the system. Anything sysfs does not know about is `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceLocation {
+    /// Domain, bus, device and function, e.g. `0000:01:00.0`.
+    pub pci_address: Option<String>,
+    /// The kernel driver bound to the PCI device, e.g. `amdgpu`.
+    pub kernel_driver: Option<String>,
+    pub primary_node: Option<String>,
+    pub render_node: Option<String>,
+}
+
+/// A sysfs tree, normally mounted at `/sys`.
+#[derive(Debug, Clone)]
+pub struct Sysfs {
+    root: PathBuf,
+}
+
+impl Default for Sysfs {
+    fn default() -> Sysfs {
+        Sysfs::new("/sys")
+    }
+}
+
+impl Sysfs {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Sysfs {
+        Sysfs {
+            root: root.into(),
+        }
+    }
+
+    /// Combines what VK_EXT_pci_bus_info and VK_EXT_physical_device_drm report with sysfs.
+    /// Either one is enough to find the other half; without both there is nothing to go by.
+    pub fn locate(&self, pci_bus_info: Option<&vk::PhysicalDevicePciBusInfoPropertiesExt>, drm: Option<&vk::PhysicalDeviceDrmPropertiesExt>) -> Option<DeviceLocation> {
+        if pci_bus_info.is_none() && drm.is_none() {
+            return None;
+        }
+
+        let primary_node = drm.filter(|drm| drm.has_primary != 0).and_then(|drm| self.drm_node(drm.primary_major, drm.primary_minor));
+        let render_node = drm.filter(|drm| drm.has_render != 0).and_then(|drm| self.drm_node(drm.render_major, drm.render_minor));
+
+        let pci_address = match pci_bus_info {
+            Some(info) => Some(format!("{:04x}:{:02x}:{:02x}.{:x}", info.pci_domain, info.pci_bus, info.pci_device, info.pci_function)),
+            None => primary_node.iter().chain(render_node.iter())
+                .filter_map(|node| link_name(&self.root.join("class/drm").join(node).join("device")))
+                .next(),
+        };
+
+        let mut location = DeviceLocation {
+            kernel_driver: pci_address.as_ref().and_then(|address| link_name(&self.pci_device(address).join("driver"))),
+            pci_address,
+            primary_node: primary_node.map(|node| format!("/dev/dri/{}", node)),
+            render_node: render_node.map(|node| format!("/dev/dri/{}", node)),
+        };
+
+        // Without VK_EXT_physical_device_drm, the nodes are those below the PCI device.
+        if drm.is_none() {
+            if let Some(ref address) = location.pci_address {
+                for node in read_dir_names(&self.pci_device(address).join("drm")) {
+                    if node.starts_with("card") {
+                        location.primary_node = Some(format!("/dev/dri/{}", node));
+                    } else if node.starts_with("renderD") {
+                        location.render_node = Some(format!("/dev/dri/{}", node));
+                    }
+                }
+            }
+        }
+
+        Some(location)
+    }
+
+    fn pci_device(&self, address: &str) -> PathBuf {
+        self.root.join("bus/pci/devices").join(address)
+    }
+
+    /// Name of the node in `class/drm` whose `dev` file holds `major:minor`.
+    fn drm_node(&self, major: i64, minor: i64) -> Option<String> {
+        let dev = format!("{}:{}", major, minor);
+        let class = self.root.join("class/drm");
+        read_dir_names(&class).into_iter()
+            .find(|node| fs::read_to_string(class.join(node).join("dev")).is_ok_and(|contents| contents.trim() == dev))
+    }
+}
+
+/// The last component of where the symlink at `path` points.
+fn link_name(path: &Path) -> Option<String> {
+    let target = fs::read_link(path).ok()?;
+    target.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Entries of `path` in sorted order, or none if it cannot be read.
+fn read_dir_names(path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(path)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.file_name().to_string_lossy().into_owned()).collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
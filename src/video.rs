@@ -0,0 +1,128 @@
+//! The standard profiles of the codecs Vulkan Video supports, for walking through what a
+//! device can decode and encode.
+
+use vk;
+use vk::VideoCodecOperationFlagsKhr as Operation;
+use vulkan::VideoProfile;
+
+/// Every codec operation with a name, in the order they are reported.
+pub const OPERATIONS: &[(Operation, &str)] = &[
+    (Operation::DecodeH264Bit, "H.264 decode"),
+    (Operation::DecodeH265Bit, "H.265 decode"),
+    (Operation::DecodeAv1Bit, "AV1 decode"),
+    (Operation::DecodeVp9Bit, "VP9 decode"),
+    (Operation::EncodeH264Bit, "H.264 encode"),
+    (Operation::EncodeH265Bit, "H.265 encode"),
+    (Operation::EncodeAv1Bit, "AV1 encode"),
+];
+
+/// `StdVideoH264ProfileIdc` values.
+const H264_PROFILES: &[(i32, &str)] = &[
+    (66, "Baseline"),
+    (77, "Main"),
+    (100, "High"),
+    (244, "High 4:4:4 Predictive"),
+];
+
+/// `StdVideoH265ProfileIdc` values.
+const H265_PROFILES: &[(i32, &str)] = &[
+    (1, "Main"),
+    (2, "Main 10"),
+    (3, "Main Still Picture"),
+    (4, "Format Range Extensions"),
+    (9, "SCC Extensions"),
+];
+
+/// `StdVideoAV1Profile` values.
+const AV1_PROFILES: &[(i32, &str)] = &[
+    (0, "Main"),
+    (1, "High"),
+    (2, "Professional"),
+];
+
+/// `StdVideoVP9Profile` values.
+const VP9_PROFILES: &[(i32, &str)] = &[
+    (0, "Profile 0"),
+    (1, "Profile 1"),
+    (2, "Profile 2"),
+    (3, "Profile 3"),
+];
+
+const CHROMA_SUBSAMPLINGS: &[vk::VideoChromaSubsamplingFlagsKhr] = &[
+    vk::VideoChromaSubsamplingFlagsKhr::Type420Bit,
+    vk::VideoChromaSubsamplingFlagsKhr::Type422Bit,
+    vk::VideoChromaSubsamplingFlagsKhr::Type444Bit,
+    vk::VideoChromaSubsamplingFlagsKhr::MonochromeBit,
+];
+
+const BIT_DEPTHS: &[vk::VideoComponentBitDepthFlagsKhr] = &[
+    vk::VideoComponentBitDepthFlagsKhr::Depth8Bit,
+    vk::VideoComponentBitDepthFlagsKhr::Depth10Bit,
+    vk::VideoComponentBitDepthFlagsKhr::Depth12Bit,
+];
+
+fn std_profiles(operation: Operation) -> &'static [(i32, &'static str)] {
+    match operation {
+        Operation::DecodeH264Bit | Operation::EncodeH264Bit => H264_PROFILES,
+        Operation::DecodeH265Bit | Operation::EncodeH265Bit => H265_PROFILES,
+        Operation::DecodeAv1Bit | Operation::EncodeAv1Bit => AV1_PROFILES,
+        Operation::DecodeVp9Bit => VP9_PROFILES,
+        _ => &[],
+    }
+}
+
+/// Every combination of standard profile, chroma subsampling and bit depth for the codec
+/// operations in `operations`. Luma and chroma always share a bit depth.
+pub fn standard_profiles(operations: Operation) -> Vec<VideoProfile> {
+    let mut profiles = Vec::new();
+    for &(operation, _) in OPERATIONS.iter().filter(|&&(operation, _)| operations.contains(operation)) {
+        for &(std_profile, _) in std_profiles(operation) {
+            for &chroma_subsampling in CHROMA_SUBSAMPLINGS {
+                for &bit_depth in BIT_DEPTHS {
+                    profiles.push(VideoProfile {
+                        operation,
+                        std_profile,
+                        chroma_subsampling,
+                        luma_bit_depth: bit_depth,
+                        chroma_bit_depth: if chroma_subsampling == vk::VideoChromaSubsamplingFlagsKhr::MonochromeBit {
+                            vk::VideoComponentBitDepthFlagsKhr::empty()
+                        } else {
+                            bit_depth
+                        },
+                    });
+                }
+            }
+        }
+    }
+    profiles
+}
+
+/// E.g. `H.264 decode`.
+pub fn operation_name(operation: Operation) -> Option<&'static str> {
+    OPERATIONS.iter().find(|&&(known, _)| known == operation).map(|&(_, name)| name)
+}
+
+/// E.g. `High` for H.264's profile 100.
+pub fn profile_name(operation: Operation, std_profile: i32) -> Option<&'static str> {
+    std_profiles(operation).iter().find(|&&(known, _)| known == std_profile).map(|&(_, name)| name)
+}
+
+/// E.g. `4:2:0`.
+pub fn chroma_subsampling_name(chroma_subsampling: vk::VideoChromaSubsamplingFlagsKhr) -> Option<&'static str> {
+    match chroma_subsampling {
+        vk::VideoChromaSubsamplingFlagsKhr::MonochromeBit => Some("4:0:0"),
+        vk::VideoChromaSubsamplingFlagsKhr::Type420Bit => Some("4:2:0"),
+        vk::VideoChromaSubsamplingFlagsKhr::Type422Bit => Some("4:2:2"),
+        vk::VideoChromaSubsamplingFlagsKhr::Type444Bit => Some("4:4:4"),
+        _ => None,
+    }
+}
+
+pub fn bit_depth(bit_depth: vk::VideoComponentBitDepthFlagsKhr) -> Option<u32> {
+    match bit_depth {
+        vk::VideoComponentBitDepthFlagsKhr::Depth8Bit => Some(8),
+        vk::VideoComponentBitDepthFlagsKhr::Depth10Bit => Some(10),
+        vk::VideoComponentBitDepthFlagsKhr::Depth12Bit => Some(12),
+        _ => None,
+    }
+}
@@ -1,6 +1,11 @@
+#![allow(non_upper_case_globals)]
+
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
-use std::ffi::CStr;
+use std::cmp;
 use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
 use std::result;
@@ -8,54 +13,807 @@ use std::result;
 const MAX_EXTENSION_NAME_SIZE: usize = 256;
 const MAX_DESCRIPTION_SIZE: usize = 256;
 const MAX_PHYSICAL_DEVICE_NAME_SIZE: usize = 256;
+pub const MAX_MEMORY_TYPES: usize = 32;
+pub const MAX_MEMORY_HEAPS: usize = 16;
 const UUID_SIZE: usize = 16;
+const LUID_SIZE: usize = 8;
+pub const MAX_DEVICE_GROUP_SIZE: usize = 32;
+const MAX_GLOBAL_PRIORITY_SIZE: usize = 16;
+const MAX_DRIVER_NAME_SIZE: usize = 256;
+const MAX_DRIVER_INFO_SIZE: usize = 256;
+
+/// Declares a fixed-size, nul-terminated character array as used by Vulkan for names and
+/// descriptions. It is serialized as a plain string.
+macro_rules! c_string {
+    ($name:ident, $size:expr) => {
+        pub struct $name(pub [c_char; $size]);
+
+        impl $name {
+            pub fn as_ptr(&self) -> *const c_char {
+                self.0.as_ptr()
+            }
+
+            pub fn to_string_lossy(&self) -> String {
+                let bytes: Vec<u8> = self.0.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(s: &'a str) -> $name {
+                let mut chars = [0; $size];
+                for (dst, src) in chars.iter_mut().zip(s.bytes().take($size - 1)) {
+                    *dst = src as c_char;
+                }
+                $name(chars)
+            }
+        }
+
+        impl Clone for $name {
+            fn clone(&self) -> $name {
+                $name(self.0)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name([0; $size])
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S: Serializer {
+                serializer.serialize_str(&self.to_string_lossy())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> result::Result<$name, D::Error> where D: Deserializer<'de> {
+                let s = String::deserialize(deserializer)?;
+                Ok($name::from(s.as_str()))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.to_string_lossy())
+            }
+        }
+    }
+}
+
+/// Declares a Vulkan enum. The driver exchanges these as plain `i32` values (see `Raw`), and
+/// values the enum does not list decode to `Unknown` instead of being undefined behaviour.
+macro_rules! vk_enum {
+    (pub enum $name:ident { $($variant:ident = $value:literal,)* }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum $name {
+            $($variant,)*
+            Unknown(i32),
+        }
+
+        impl RawEnum for $name {
+            fn from_raw(raw: i32) -> $name {
+                match raw {
+                    $($value => $name::$variant,)*
+                    raw => $name::Unknown(raw),
+                }
+            }
+
+            fn as_raw(self) -> i32 {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(raw) => raw,
+                }
+            }
+        }
+
+        impl $name {
+            /// Every value the enum names, in declaration order.
+            pub const VALUES: &'static [$name] = &[$($name::$variant,)*];
+        }
+    }
+}
+
+/// Implements `Structure`, and a `Default` that is all zeroes apart from `s_type`, for a
+/// struct starting with `s_type` and `p_next`.
+macro_rules! vk_structure {
+    ($name:ident, $structure_type:ident) => {
+        impl Default for $name {
+            fn default() -> $name {
+                let mut value: $name = unsafe { mem::zeroed() };
+                value.s_type = StructureType::$structure_type.into();
+                value
+            }
+        }
+
+        unsafe impl Structure for $name {}
+    }
+}
+
+/// Conversion between a Vulkan enum and the `i32` the driver reads and writes.
+pub trait RawEnum: Copy {
+    fn from_raw(raw: i32) -> Self;
+    fn as_raw(self) -> i32;
+}
+
+/// An enum value as stored in Vulkan structs and returned by Vulkan commands. It is only
+/// decoded on access, so any value the driver writes is valid.
+#[repr(transparent)]
+pub struct Raw<T> {
+    raw: i32,
+    marker: PhantomData<T>,
+}
+
+impl<T: RawEnum> Raw<T> {
+    pub fn new(value: T) -> Raw<T> {
+        Raw::from_raw(value.as_raw())
+    }
+
+    pub fn from_raw(raw: i32) -> Raw<T> {
+        Raw { raw, marker: PhantomData }
+    }
+
+    pub fn get(self) -> T {
+        T::from_raw(self.raw)
+    }
+
+    pub fn as_raw(self) -> i32 {
+        self.raw
+    }
+}
+
+impl<T: RawEnum> From<T> for Raw<T> {
+    fn from(value: T) -> Raw<T> {
+        Raw::new(value)
+    }
+}
+
+impl<T> Clone for Raw<T> {
+    fn clone(&self) -> Raw<T> {
+        *self
+    }
+}
+
+impl<T> Copy for Raw<T> {}
+
+impl<T> Default for Raw<T> {
+    fn default() -> Raw<T> {
+        Raw { raw: 0, marker: PhantomData }
+    }
+}
+
+impl<T: RawEnum + PartialEq> PartialEq<T> for Raw<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.get() == *other
+    }
+}
+
+impl<T: RawEnum + fmt::Debug> fmt::Debug for Raw<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T: RawEnum + Serialize> Serialize for Raw<T> {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S: Serializer {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<'de, T: RawEnum + Deserialize<'de>> Deserialize<'de> for Raw<T> {
+    fn deserialize<D>(deserializer: D) -> result::Result<Raw<T>, D::Error> where D: Deserializer<'de> {
+        T::deserialize(deserializer).map(Raw::new)
+    }
+}
 
 pub type Bool32 = u32;
 pub type DeviceSize = u64;
 
+pub const API_VERSION_1_0: u32 = make_api_version(0, 1, 0, 0);
+pub const API_VERSION_1_1: u32 = make_api_version(0, 1, 1, 0);
+pub const API_VERSION_1_2: u32 = make_api_version(0, 1, 2, 0);
+pub const API_VERSION_1_3: u32 = make_api_version(0, 1, 3, 0);
+pub const API_VERSION_1_4: u32 = make_api_version(0, 1, 4, 0);
+
+pub const fn make_api_version(variant: u32, major: u32, minor: u32, patch: u32) -> u32 {
+    (variant << 29) | (major << 22) | (minor << 12) | patch
+}
+
+pub fn api_version_variant(version: u32) -> u32 {
+    version >> 29
+}
+
+pub fn api_version_major(version: u32) -> u32 {
+    (version >> 22) & 0x7f
+}
+
+pub fn api_version_minor(version: u32) -> u32 {
+    (version >> 12) & 0x3ff
+}
+
+pub fn api_version_patch(version: u32) -> u32 {
+    version & 0xfff
+}
+
+/// A version number packed by `make_api_version`, split into its parts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Version {
+    pub variant: u32,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl From<u32> for Version {
+    fn from(version: u32) -> Version {
+        Version {
+            variant: api_version_variant(version),
+            major: api_version_major(version),
+            minor: api_version_minor(version),
+            patch: api_version_patch(version),
+        }
+    }
+}
+
+impl From<Version> for u32 {
+    fn from(version: Version) -> u32 {
+        make_api_version(version.variant, version.major, version.minor, version.patch)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.variant != 0 {
+            write!(f, " (variant {})", self.variant)?;
+        }
+        Ok(())
+    }
+}
+
 pub type Instance = *const c_void;
 pub type PhysicalDevice = *const c_void;
+pub type Device = *const c_void;
+/// Non-dispatchable handles are 64 bit on every platform.
+pub type SurfaceKhr = u64;
+pub type DisplayKhr = u64;
+pub type DisplayModeKhr = u64;
+pub const NULL_HANDLE: u64 = 0;
+
+vk_enum! {
+    pub enum Result {
+        Success = 0,
+        NotReady = 1,
+        Timeout = 2,
+        EventSet = 3,
+        EventReset = 4,
+        Incomplete = 5,
+        ErrorOutOfHostMemory = -1,
+        ErrorOutOfDeviceMemory = -2,
+        ErrorInitializationFailed = -3,
+        ErrorDeviceLost = -4,
+        ErrorMemoryMapFailed = -5,
+        ErrorLayerNotPresent = -6,
+        ErrorExtensionNotPresent = -7,
+        ErrorFeatureNotPresent = -8,
+        ErrorIncompatibleDriver = -9,
+        ErrorTooManyObjects = -10,
+        ErrorFormatNotSupported = -11,
+        ErrorFragmentedPool = -12,
+        ErrorUnknown = -13,
+        ErrorSurfaceLostKhr = -1000000000,
+        ErrorNativeWindowInUseKhr = -1000000001,
+        SuboptimalKhr = 1000001003,
+        ErrorOutOfDateKhr = -1000001004,
+        ErrorIncompatibleDisplayKhr = -1000003001,
+        ErrorValidationFailedExt = -1000011001,
+        ErrorInvalidShaderNv = -1000012000,
+        ErrorImageUsageNotSupportedKhr = -1000023000,
+        ErrorVideoPictureLayoutNotSupportedKhr = -1000023001,
+        ErrorVideoProfileOperationNotSupportedKhr = -1000023002,
+        ErrorVideoProfileFormatNotSupportedKhr = -1000023003,
+        ErrorVideoProfileCodecNotSupportedKhr = -1000023004,
+        ErrorVideoStdVersionNotSupportedKhr = -1000023005,
+        ErrorOutOfPoolMemoryKhr = -1000069000,
+        ErrorInvalidExternalHandleKhr = -1000072003,
+    }
+}
+
+vk_enum! {
+    pub enum StructureType {
+        ApplicationInfo = 0,
+        InstanceCreateInfo = 1,
+        DeviceQueueCreateInfo = 2,
+        DeviceCreateInfo = 3,
+        PhysicalDeviceVulkan11Features = 49,
+        PhysicalDeviceVulkan11Properties = 50,
+        PhysicalDeviceVulkan12Features = 51,
+        PhysicalDeviceVulkan12Properties = 52,
+        PhysicalDeviceVulkan13Features = 53,
+        PhysicalDeviceVulkan13Properties = 54,
+        PhysicalDeviceVulkan14Features = 55,
+        PhysicalDeviceVulkan14Properties = 56,
+        VideoProfileInfoKhr = 1000023000,
+        VideoCapabilitiesKhr = 1000023001,
+        QueueFamilyVideoPropertiesKhr = 1000023012,
+        VideoProfileListInfoKhr = 1000023013,
+        PhysicalDeviceVideoFormatInfoKhr = 1000023014,
+        VideoFormatPropertiesKhr = 1000023015,
+        VideoDecodeCapabilitiesKhr = 1000024001,
+        VideoEncodeH264CapabilitiesKhr = 1000038000,
+        VideoEncodeH264ProfileInfoKhr = 1000038007,
+        VideoEncodeH265CapabilitiesKhr = 1000039000,
+        VideoEncodeH265ProfileInfoKhr = 1000039007,
+        VideoDecodeH264CapabilitiesKhr = 1000040000,
+        VideoDecodeH264ProfileInfoKhr = 1000040003,
+        PhysicalDeviceMultiviewFeatures = 1000053001,
+        PhysicalDeviceMultiviewProperties = 1000053002,
+        PhysicalDeviceFeatures2 = 1000059000,
+        PhysicalDeviceProperties2 = 1000059001,
+        ImageFormatProperties2 = 1000059003,
+        PhysicalDeviceImageFormatInfo2 = 1000059004,
+        QueueFamilyProperties2 = 1000059005,
+        PhysicalDeviceMemoryProperties2 = 1000059006,
+        PhysicalDeviceShaderDrawParametersFeatures = 1000063000,
+        PhysicalDeviceGroupProperties = 1000070000,
+        PhysicalDeviceExternalImageFormatInfo = 1000071000,
+        ExternalImageFormatProperties = 1000071001,
+        PhysicalDeviceExternalBufferInfo = 1000071002,
+        ExternalBufferProperties = 1000071003,
+        PhysicalDeviceIdProperties = 1000071004,
+        PhysicalDeviceExternalSemaphoreInfo = 1000076000,
+        ExternalSemaphoreProperties = 1000076001,
+        PhysicalDevice16BitStorageFeatures = 1000083000,
+        PhysicalDeviceSubgroupProperties = 1000094000,
+        PhysicalDeviceExternalFenceInfo = 1000112000,
+        ExternalFenceProperties = 1000112001,
+        PhysicalDevicePointClippingProperties = 1000117000,
+        PhysicalDeviceVariablePointersFeatures = 1000120000,
+        PhysicalDeviceProtectedMemoryFeatures = 1000145000,
+        PhysicalDeviceProtectedMemoryProperties = 1000145002,
+        PhysicalDeviceSamplerYcbcrConversionFeatures = 1000156004,
+        PhysicalDeviceMaintenance3Properties = 1000168000,
+        VideoDecodeH265CapabilitiesKhr = 1000187000,
+        VideoDecodeH265ProfileInfoKhr = 1000187003,
+        PhysicalDeviceDriverProperties = 1000196000,
+        QueueFamilyCheckpointPropertiesNv = 1000206001,
+        PhysicalDevicePciBusInfoPropertiesExt = 1000212000,
+        PhysicalDeviceMemoryBudgetPropertiesExt = 1000237000,
+        PhysicalDeviceToolProperties = 1000245000,
+        HeadlessSurfaceCreateInfoExt = 1000256000,
+        VideoEncodeCapabilitiesKhr = 1000299003,
+        PhysicalDeviceDrmPropertiesExt = 1000353000,
+        QueueFamilyGlobalPriorityPropertiesKhr = 1000388001,
+        VideoDecodeAv1CapabilitiesKhr = 1000512000,
+        VideoDecodeAv1ProfileInfoKhr = 1000512003,
+        VideoEncodeAv1CapabilitiesKhr = 1000513000,
+        VideoEncodeAv1ProfileInfoKhr = 1000513005,
+        VideoDecodeVp9CapabilitiesKhr = 1000514001,
+        VideoDecodeVp9ProfileInfoKhr = 1000514003,
+    }
+}
+
+/// The header every struct that can be part of a `p_next` chain starts with.
+#[repr(C)]
+pub struct BaseOutStructure {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut BaseOutStructure,
+}
+
+/// A struct that can be linked into a `p_next` chain. Its `Default` has `s_type` set.
+///
+/// # Safety
+///
+/// The struct must be `#[repr(C)]` and start with the fields of `BaseOutStructure`.
+pub unsafe trait Structure: Default {}
+
+vk_enum! {
+    pub enum DriverId {
+        AmdProprietary = 1,
+        AmdOpenSource = 2,
+        MesaRadv = 3,
+        NvidiaProprietary = 4,
+        IntelProprietaryWindows = 5,
+        IntelOpenSourceMesa = 6,
+        ImaginationProprietary = 7,
+        QualcommProprietary = 8,
+        ArmProprietary = 9,
+        GoogleSwiftshader = 10,
+        GgpProprietary = 11,
+        BroadcomProprietary = 12,
+        MesaLlvmpipe = 13,
+        Moltenvk = 14,
+        CoreaviProprietary = 15,
+        JuiceProprietary = 16,
+        VerisiliconProprietary = 17,
+        MesaTurnip = 18,
+        MesaV3dv = 19,
+        MesaPanvk = 20,
+        SamsungProprietary = 21,
+        MesaVenus = 22,
+        MesaDozen = 23,
+        MesaNvk = 24,
+        ImaginationOpenSourceMesa = 25,
+        MesaHoneykrisp = 26,
+        VulkanScEmulationOnCpu = 27,
+    }
+}
+
+vk_enum! {
+    pub enum PointClippingBehavior {
+        AllClipPlanes = 0,
+        UserClipPlanesOnly = 1,
+    }
+}
+
+vk_enum! {
+    pub enum ShaderFloatControlsIndependence {
+        Only32Bit = 0,
+        All = 1,
+        None = 2,
+    }
+}
+
+vk_enum! {
+    pub enum PipelineRobustnessBufferBehavior {
+        DeviceDefault = 0,
+        Disabled = 1,
+        RobustBufferAccess = 2,
+        RobustBufferAccess2 = 3,
+    }
+}
+
+vk_enum! {
+    pub enum PipelineRobustnessImageBehavior {
+        DeviceDefault = 0,
+        Disabled = 1,
+        RobustImageAccess = 2,
+        RobustImageAccess2 = 3,
+    }
+}
+
+vk_enum! {
+    pub enum QueueGlobalPriorityKhr {
+        Low = 128,
+        Medium = 256,
+        High = 512,
+        Realtime = 1024,
+    }
+}
+
+vk_enum! {
+    pub enum PresentModeKhr {
+        Immediate = 0,
+        Mailbox = 1,
+        Fifo = 2,
+        FifoRelaxed = 3,
+        SharedDemandRefresh = 1000111000,
+        SharedContinuousRefresh = 1000111001,
+        FifoLatestReady = 1000361000,
+    }
+}
+
+vk_enum! {
+    pub enum ColorSpaceKhr {
+        SrgbNonlinear = 0,
+        DisplayP3NonlinearExt = 1000104001,
+        ExtendedSrgbLinearExt = 1000104002,
+        DisplayP3LinearExt = 1000104003,
+        DciP3NonlinearExt = 1000104004,
+        Bt709LinearExt = 1000104005,
+        Bt709NonlinearExt = 1000104006,
+        Bt2020LinearExt = 1000104007,
+        Hdr10St2084Ext = 1000104008,
+        DolbyvisionExt = 1000104009,
+        Hdr10HlgExt = 1000104010,
+        AdobergbLinearExt = 1000104011,
+        AdobergbNonlinearExt = 1000104012,
+        PassThroughExt = 1000104013,
+        ExtendedSrgbNonlinearExt = 1000104014,
+        DisplayNativeAmd = 1000213000,
+    }
+}
+
+vk_enum! {
+    pub enum ImageLayout {
+        Undefined = 0,
+        General = 1,
+        ColorAttachmentOptimal = 2,
+        DepthStencilAttachmentOptimal = 3,
+        DepthStencilReadOnlyOptimal = 4,
+        ShaderReadOnlyOptimal = 5,
+        TransferSrcOptimal = 6,
+        TransferDstOptimal = 7,
+        Preinitialized = 8,
+        PresentSrcKhr = 1000001002,
+        DepthReadOnlyStencilAttachmentOptimal = 1000117000,
+        DepthAttachmentStencilReadOnlyOptimal = 1000117001,
+        RenderingLocalRead = 1000232000,
+        DepthAttachmentOptimal = 1000241000,
+        DepthReadOnlyOptimal = 1000241001,
+        StencilAttachmentOptimal = 1000241002,
+        StencilReadOnlyOptimal = 1000241003,
+        ReadOnlyOptimal = 1000314000,
+        AttachmentOptimal = 1000314001,
+    }
+}
+
+// The formats of Vulkan 1.0 to 1.4, without those added by extensions.
+vk_enum! {
+    pub enum Format {
+        Undefined = 0,
+        R4g4UnormPack8 = 1,
+        R4g4b4a4UnormPack16 = 2,
+        B4g4r4a4UnormPack16 = 3,
+        R5g6b5UnormPack16 = 4,
+        B5g6r5UnormPack16 = 5,
+        R5g5b5a1UnormPack16 = 6,
+        B5g5r5a1UnormPack16 = 7,
+        A1r5g5b5UnormPack16 = 8,
+        R8Unorm = 9,
+        R8Snorm = 10,
+        R8Uscaled = 11,
+        R8Sscaled = 12,
+        R8Uint = 13,
+        R8Sint = 14,
+        R8Srgb = 15,
+        R8g8Unorm = 16,
+        R8g8Snorm = 17,
+        R8g8Uscaled = 18,
+        R8g8Sscaled = 19,
+        R8g8Uint = 20,
+        R8g8Sint = 21,
+        R8g8Srgb = 22,
+        R8g8b8Unorm = 23,
+        R8g8b8Snorm = 24,
+        R8g8b8Uscaled = 25,
+        R8g8b8Sscaled = 26,
+        R8g8b8Uint = 27,
+        R8g8b8Sint = 28,
+        R8g8b8Srgb = 29,
+        B8g8r8Unorm = 30,
+        B8g8r8Snorm = 31,
+        B8g8r8Uscaled = 32,
+        B8g8r8Sscaled = 33,
+        B8g8r8Uint = 34,
+        B8g8r8Sint = 35,
+        B8g8r8Srgb = 36,
+        R8g8b8a8Unorm = 37,
+        R8g8b8a8Snorm = 38,
+        R8g8b8a8Uscaled = 39,
+        R8g8b8a8Sscaled = 40,
+        R8g8b8a8Uint = 41,
+        R8g8b8a8Sint = 42,
+        R8g8b8a8Srgb = 43,
+        B8g8r8a8Unorm = 44,
+        B8g8r8a8Snorm = 45,
+        B8g8r8a8Uscaled = 46,
+        B8g8r8a8Sscaled = 47,
+        B8g8r8a8Uint = 48,
+        B8g8r8a8Sint = 49,
+        B8g8r8a8Srgb = 50,
+        A8b8g8r8UnormPack32 = 51,
+        A8b8g8r8SnormPack32 = 52,
+        A8b8g8r8UscaledPack32 = 53,
+        A8b8g8r8SscaledPack32 = 54,
+        A8b8g8r8UintPack32 = 55,
+        A8b8g8r8SintPack32 = 56,
+        A8b8g8r8SrgbPack32 = 57,
+        A2r10g10b10UnormPack32 = 58,
+        A2r10g10b10SnormPack32 = 59,
+        A2r10g10b10UscaledPack32 = 60,
+        A2r10g10b10SscaledPack32 = 61,
+        A2r10g10b10UintPack32 = 62,
+        A2r10g10b10SintPack32 = 63,
+        A2b10g10r10UnormPack32 = 64,
+        A2b10g10r10SnormPack32 = 65,
+        A2b10g10r10UscaledPack32 = 66,
+        A2b10g10r10SscaledPack32 = 67,
+        A2b10g10r10UintPack32 = 68,
+        A2b10g10r10SintPack32 = 69,
+        R16Unorm = 70,
+        R16Snorm = 71,
+        R16Uscaled = 72,
+        R16Sscaled = 73,
+        R16Uint = 74,
+        R16Sint = 75,
+        R16Sfloat = 76,
+        R16g16Unorm = 77,
+        R16g16Snorm = 78,
+        R16g16Uscaled = 79,
+        R16g16Sscaled = 80,
+        R16g16Uint = 81,
+        R16g16Sint = 82,
+        R16g16Sfloat = 83,
+        R16g16b16Unorm = 84,
+        R16g16b16Snorm = 85,
+        R16g16b16Uscaled = 86,
+        R16g16b16Sscaled = 87,
+        R16g16b16Uint = 88,
+        R16g16b16Sint = 89,
+        R16g16b16Sfloat = 90,
+        R16g16b16a16Unorm = 91,
+        R16g16b16a16Snorm = 92,
+        R16g16b16a16Uscaled = 93,
+        R16g16b16a16Sscaled = 94,
+        R16g16b16a16Uint = 95,
+        R16g16b16a16Sint = 96,
+        R16g16b16a16Sfloat = 97,
+        R32Uint = 98,
+        R32Sint = 99,
+        R32Sfloat = 100,
+        R32g32Uint = 101,
+        R32g32Sint = 102,
+        R32g32Sfloat = 103,
+        R32g32b32Uint = 104,
+        R32g32b32Sint = 105,
+        R32g32b32Sfloat = 106,
+        R32g32b32a32Uint = 107,
+        R32g32b32a32Sint = 108,
+        R32g32b32a32Sfloat = 109,
+        R64Uint = 110,
+        R64Sint = 111,
+        R64Sfloat = 112,
+        R64g64Uint = 113,
+        R64g64Sint = 114,
+        R64g64Sfloat = 115,
+        R64g64b64Uint = 116,
+        R64g64b64Sint = 117,
+        R64g64b64Sfloat = 118,
+        R64g64b64a64Uint = 119,
+        R64g64b64a64Sint = 120,
+        R64g64b64a64Sfloat = 121,
+        B10g11r11UfloatPack32 = 122,
+        E5b9g9r9UfloatPack32 = 123,
+        D16Unorm = 124,
+        X8D24UnormPack32 = 125,
+        D32Sfloat = 126,
+        S8Uint = 127,
+        D16UnormS8Uint = 128,
+        D24UnormS8Uint = 129,
+        D32SfloatS8Uint = 130,
+        Bc1RgbUnormBlock = 131,
+        Bc1RgbSrgbBlock = 132,
+        Bc1RgbaUnormBlock = 133,
+        Bc1RgbaSrgbBlock = 134,
+        Bc2UnormBlock = 135,
+        Bc2SrgbBlock = 136,
+        Bc3UnormBlock = 137,
+        Bc3SrgbBlock = 138,
+        Bc4UnormBlock = 139,
+        Bc4SnormBlock = 140,
+        Bc5UnormBlock = 141,
+        Bc5SnormBlock = 142,
+        Bc6hUfloatBlock = 143,
+        Bc6hSfloatBlock = 144,
+        Bc7UnormBlock = 145,
+        Bc7SrgbBlock = 146,
+        Etc2R8g8b8UnormBlock = 147,
+        Etc2R8g8b8SrgbBlock = 148,
+        Etc2R8g8b8a1UnormBlock = 149,
+        Etc2R8g8b8a1SrgbBlock = 150,
+        Etc2R8g8b8a8UnormBlock = 151,
+        Etc2R8g8b8a8SrgbBlock = 152,
+        EacR11UnormBlock = 153,
+        EacR11SnormBlock = 154,
+        EacR11g11UnormBlock = 155,
+        EacR11g11SnormBlock = 156,
+        Astc4x4UnormBlock = 157,
+        Astc4x4SrgbBlock = 158,
+        Astc5x4UnormBlock = 159,
+        Astc5x4SrgbBlock = 160,
+        Astc5x5UnormBlock = 161,
+        Astc5x5SrgbBlock = 162,
+        Astc6x5UnormBlock = 163,
+        Astc6x5SrgbBlock = 164,
+        Astc6x6UnormBlock = 165,
+        Astc6x6SrgbBlock = 166,
+        Astc8x5UnormBlock = 167,
+        Astc8x5SrgbBlock = 168,
+        Astc8x6UnormBlock = 169,
+        Astc8x6SrgbBlock = 170,
+        Astc8x8UnormBlock = 171,
+        Astc8x8SrgbBlock = 172,
+        Astc10x5UnormBlock = 173,
+        Astc10x5SrgbBlock = 174,
+        Astc10x6UnormBlock = 175,
+        Astc10x6SrgbBlock = 176,
+        Astc10x8UnormBlock = 177,
+        Astc10x8SrgbBlock = 178,
+        Astc10x10UnormBlock = 179,
+        Astc10x10SrgbBlock = 180,
+        Astc12x10UnormBlock = 181,
+        Astc12x10SrgbBlock = 182,
+        Astc12x12UnormBlock = 183,
+        Astc12x12SrgbBlock = 184,
+        Astc4x4SfloatBlock = 1000066000,
+        Astc5x4SfloatBlock = 1000066001,
+        Astc5x5SfloatBlock = 1000066002,
+        Astc6x5SfloatBlock = 1000066003,
+        Astc6x6SfloatBlock = 1000066004,
+        Astc8x5SfloatBlock = 1000066005,
+        Astc8x6SfloatBlock = 1000066006,
+        Astc8x8SfloatBlock = 1000066007,
+        Astc10x5SfloatBlock = 1000066008,
+        Astc10x6SfloatBlock = 1000066009,
+        Astc10x8SfloatBlock = 1000066010,
+        Astc10x10SfloatBlock = 1000066011,
+        Astc12x10SfloatBlock = 1000066012,
+        Astc12x12SfloatBlock = 1000066013,
+        G8b8g8r8422Unorm = 1000156000,
+        B8g8r8g8422Unorm = 1000156001,
+        G8B8R83plane420Unorm = 1000156002,
+        G8B8r82plane420Unorm = 1000156003,
+        G8B8R83plane422Unorm = 1000156004,
+        G8B8r82plane422Unorm = 1000156005,
+        G8B8R83plane444Unorm = 1000156006,
+        R10x6UnormPack16 = 1000156007,
+        R10x6g10x6Unorm2pack16 = 1000156008,
+        R10x6g10x6b10x6a10x6Unorm4pack16 = 1000156009,
+        G10x6b10x6g10x6r10x6422Unorm4pack16 = 1000156010,
+        B10x6g10x6r10x6g10x6422Unorm4pack16 = 1000156011,
+        G10x6B10x6R10x63plane420Unorm3pack16 = 1000156012,
+        G10x6B10x6r10x62plane420Unorm3pack16 = 1000156013,
+        G10x6B10x6R10x63plane422Unorm3pack16 = 1000156014,
+        G10x6B10x6r10x62plane422Unorm3pack16 = 1000156015,
+        G10x6B10x6R10x63plane444Unorm3pack16 = 1000156016,
+        R12x4UnormPack16 = 1000156017,
+        R12x4g12x4Unorm2pack16 = 1000156018,
+        R12x4g12x4b12x4a12x4Unorm4pack16 = 1000156019,
+        G12x4b12x4g12x4r12x4422Unorm4pack16 = 1000156020,
+        B12x4g12x4r12x4g12x4422Unorm4pack16 = 1000156021,
+        G12x4B12x4R12x43plane420Unorm3pack16 = 1000156022,
+        G12x4B12x4r12x42plane420Unorm3pack16 = 1000156023,
+        G12x4B12x4R12x43plane422Unorm3pack16 = 1000156024,
+        G12x4B12x4r12x42plane422Unorm3pack16 = 1000156025,
+        G12x4B12x4R12x43plane444Unorm3pack16 = 1000156026,
+        G16b16g16r16422Unorm = 1000156027,
+        B16g16r16g16422Unorm = 1000156028,
+        G16B16R163plane420Unorm = 1000156029,
+        G16B16r162plane420Unorm = 1000156030,
+        G16B16R163plane422Unorm = 1000156031,
+        G16B16r162plane422Unorm = 1000156032,
+        G16B16R163plane444Unorm = 1000156033,
+        G8B8r82plane444Unorm = 1000330000,
+        G10x6B10x6r10x62plane444Unorm3pack16 = 1000330001,
+        G12x4B12x4r12x42plane444Unorm3pack16 = 1000330002,
+        G16B16r162plane444Unorm = 1000330003,
+        A4r4g4b4UnormPack16 = 1000340000,
+        A4b4g4r4UnormPack16 = 1000340001,
+        A1b5g5r5UnormPack16 = 1000470000,
+        A8Unorm = 1000470001,
+
+    }
+}
+
+vk_enum! {
+    pub enum ImageType {
+        Type1d = 0,
+        Type2d = 1,
+        Type3d = 2,
+    }
+}
+
+vk_enum! {
+    pub enum ImageTiling {
+        Optimal = 0,
+        Linear = 1,
+        DrmFormatModifierExt = 1000158000,
+    }
+}
 
-#[allow(dead_code)]
-#[derive(Debug, PartialEq)]
-#[repr(i32)]
-pub enum Result {
-    Success = 0,
-    NotReady = 1,
-    Timeout = 2,
-    EventSet = 3,
-    EventReset = 4,
-    Incomplete = 5,
-    ErrorOutOfHostMemory = -1,
-    ErrorOutOfDeviceMemory = -2,
-    ErrorInitializationFailed = -3,
-    ErrorDeviceLost = -4,
-    ErrorMemoryMapFailed = -5,
-    ErrorLayerNotPresent = -6,
-    ErrorExtensionNotPresent = -7,
-    ErrorFeatureNotPresent = -8,
-    ErrorIncompatibleDriver = -9,
-    ErrorTooManyObjects = -10,
-    ErrorFormatNotSupported = -11,
-    ErrorFragmentedPool = -12,
-    ErrorSurfaceLostKhr = -1000000000,
-    ErrorNativeWindowInUseKhr = -1000000001,
-    SuboptimalKhr = 1000001003,
-    ErrorOutOfDateKhr = -1000001004,
-    ErrorIncompatibleDisplayKhr = -1000003001,
-    ErrorValidationFailedExt = -1000011001,
-    ErrorInvalidShaderNv = -1000012000,
-    ErrorOutOfPoolMemoryKhr = -1000069000,
-    ErrorInvalidExternalHandleKhr = -1000072003,
-    MaxEnum = 0x7fffffff,
-}
-
-#[repr(i32)]
-#[derive(Debug, PartialEq, Serialize)]
-pub enum StructureType {
-    ApplicationInfo = 0,
-    InstanceCreateInfo = 1,
-    MaxEnum = 0x7fffffff,
+vk_enum! {
+    pub enum ComponentSwizzle {
+        Identity = 0,
+        Zero = 1,
+        One = 2,
+        R = 3,
+        G = 4,
+        B = 5,
+        A = 6,
+    }
 }
 
 bitflags! {
@@ -66,7 +824,8 @@ bitflags! {
 }
 
 bitflags! {
-    #[derive(Serialize)]
+    #[derive(Default, Serialize, Deserialize)]
+    #[repr(transparent)]
     pub struct SampleCountFlags: u32 {
         const Count1Bit = 0x00000001;
         const Count2Bit = 0x00000002;
@@ -79,176 +838,604 @@ bitflags! {
 }
 
 bitflags! {
-    #[derive(Serialize)]
+    #[derive(Default, Serialize, Deserialize)]
     pub struct QueueFlags: u32 {
         const GraphicsBit = 0x00000001;
         const ComputeBit = 0x00000002;
         const TransferBit = 0x00000004;
         const SparseBindingBit = 0x00000008;
+        const ProtectedBit = 0x00000010;
+        const VideoDecodeBitKhr = 0x00000020;
+        const VideoEncodeBitKhr = 0x00000040;
+        const OpticalFlowBitNv = 0x00000100;
     }
 }
 
-#[repr(C)]
-#[derive(Debug, Serialize)]
-pub struct Extend3D {
-    pub width: u32,
-    pub height: u32,
-    pub depth: u32,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct PipelineStageFlags: u32 {
+        const TopOfPipeBit = 0x00000001;
+        const DrawIndirectBit = 0x00000002;
+        const VertexInputBit = 0x00000004;
+        const VertexShaderBit = 0x00000008;
+        const TessellationControlShaderBit = 0x00000010;
+        const TessellationEvaluationShaderBit = 0x00000020;
+        const GeometryShaderBit = 0x00000040;
+        const FragmentShaderBit = 0x00000080;
+        const EarlyFragmentTestsBit = 0x00000100;
+        const LateFragmentTestsBit = 0x00000200;
+        const ColorAttachmentOutputBit = 0x00000400;
+        const ComputeShaderBit = 0x00000800;
+        const TransferBit = 0x00001000;
+        const BottomOfPipeBit = 0x00002000;
+        const HostBit = 0x00004000;
+        const AllGraphicsBit = 0x00008000;
+        const AllCommandsBit = 0x00010000;
+        const CommandPreprocessBitNv = 0x00020000;
+        const ConditionalRenderingBitExt = 0x00040000;
+        const TaskShaderBitExt = 0x00080000;
+        const MeshShaderBitExt = 0x00100000;
+        const RayTracingShaderBitKhr = 0x00200000;
+        const FragmentShadingRateAttachmentBitKhr = 0x00400000;
+        const FragmentDensityProcessBitExt = 0x00800000;
+        const TransformFeedbackBitExt = 0x01000000;
+        const AccelerationStructureBuildBitKhr = 0x02000000;
+    }
 }
 
-pub struct LayerNameType(pub [c_char; MAX_EXTENSION_NAME_SIZE]);
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoCodecOperationFlagsKhr: u32 {
+        const DecodeH264Bit = 0x00000001;
+        const DecodeH265Bit = 0x00000002;
+        const DecodeAv1Bit = 0x00000004;
+        const DecodeVp9Bit = 0x00000008;
+        const EncodeH264Bit = 0x00010000;
+        const EncodeH265Bit = 0x00020000;
+        const EncodeAv1Bit = 0x00040000;
+    }
+}
 
-impl Serialize for LayerNameType {
-    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S:Serializer {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        serializer.serialize_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoChromaSubsamplingFlagsKhr: u32 {
+        const MonochromeBit = 0x00000001;
+        const Type420Bit = 0x00000002;
+        const Type422Bit = 0x00000004;
+        const Type444Bit = 0x00000008;
     }
 }
 
-impl fmt::Debug for LayerNameType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        f.write_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoComponentBitDepthFlagsKhr: u32 {
+        const Depth8Bit = 0x00000001;
+        const Depth10Bit = 0x00000004;
+        const Depth12Bit = 0x00000010;
     }
 }
 
-pub struct DescriptionType(pub [c_char; MAX_DESCRIPTION_SIZE]);
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoCapabilityFlagsKhr: u32 {
+        const ProtectedContentBit = 0x00000001;
+        const SeparateReferenceImagesBit = 0x00000002;
+    }
+}
 
-impl Serialize for DescriptionType {
-    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S:Serializer {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        serializer.serialize_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoDecodeCapabilityFlagsKhr: u32 {
+        const DpbAndOutputCoincideBit = 0x00000001;
+        const DpbAndOutputDistinctBit = 0x00000002;
     }
 }
 
-impl fmt::Debug for DescriptionType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        f.write_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoEncodeCapabilityFlagsKhr: u32 {
+        const PrecedingExternallyEncodedBytesBit = 0x00000001;
+        const InsufficientBitstreamBufferRangeDetectionBit = 0x00000002;
+        const QuantizationDeltaMapBit = 0x00000004;
+        const EmphasisMapBit = 0x00000008;
     }
 }
 
-#[repr(C)]
-#[derive(Debug, Serialize)]
-pub struct LayerProperties {
-    pub layer_name: LayerNameType,
-    pub spec_version: u32,
-    pub implementation_version: u32,
-    pub description: DescriptionType,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoEncodeRateControlModeFlagsKhr: u32 {
+        const DisabledBit = 0x00000001;
+        const CbrBit = 0x00000002;
+        const VbrBit = 0x00000004;
+    }
 }
 
-pub struct ExtensionNameType(pub [c_char; MAX_EXTENSION_NAME_SIZE]);
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct VideoEncodeFeedbackFlagsKhr: u32 {
+        const BitstreamBufferOffsetBit = 0x00000001;
+        const BitstreamBytesWrittenBit = 0x00000002;
+        const BitstreamHasOverridesBit = 0x00000004;
+    }
+}
 
-impl Serialize for ExtensionNameType {
-    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S:Serializer {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        serializer.serialize_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct FormatFeatureFlags: u32 {
+        const SampledImageBit = 0x00000001;
+        const StorageImageBit = 0x00000002;
+        const StorageImageAtomicBit = 0x00000004;
+        const UniformTexelBufferBit = 0x00000008;
+        const StorageTexelBufferBit = 0x00000010;
+        const StorageTexelBufferAtomicBit = 0x00000020;
+        const VertexBufferBit = 0x00000040;
+        const ColorAttachmentBit = 0x00000080;
+        const ColorAttachmentBlendBit = 0x00000100;
+        const DepthStencilAttachmentBit = 0x00000200;
+        const BlitSrcBit = 0x00000400;
+        const BlitDstBit = 0x00000800;
+        const SampledImageFilterLinearBit = 0x00001000;
+        const SampledImageFilterCubicBitExt = 0x00002000;
+        const TransferSrcBit = 0x00004000;
+        const TransferDstBit = 0x00008000;
+        const SampledImageFilterMinmaxBit = 0x00010000;
+        const MidpointChromaSamplesBit = 0x00020000;
+        const SampledImageYcbcrConversionLinearFilterBit = 0x00040000;
+        const SampledImageYcbcrConversionSeparateReconstructionFilterBit = 0x00080000;
+        const SampledImageYcbcrConversionChromaReconstructionExplicitBit = 0x00100000;
+        const SampledImageYcbcrConversionChromaReconstructionExplicitForceableBit = 0x00200000;
+        const DisjointBit = 0x00400000;
+        const CositedChromaSamplesBit = 0x00800000;
+        const FragmentDensityMapBitExt = 0x01000000;
+        const VideoDecodeOutputBitKhr = 0x02000000;
+        const VideoDecodeDpbBitKhr = 0x04000000;
+        const VideoEncodeInputBitKhr = 0x08000000;
+        const VideoEncodeDpbBitKhr = 0x10000000;
+        const AccelerationStructureVertexBufferBitKhr = 0x20000000;
+        const FragmentShadingRateAttachmentBitKhr = 0x40000000;
     }
 }
 
-impl fmt::Debug for ExtensionNameType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        f.write_str(&s.to_string_lossy())
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    #[repr(transparent)]
+    pub struct ImageUsageFlags: u32 {
+        const TransferSrcBit = 0x00000001;
+        const TransferDstBit = 0x00000002;
+        const SampledBit = 0x00000004;
+        const StorageBit = 0x00000008;
+        const ColorAttachmentBit = 0x00000010;
+        const DepthStencilAttachmentBit = 0x00000020;
+        const TransientAttachmentBit = 0x00000040;
+        const InputAttachmentBit = 0x00000080;
+        const VideoDecodeDstBitKhr = 0x00000400;
+        const VideoDecodeSrcBitKhr = 0x00000800;
+        const VideoDecodeDpbBitKhr = 0x00001000;
+        const VideoEncodeDstBitKhr = 0x00002000;
+        const VideoEncodeSrcBitKhr = 0x00004000;
+        const VideoEncodeDpbBitKhr = 0x00008000;
+        const HostTransferBit = 0x00400000;
     }
 }
 
-#[repr(C)]
-#[derive(Debug, Serialize)]
-pub struct ExtensionProperties {
-    pub extension_name: ExtensionNameType,
-    pub spec_version: u32,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    #[repr(transparent)]
+    pub struct ImageCreateFlags: u32 {
+        const SparseBindingBit = 0x00000001;
+        const SparseResidencyBit = 0x00000002;
+        const SparseAliasedBit = 0x00000004;
+        const MutableFormatBit = 0x00000008;
+        const CubeCompatibleBit = 0x00000010;
+        const AliasBit = 0x00000400;
+        const SplitInstanceBindRegionsBit = 0x00000040;
+        const Array2dCompatibleBit = 0x00000020;
+        const BlockTexelViewCompatibleBit = 0x00000080;
+        const ExtendedUsageBit = 0x00000100;
+        const ProtectedBit = 0x00000800;
+        const DisjointBit = 0x00000200;
+    }
 }
 
-#[repr(C)]
-#[derive(Debug)]
-pub struct AllocationCallbacks {
-    p_user_data: *const c_void,
-    pfn_allocation: *const c_void,
-    pfn_reallocation: *const c_void,
-    pfn_free: *const c_void,
-    pfn_internal_allocation: *const c_void,
-    pfn_internal_free: *const c_void,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ImageAspectFlags: u32 {
+        const ColorBit = 0x00000001;
+        const DepthBit = 0x00000002;
+        const StencilBit = 0x00000004;
+        const MetadataBit = 0x00000008;
+        const Plane0Bit = 0x00000010;
+        const Plane1Bit = 0x00000020;
+        const Plane2Bit = 0x00000040;
+    }
 }
 
-#[repr(C)]
-#[derive(Debug)]
-pub struct ApplicationInfo {
-    pub s_type: StructureType,
-    pub p_next: *const c_void,
-    pub p_application_name: *const c_char,
-    pub application_version: u32,
-    pub p_engine_name: *const c_char,
-    pub engine_version: u32,
-    pub api_version: u32,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct SparseImageFormatFlags: u32 {
+        const SingleMiptailBit = 0x00000001;
+        const AlignedMipSizeBit = 0x00000002;
+        const NonstandardBlockSizeBit = 0x00000004;
+    }
 }
 
-#[repr(C)]
-#[derive(Debug)]
-pub struct InstanceCreateInfo {
-    pub s_type: StructureType,
-    pub p_next: *const c_void,
-    pub flags: InstanceCreateFlags,
-    pub p_application_info: *const ApplicationInfo,
-    pub enabled_layer_count: u32,
-    pub pp_enabled_layer_names: *const *const c_char,
-    pub enabled_extension_count: u32,
-    pub pp_enabled_extension_names: *const *const c_char,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct SurfaceTransformFlagsKhr: u32 {
+        const IdentityBit = 0x00000001;
+        const Rotate90Bit = 0x00000002;
+        const Rotate180Bit = 0x00000004;
+        const Rotate270Bit = 0x00000008;
+        const HorizontalMirrorBit = 0x00000010;
+        const HorizontalMirrorRotate90Bit = 0x00000020;
+        const HorizontalMirrorRotate180Bit = 0x00000040;
+        const HorizontalMirrorRotate270Bit = 0x00000080;
+        const InheritBit = 0x00000100;
+    }
 }
 
-#[repr(C)]
-#[derive(Debug, Serialize)]
-pub struct PhysicalDeviceFeatures {
-    pub robust_buffer_access: Bool32,
-    pub full_draw_index_uint32: Bool32,
-    pub image_cube_array: Bool32,
-    pub independent_blend: Bool32,
-    pub geometry_shader: Bool32,
-    pub tessellation_shader: Bool32,
-    pub sample_rate_shading: Bool32,
-    pub dual_src_blend: Bool32,
-    pub logic_op: Bool32,
-    pub multi_draw_indirect: Bool32,
-    pub draw_indirect_first_instance: Bool32,
-    pub depth_clamp: Bool32,
-    pub depth_bias_clamp: Bool32,
-    pub fill_mode_non_solid: Bool32,
-    pub depth_bounds: Bool32,
-    pub wide_lines: Bool32,
-    pub large_points: Bool32,
-    pub alpha_to_one: Bool32,
-    pub multi_viewport: Bool32,
-    pub sampler_anisotropy: Bool32,
-    pub texture_compression_etc2: Bool32,
-    pub texture_compression_astc_ldr: Bool32,
-    pub texture_compression_bc: Bool32,
-    pub occlusion_query_precise: Bool32,
-    pub pipeline_statistics_query: Bool32,
-    pub vertex_pipeline_stores_and_atomics: Bool32,
-    pub fragment_stores_and_atomics: Bool32,
-    pub shader_tessellation_and_geometry_point_size: Bool32,
-    pub shader_image_gather_extended: Bool32,
-    pub shader_storage_image_extended_formats: Bool32,
-    pub shader_storage_image_multisample: Bool32,
-    pub shader_storage_image_read_without_format: Bool32,
-    pub shader_storage_image_write_without_format: Bool32,
-    pub shader_uniform_buffer_array_dynamic_indexing: Bool32,
-    pub shader_sampled_image_array_dynamic_indexing: Bool32,
-    pub shader_storage_buffer_array_dynamic_indexing: Bool32,
-    pub shader_storage_image_array_dynamic_index: Bool32,
-    pub shader_clip_distance: Bool32,
-    pub shader_cull_distance: Bool32,
-    pub shader_float64: Bool32,
-    pub shader_int64: Bool32,
-    pub shader_int16: Bool32,
-    pub shader_resource_residency: Bool32,
-    pub shader_resource_min_load: Bool32,
-    pub sparse_binding: Bool32,
-    pub sparse_residency_buffer: Bool32,
-    pub sparse_residency_image_2d: Bool32,
-    pub sparse_residency_image_3d: Bool32,
-    pub sparse_residency_2_samples: Bool32,
-    pub sparse_residency_4_samples: Bool32,
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct CompositeAlphaFlagsKhr: u32 {
+        const OpaqueBit = 0x00000001;
+        const PreMultipliedBit = 0x00000002;
+        const PostMultipliedBit = 0x00000004;
+        const InheritBit = 0x00000008;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct DisplayPlaneAlphaFlagsKhr: u32 {
+        const OpaqueBit = 0x00000001;
+        const GlobalBit = 0x00000002;
+        const PerPixelBit = 0x00000004;
+        const PerPixelPremultipliedBit = 0x00000008;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ShaderStageFlags: u32 {
+        const VertexBit = 0x00000001;
+        const TessellationControlBit = 0x00000002;
+        const TessellationEvaluationBit = 0x00000004;
+        const GeometryBit = 0x00000008;
+        const FragmentBit = 0x00000010;
+        const ComputeBit = 0x00000020;
+        const TaskBitExt = 0x00000040;
+        const MeshBitExt = 0x00000080;
+        const RaygenBitKhr = 0x00000100;
+        const AnyHitBitKhr = 0x00000200;
+        const ClosestHitBitKhr = 0x00000400;
+        const MissBitKhr = 0x00000800;
+        const IntersectionBitKhr = 0x00001000;
+        const CallableBitKhr = 0x00002000;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct SubgroupFeatureFlags: u32 {
+        const BasicBit = 0x00000001;
+        const VoteBit = 0x00000002;
+        const ArithmeticBit = 0x00000004;
+        const BallotBit = 0x00000008;
+        const ShuffleBit = 0x00000010;
+        const ShuffleRelativeBit = 0x00000020;
+        const ClusteredBit = 0x00000040;
+        const QuadBit = 0x00000080;
+        const PartitionedBitNv = 0x00000100;
+        const RotateBit = 0x00000200;
+        const RotateClusteredBit = 0x00000400;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ResolveModeFlags: u32 {
+        const SampleZeroBit = 0x00000001;
+        const AverageBit = 0x00000002;
+        const MinBit = 0x00000004;
+        const MaxBit = 0x00000008;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct MemoryPropertyFlags: u32 {
+        const DeviceLocalBit = 0x00000001;
+        const HostVisibleBit = 0x00000002;
+        const HostCoherentBit = 0x00000004;
+        const HostCachedBit = 0x00000008;
+        const LazilyAllocatedBit = 0x00000010;
+        const ProtectedBit = 0x00000020;
+        const DeviceCoherentBitAmd = 0x00000040;
+        const DeviceUncachedBitAmd = 0x00000080;
+        const RdmaCapableBitNv = 0x00000100;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct MemoryHeapFlags: u32 {
+        const DeviceLocalBit = 0x00000001;
+        const MultiInstanceBit = 0x00000002;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct BufferCreateFlags: u32 {
+        const SparseBindingBit = 0x00000001;
+        const SparseResidencyBit = 0x00000002;
+        const SparseAliasedBit = 0x00000004;
+        const ProtectedBit = 0x00000008;
+        const DeviceAddressCaptureReplayBit = 0x00000010;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct BufferUsageFlags: u32 {
+        const TransferSrcBit = 0x00000001;
+        const TransferDstBit = 0x00000002;
+        const UniformTexelBufferBit = 0x00000004;
+        const StorageTexelBufferBit = 0x00000008;
+        const UniformBufferBit = 0x00000010;
+        const StorageBufferBit = 0x00000020;
+        const IndexBufferBit = 0x00000040;
+        const VertexBufferBit = 0x00000080;
+        const IndirectBufferBit = 0x00000100;
+        const ShaderDeviceAddressBit = 0x00020000;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalMemoryHandleTypeFlags: u32 {
+        const OpaqueFdBit = 0x00000001;
+        const OpaqueWin32Bit = 0x00000002;
+        const OpaqueWin32KmtBit = 0x00000004;
+        const D3d11TextureBit = 0x00000008;
+        const D3d11TextureKmtBit = 0x00000010;
+        const D3d12HeapBit = 0x00000020;
+        const D3d12ResourceBit = 0x00000040;
+        const HostAllocationBitExt = 0x00000080;
+        const HostMappedForeignMemoryBitExt = 0x00000100;
+        const DmaBufBitExt = 0x00000200;
+        const AndroidHardwareBufferBitAndroid = 0x00000400;
+        const ZirconVmoBitFuchsia = 0x00000800;
+        const RdmaAddressBitNv = 0x00001000;
+        const ScreenBufferBitQnx = 0x00004000;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalMemoryFeatureFlags: u32 {
+        const DedicatedOnlyBit = 0x00000001;
+        const ExportableBit = 0x00000002;
+        const ImportableBit = 0x00000004;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalSemaphoreHandleTypeFlags: u32 {
+        const OpaqueFdBit = 0x00000001;
+        const OpaqueWin32Bit = 0x00000002;
+        const OpaqueWin32KmtBit = 0x00000004;
+        const D3d12FenceBit = 0x00000008;
+        const SyncFdBit = 0x00000010;
+        const ZirconEventBitFuchsia = 0x00000080;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalSemaphoreFeatureFlags: u32 {
+        const ExportableBit = 0x00000001;
+        const ImportableBit = 0x00000002;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalFenceHandleTypeFlags: u32 {
+        const OpaqueFdBit = 0x00000001;
+        const OpaqueWin32Bit = 0x00000002;
+        const OpaqueWin32KmtBit = 0x00000004;
+        const SyncFdBit = 0x00000008;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ExternalFenceFeatureFlags: u32 {
+        const ExportableBit = 0x00000001;
+        const ImportableBit = 0x00000002;
+    }
+}
+
+bitflags! {
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct ToolPurposeFlags: u32 {
+        const ValidationBit = 0x00000001;
+        const ProfilingBit = 0x00000002;
+        const TracingBit = 0x00000004;
+        const AdditionalFeaturesBit = 0x00000008;
+        const ModifyingFeaturesBit = 0x00000010;
+        const DebugReportingBitExt = 0x00000020;
+        const DebugMarkersBitExt = 0x00000040;
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Offset2D {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Extend2D {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Extend3D {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+}
+
+c_string!(LayerNameType, MAX_EXTENSION_NAME_SIZE);
+
+c_string!(DescriptionType, MAX_DESCRIPTION_SIZE);
+
+#[repr(C)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayerProperties {
+    pub layer_name: LayerNameType,
+    pub spec_version: u32,
+    pub implementation_version: u32,
+    pub description: DescriptionType,
+}
+
+c_string!(ExtensionNameType, MAX_EXTENSION_NAME_SIZE);
+
+#[repr(C)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtensionProperties {
+    pub extension_name: ExtensionNameType,
+    pub spec_version: u32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct AllocationCallbacks {
+    p_user_data: *const c_void,
+    pfn_allocation: *const c_void,
+    pfn_reallocation: *const c_void,
+    pfn_free: *const c_void,
+    pfn_internal_allocation: *const c_void,
+    pfn_internal_free: *const c_void,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct ApplicationInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub p_application_name: *const c_char,
+    pub application_version: u32,
+    pub p_engine_name: *const c_char,
+    pub engine_version: u32,
+    pub api_version: u32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct InstanceCreateInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub flags: InstanceCreateFlags,
+    pub p_application_info: *const ApplicationInfo,
+    pub enabled_layer_count: u32,
+    pub pp_enabled_layer_names: *const *const c_char,
+    pub enabled_extension_count: u32,
+    pub pp_enabled_extension_names: *const *const c_char,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct DeviceQueueCreateInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub flags: u32,
+    pub queue_family_index: u32,
+    pub queue_count: u32,
+    pub p_queue_priorities: *const f32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct DeviceCreateInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub flags: u32,
+    pub queue_create_info_count: u32,
+    pub p_queue_create_infos: *const DeviceQueueCreateInfo,
+    pub enabled_layer_count: u32,
+    pub pp_enabled_layer_names: *const *const c_char,
+    pub enabled_extension_count: u32,
+    pub pp_enabled_extension_names: *const *const c_char,
+    pub p_enabled_features: *const PhysicalDeviceFeatures,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceFeatures {
+    pub robust_buffer_access: Bool32,
+    pub full_draw_index_uint32: Bool32,
+    pub image_cube_array: Bool32,
+    pub independent_blend: Bool32,
+    pub geometry_shader: Bool32,
+    pub tessellation_shader: Bool32,
+    pub sample_rate_shading: Bool32,
+    pub dual_src_blend: Bool32,
+    pub logic_op: Bool32,
+    pub multi_draw_indirect: Bool32,
+    pub draw_indirect_first_instance: Bool32,
+    pub depth_clamp: Bool32,
+    pub depth_bias_clamp: Bool32,
+    pub fill_mode_non_solid: Bool32,
+    pub depth_bounds: Bool32,
+    pub wide_lines: Bool32,
+    pub large_points: Bool32,
+    pub alpha_to_one: Bool32,
+    pub multi_viewport: Bool32,
+    pub sampler_anisotropy: Bool32,
+    pub texture_compression_etc2: Bool32,
+    pub texture_compression_astc_ldr: Bool32,
+    pub texture_compression_bc: Bool32,
+    pub occlusion_query_precise: Bool32,
+    pub pipeline_statistics_query: Bool32,
+    pub vertex_pipeline_stores_and_atomics: Bool32,
+    pub fragment_stores_and_atomics: Bool32,
+    pub shader_tessellation_and_geometry_point_size: Bool32,
+    pub shader_image_gather_extended: Bool32,
+    pub shader_storage_image_extended_formats: Bool32,
+    pub shader_storage_image_multisample: Bool32,
+    pub shader_storage_image_read_without_format: Bool32,
+    pub shader_storage_image_write_without_format: Bool32,
+    pub shader_uniform_buffer_array_dynamic_indexing: Bool32,
+    pub shader_sampled_image_array_dynamic_indexing: Bool32,
+    pub shader_storage_buffer_array_dynamic_indexing: Bool32,
+    pub shader_storage_image_array_dynamic_index: Bool32,
+    pub shader_clip_distance: Bool32,
+    pub shader_cull_distance: Bool32,
+    pub shader_float64: Bool32,
+    pub shader_int64: Bool32,
+    pub shader_int16: Bool32,
+    pub shader_resource_residency: Bool32,
+    pub shader_resource_min_load: Bool32,
+    pub sparse_binding: Bool32,
+    pub sparse_residency_buffer: Bool32,
+    pub sparse_residency_image_2d: Bool32,
+    pub sparse_residency_image_3d: Bool32,
+    pub sparse_residency_2_samples: Bool32,
+    pub sparse_residency_4_samples: Bool32,
     pub sparse_residency_8_samples: Bool32,
     pub sparse_residency_16_samples: Bool32,
     pub sparse_residency_aliased: Bool32,
@@ -256,49 +1443,36 @@ pub struct PhysicalDeviceFeatures {
     pub inherited_queries: Bool32,
 }
 
-pub struct DeviceNameType(pub [c_char; MAX_PHYSICAL_DEVICE_NAME_SIZE]);
+c_string!(DeviceNameType, MAX_PHYSICAL_DEVICE_NAME_SIZE);
 
-impl Serialize for DeviceNameType {
-    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S:Serializer {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        serializer.serialize_str(&s.to_string_lossy())
-    }
+#[repr(C)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceProperties {
+    pub api_version: u32,
+    pub driver_version: u32,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub device_type: Raw<PhysicalDeviceType>,
+    pub device_name: DeviceNameType,
+    pub pipeline_cache_uuid: [u8; UUID_SIZE],
+    pub limit: PhysicalDeviceLimits,
+    pub sparse_properties: PhysicalDeviceSparseProperties,
 }
 
-impl fmt::Debug for DeviceNameType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = unsafe { CStr::from_ptr(self.0.as_ptr()) };
-        f.write_str(&s.to_string_lossy())
+vk_enum! {
+    pub enum PhysicalDeviceType {
+        Other = 0,
+        IntegratedGpu = 1,
+        DiscreteGpu = 2,
+        VirtualGpu = 3,
+        Cpu = 4,
     }
 }
 
 #[repr(C)]
-#[derive(Debug, Serialize)]
-pub struct PhysicalDeviceProperties {
-    api_version: u32,
-    driver_version: u32,
-    vendor_id: u32,
-    device_id: u32,
-    device_type: PhysicalDeviceType,
-    device_name: DeviceNameType,
-    pipeline_cache_uuid: [u8; UUID_SIZE],
-    limit: PhysicalDeviceLimits,
-    sparse_properties: PhysicalDeviceSparseProperties,
-}
-
-#[repr(i32)]
-#[derive(Debug, PartialEq, Serialize)]
-pub enum PhysicalDeviceType {
-    Other = 0,
-    IntegratedGpu = 1,
-    DiscreteGpu = 2,
-    VirtualGpu = 3,
-    Cpu = 4,
-    MaxEnum = 0x7fffffff,
-}
-
-#[repr(C)]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicalDeviceLimits {
     pub max_image_dimension_1d: u32,
     pub max_image_dimension_2d: u32,
@@ -409,20 +1583,1388 @@ pub struct PhysicalDeviceLimits {
 }
 
 #[repr(C)]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicalDeviceSparseProperties {
-    residency_standard_2d_block_shape: Bool32,
-    residency_standard_2d_multisample_block_shape: Bool32,
-    residency_standard_3d_block_shape: Bool32,
-    residency_aligned_mip_size: Bool32,
-    residency_non_resident_strict: Bool32,
+    pub residency_standard_2d_block_shape: Bool32,
+    pub residency_standard_2d_multisample_block_shape: Bool32,
+    pub residency_standard_3d_block_shape: Bool32,
+    pub residency_aligned_mip_size: Bool32,
+    pub residency_non_resident_strict: Bool32,
 }
 
 #[repr(C)]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct QueueFamilyProperties {
     pub queue_flags: QueueFlags,
     pub queue_count: u32,
     pub timestamp_valid_bits: u32,
     pub min_image_transfer_granularity: Extend3D,
 }
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryType {
+    pub property_flags: MemoryPropertyFlags,
+    pub heap_index: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryHeap {
+    pub size: DeviceSize,
+    pub flags: MemoryHeapFlags,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhysicalDeviceMemoryProperties {
+    pub memory_type_count: u32,
+    pub memory_types: [MemoryType; MAX_MEMORY_TYPES],
+    pub memory_heap_count: u32,
+    pub memory_heaps: [MemoryHeap; MAX_MEMORY_HEAPS],
+}
+
+impl PhysicalDeviceMemoryProperties {
+    /// Fills the fixed-size arrays from `types` and `heaps`, dropping what does not fit.
+    pub fn new(types: &[MemoryType], heaps: &[MemoryHeap]) -> PhysicalDeviceMemoryProperties {
+        let mut properties = PhysicalDeviceMemoryProperties::default();
+        for (slot, memory_type) in properties.memory_types.iter_mut().zip(types) {
+            *slot = *memory_type;
+            properties.memory_type_count += 1;
+        }
+        for (slot, memory_heap) in properties.memory_heaps.iter_mut().zip(heaps) {
+            *slot = *memory_heap;
+            properties.memory_heap_count += 1;
+        }
+        properties
+    }
+
+    pub fn memory_types(&self) -> &[MemoryType] {
+        &self.memory_types[..cmp::min(self.memory_type_count as usize, MAX_MEMORY_TYPES)]
+    }
+
+    pub fn memory_heaps(&self) -> &[MemoryHeap] {
+        &self.memory_heaps[..cmp::min(self.memory_heap_count as usize, MAX_MEMORY_HEAPS)]
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatProperties {
+    pub linear_tiling_features: FormatFeatureFlags,
+    pub optimal_tiling_features: FormatFeatureFlags,
+    pub buffer_features: FormatFeatureFlags,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageFormatProperties {
+    pub max_extent: Extend3D,
+    pub max_mip_levels: u32,
+    pub max_array_layers: u32,
+    pub sample_counts: SampleCountFlags,
+    pub max_resource_size: DeviceSize,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SparseImageFormatProperties {
+    pub aspect_mask: ImageAspectFlags,
+    pub image_granularity: Extend3D,
+    pub flags: SparseImageFormatFlags,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceFeatures2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub features: PhysicalDeviceFeatures,
+}
+
+vk_structure!(PhysicalDeviceFeatures2, PhysicalDeviceFeatures2);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceProperties2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub properties: PhysicalDeviceProperties,
+}
+
+vk_structure!(PhysicalDeviceProperties2, PhysicalDeviceProperties2);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceMemoryProperties2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub memory_properties: PhysicalDeviceMemoryProperties,
+}
+
+vk_structure!(PhysicalDeviceMemoryProperties2, PhysicalDeviceMemoryProperties2);
+
+/// Provided by VK_EXT_memory_budget. Only the first `memory_heap_count` entries are used.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceMemoryBudgetPropertiesExt {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub heap_budget: [DeviceSize; MAX_MEMORY_HEAPS],
+    pub heap_usage: [DeviceSize; MAX_MEMORY_HEAPS],
+}
+
+vk_structure!(PhysicalDeviceMemoryBudgetPropertiesExt, PhysicalDeviceMemoryBudgetPropertiesExt);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceImageFormatInfo2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub format: Raw<Format>,
+    pub image_type: Raw<ImageType>,
+    pub tiling: Raw<ImageTiling>,
+    pub usage: ImageUsageFlags,
+    pub flags: ImageCreateFlags,
+}
+
+vk_structure!(PhysicalDeviceImageFormatInfo2, PhysicalDeviceImageFormatInfo2);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ImageFormatProperties2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub image_format_properties: ImageFormatProperties,
+}
+
+vk_structure!(ImageFormatProperties2, ImageFormatProperties2);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalMemoryProperties {
+    pub external_memory_features: ExternalMemoryFeatureFlags,
+    pub export_from_imported_handle_types: ExternalMemoryHandleTypeFlags,
+    pub compatible_handle_types: ExternalMemoryHandleTypeFlags,
+}
+
+/// Asks `vkGetPhysicalDeviceImageFormatProperties2` about images with memory of `handle_type`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceExternalImageFormatInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub handle_type: ExternalMemoryHandleTypeFlags,
+}
+
+vk_structure!(PhysicalDeviceExternalImageFormatInfo, PhysicalDeviceExternalImageFormatInfo);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ExternalImageFormatProperties {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub external_memory_properties: ExternalMemoryProperties,
+}
+
+vk_structure!(ExternalImageFormatProperties, ExternalImageFormatProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceExternalBufferInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub flags: BufferCreateFlags,
+    pub usage: BufferUsageFlags,
+    pub handle_type: ExternalMemoryHandleTypeFlags,
+}
+
+vk_structure!(PhysicalDeviceExternalBufferInfo, PhysicalDeviceExternalBufferInfo);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct ExternalBufferProperties {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub external_memory_properties: ExternalMemoryProperties,
+}
+
+vk_structure!(ExternalBufferProperties, ExternalBufferProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceExternalSemaphoreInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub handle_type: ExternalSemaphoreHandleTypeFlags,
+}
+
+vk_structure!(PhysicalDeviceExternalSemaphoreInfo, PhysicalDeviceExternalSemaphoreInfo);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalSemaphoreProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub export_from_imported_handle_types: ExternalSemaphoreHandleTypeFlags,
+    pub compatible_handle_types: ExternalSemaphoreHandleTypeFlags,
+    pub external_semaphore_features: ExternalSemaphoreFeatureFlags,
+}
+
+vk_structure!(ExternalSemaphoreProperties, ExternalSemaphoreProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceExternalFenceInfo {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub handle_type: ExternalFenceHandleTypeFlags,
+}
+
+vk_structure!(PhysicalDeviceExternalFenceInfo, PhysicalDeviceExternalFenceInfo);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalFenceProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub export_from_imported_handle_types: ExternalFenceHandleTypeFlags,
+    pub compatible_handle_types: ExternalFenceHandleTypeFlags,
+    pub external_fence_features: ExternalFenceFeatureFlags,
+}
+
+vk_structure!(ExternalFenceProperties, ExternalFenceProperties);
+
+/// A tool such as a validation layer, profiler or capture layer active on the device.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceToolProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub name: ExtensionNameType,
+    pub version: ExtensionNameType,
+    pub purposes: ToolPurposeFlags,
+    pub description: DescriptionType,
+    /// The layer that implements the tool, empty if it is not a layer.
+    pub layer: LayerNameType,
+}
+
+vk_structure!(PhysicalDeviceToolProperties, PhysicalDeviceToolProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceGroupProperties {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub physical_device_count: u32,
+    pub physical_devices: [PhysicalDevice; MAX_DEVICE_GROUP_SIZE],
+    pub subset_allocation: Bool32,
+}
+
+vk_structure!(PhysicalDeviceGroupProperties, PhysicalDeviceGroupProperties);
+
+impl PhysicalDeviceGroupProperties {
+    pub fn physical_devices(&self) -> &[PhysicalDevice] {
+        &self.physical_devices[..cmp::min(self.physical_device_count as usize, MAX_DEVICE_GROUP_SIZE)]
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct QueueFamilyProperties2 {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub queue_family_properties: QueueFamilyProperties,
+}
+
+vk_structure!(QueueFamilyProperties2, QueueFamilyProperties2);
+
+/// Provided by VK_KHR_global_priority and core since 1.4. Only the first `priority_count`
+/// entries are used.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct QueueFamilyGlobalPriorityPropertiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub priority_count: u32,
+    pub priorities: [Raw<QueueGlobalPriorityKhr>; MAX_GLOBAL_PRIORITY_SIZE],
+}
+
+vk_structure!(QueueFamilyGlobalPriorityPropertiesKhr, QueueFamilyGlobalPriorityPropertiesKhr);
+
+impl QueueFamilyGlobalPriorityPropertiesKhr {
+    /// Fills the fixed-size array from `priorities`, dropping what does not fit.
+    pub fn new(priorities: &[QueueGlobalPriorityKhr]) -> QueueFamilyGlobalPriorityPropertiesKhr {
+        let mut properties = QueueFamilyGlobalPriorityPropertiesKhr::default();
+        for (slot, &priority) in properties.priorities.iter_mut().zip(priorities) {
+            *slot = priority.into();
+            properties.priority_count += 1;
+        }
+        properties
+    }
+
+    pub fn priorities(&self) -> &[Raw<QueueGlobalPriorityKhr>] {
+        &self.priorities[..cmp::min(self.priority_count as usize, MAX_GLOBAL_PRIORITY_SIZE)]
+    }
+}
+
+/// Provided by VK_NV_device_diagnostic_checkpoints.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct QueueFamilyCheckpointPropertiesNv {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub checkpoint_execution_stage_mask: PipelineStageFlags,
+}
+
+vk_structure!(QueueFamilyCheckpointPropertiesNv, QueueFamilyCheckpointPropertiesNv);
+
+/// Provided by VK_KHR_video_queue.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct QueueFamilyVideoPropertiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub video_codec_operations: VideoCodecOperationFlagsKhr,
+}
+
+vk_structure!(QueueFamilyVideoPropertiesKhr, QueueFamilyVideoPropertiesKhr);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComponentMapping {
+    pub r: Raw<ComponentSwizzle>,
+    pub g: Raw<ComponentSwizzle>,
+    pub b: Raw<ComponentSwizzle>,
+    pub a: Raw<ComponentSwizzle>,
+}
+
+/// Provided by VK_KHR_video_queue. `p_next` carries the codec's profile structure.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    /// Exactly one codec operation.
+    pub video_codec_operation: VideoCodecOperationFlagsKhr,
+    pub chroma_subsampling: VideoChromaSubsamplingFlagsKhr,
+    pub luma_bit_depth: VideoComponentBitDepthFlagsKhr,
+    /// Empty for monochrome profiles.
+    pub chroma_bit_depth: VideoComponentBitDepthFlagsKhr,
+}
+
+vk_structure!(VideoProfileInfoKhr, VideoProfileInfoKhr);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoProfileListInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub profile_count: u32,
+    pub p_profiles: *const VideoProfileInfoKhr,
+}
+
+vk_structure!(VideoProfileListInfoKhr, VideoProfileListInfoKhr);
+
+/// Provided by VK_KHR_video_decode_h264. `std_profile_idc` is a `StdVideoH264ProfileIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeH264ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile_idc: i32,
+    /// Zero for progressive pictures.
+    pub picture_layout: u32,
+}
+
+vk_structure!(VideoDecodeH264ProfileInfoKhr, VideoDecodeH264ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_decode_h265. `std_profile_idc` is a `StdVideoH265ProfileIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeH265ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile_idc: i32,
+}
+
+vk_structure!(VideoDecodeH265ProfileInfoKhr, VideoDecodeH265ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_decode_av1. `std_profile` is a `StdVideoAV1Profile`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeAv1ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile: i32,
+    pub film_grain_support: Bool32,
+}
+
+vk_structure!(VideoDecodeAv1ProfileInfoKhr, VideoDecodeAv1ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_decode_vp9. `std_profile` is a `StdVideoVP9Profile`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeVp9ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile: i32,
+}
+
+vk_structure!(VideoDecodeVp9ProfileInfoKhr, VideoDecodeVp9ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_encode_h264. `std_profile_idc` is a `StdVideoH264ProfileIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeH264ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile_idc: i32,
+}
+
+vk_structure!(VideoEncodeH264ProfileInfoKhr, VideoEncodeH264ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_encode_h265. `std_profile_idc` is a `StdVideoH265ProfileIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeH265ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile_idc: i32,
+}
+
+vk_structure!(VideoEncodeH265ProfileInfoKhr, VideoEncodeH265ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_encode_av1. `std_profile` is a `StdVideoAV1Profile`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeAv1ProfileInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub std_profile: i32,
+}
+
+vk_structure!(VideoEncodeAv1ProfileInfoKhr, VideoEncodeAv1ProfileInfoKhr);
+
+/// Provided by VK_KHR_video_queue.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoCapabilitiesKhr {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub flags: VideoCapabilityFlagsKhr,
+    pub min_bitstream_buffer_offset_alignment: DeviceSize,
+    pub min_bitstream_buffer_size_alignment: DeviceSize,
+    pub picture_access_granularity: Extend2D,
+    pub min_coded_extent: Extend2D,
+    pub max_coded_extent: Extend2D,
+    pub max_dpb_slots: u32,
+    pub max_active_reference_pictures: u32,
+    pub std_header_version: ExtensionProperties,
+}
+
+vk_structure!(VideoCapabilitiesKhr, VideoCapabilitiesKhr);
+
+/// Provided by VK_KHR_video_decode_queue.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoDecodeCapabilitiesKhr {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub flags: VideoDecodeCapabilityFlagsKhr,
+}
+
+vk_structure!(VideoDecodeCapabilitiesKhr, VideoDecodeCapabilitiesKhr);
+
+/// Provided by VK_KHR_video_encode_queue.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoEncodeCapabilitiesKhr {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub flags: VideoEncodeCapabilityFlagsKhr,
+    pub rate_control_modes: VideoEncodeRateControlModeFlagsKhr,
+    pub max_rate_control_layers: u32,
+    /// In bits per second.
+    pub max_bitrate: u64,
+    pub max_quality_levels: u32,
+    pub encode_input_picture_granularity: Extend2D,
+    pub supported_encode_feedback_flags: VideoEncodeFeedbackFlagsKhr,
+}
+
+vk_structure!(VideoEncodeCapabilitiesKhr, VideoEncodeCapabilitiesKhr);
+
+/// Provided by VK_KHR_video_decode_h264. `max_level_idc` is a `StdVideoH264LevelIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeH264CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub max_level_idc: i32,
+    pub field_offset_granularity: Offset2D,
+}
+
+vk_structure!(VideoDecodeH264CapabilitiesKhr, VideoDecodeH264CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_decode_h265. `max_level_idc` is a `StdVideoH265LevelIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeH265CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub max_level_idc: i32,
+}
+
+vk_structure!(VideoDecodeH265CapabilitiesKhr, VideoDecodeH265CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_decode_av1. `max_level` is a `StdVideoAV1Level`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeAv1CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub max_level: i32,
+}
+
+vk_structure!(VideoDecodeAv1CapabilitiesKhr, VideoDecodeAv1CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_decode_vp9. `max_level` is a `StdVideoVP9Level`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoDecodeVp9CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub max_level: i32,
+}
+
+vk_structure!(VideoDecodeVp9CapabilitiesKhr, VideoDecodeVp9CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_encode_h264. `max_level_idc` is a `StdVideoH264LevelIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeH264CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub flags: u32,
+    pub max_level_idc: i32,
+    pub max_slice_count: u32,
+    pub max_p_picture_l0_reference_count: u32,
+    pub max_b_picture_l0_reference_count: u32,
+    pub max_l1_reference_count: u32,
+    pub max_temporal_layer_count: u32,
+    pub expect_dyadic_temporal_layer_pattern: Bool32,
+    pub min_qp: i32,
+    pub max_qp: i32,
+    pub prefers_gop_remaining_frames: Bool32,
+    pub requires_gop_remaining_frames: Bool32,
+    pub std_syntax_flags: u32,
+}
+
+vk_structure!(VideoEncodeH264CapabilitiesKhr, VideoEncodeH264CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_encode_h265. `max_level_idc` is a `StdVideoH265LevelIdc`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeH265CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub flags: u32,
+    pub max_level_idc: i32,
+    pub max_slice_segment_count: u32,
+    pub max_tiles: Extend2D,
+    pub ctb_sizes: u32,
+    pub transform_block_sizes: u32,
+    pub max_p_picture_l0_reference_count: u32,
+    pub max_b_picture_l0_reference_count: u32,
+    pub max_l1_reference_count: u32,
+    pub max_sub_layer_count: u32,
+    pub expect_dyadic_temporal_sub_layer_pattern: Bool32,
+    pub min_qp: i32,
+    pub max_qp: i32,
+    pub prefers_gop_remaining_frames: Bool32,
+    pub requires_gop_remaining_frames: Bool32,
+    pub std_syntax_flags: u32,
+}
+
+vk_structure!(VideoEncodeH265CapabilitiesKhr, VideoEncodeH265CapabilitiesKhr);
+
+/// Provided by VK_KHR_video_encode_av1. `max_level` is a `StdVideoAV1Level`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoEncodeAv1CapabilitiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub flags: u32,
+    pub max_level: i32,
+    pub coded_picture_alignment: Extend2D,
+    pub max_tiles: Extend2D,
+    pub min_tile_size: Extend2D,
+    pub max_tile_size: Extend2D,
+    pub superblock_sizes: u32,
+    pub max_single_reference_count: u32,
+    pub single_reference_name_mask: u32,
+    pub max_unidirectional_compound_reference_count: u32,
+    pub max_unidirectional_compound_group1_reference_count: u32,
+    pub unidirectional_compound_reference_name_mask: u32,
+    pub max_bidirectional_compound_reference_count: u32,
+    pub max_bidirectional_compound_group1_reference_count: u32,
+    pub max_bidirectional_compound_group2_reference_count: u32,
+    pub bidirectional_compound_reference_name_mask: u32,
+    pub max_temporal_layer_count: u32,
+    pub max_spatial_layer_count: u32,
+    pub max_operating_points: u32,
+    pub min_q_index: u32,
+    pub max_q_index: u32,
+    pub prefers_gop_remaining_frames: Bool32,
+    pub requires_gop_remaining_frames: Bool32,
+    pub std_syntax_flags: u32,
+}
+
+vk_structure!(VideoEncodeAv1CapabilitiesKhr, VideoEncodeAv1CapabilitiesKhr);
+
+/// Picks the formats `vkGetPhysicalDeviceVideoFormatPropertiesKHR` reports. `p_next` must
+/// carry a `VideoProfileListInfoKhr`.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceVideoFormatInfoKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub image_usage: ImageUsageFlags,
+}
+
+vk_structure!(PhysicalDeviceVideoFormatInfoKhr, PhysicalDeviceVideoFormatInfoKhr);
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct VideoFormatPropertiesKhr {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *mut c_void,
+    pub format: Raw<Format>,
+    pub component_mapping: ComponentMapping,
+    pub image_create_flags: ImageCreateFlags,
+    pub image_type: Raw<ImageType>,
+    pub image_tiling: Raw<ImageTiling>,
+    pub image_usage_flags: ImageUsageFlags,
+}
+
+vk_structure!(VideoFormatPropertiesKhr, VideoFormatPropertiesKhr);
+
+/// Provided by VK_EXT_headless_surface.
+#[repr(C)]
+pub struct HeadlessSurfaceCreateInfoExt {
+    pub s_type: Raw<StructureType>,
+    pub p_next: *const c_void,
+    pub flags: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfaceCapabilitiesKhr {
+    /// Zero `max_image_count` means there is no limit.
+    pub min_image_count: u32,
+    pub max_image_count: u32,
+    /// Both 0xFFFFFFFF if the size is decided by the swapchain.
+    pub current_extent: Extend2D,
+    pub min_image_extent: Extend2D,
+    pub max_image_extent: Extend2D,
+    pub max_image_array_layers: u32,
+    pub supported_transforms: SurfaceTransformFlagsKhr,
+    pub current_transform: SurfaceTransformFlagsKhr,
+    pub supported_composite_alpha: CompositeAlphaFlagsKhr,
+    pub supported_usage_flags: ImageUsageFlags,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfaceFormatKhr {
+    pub format: Raw<Format>,
+    pub color_space: Raw<ColorSpaceKhr>,
+}
+
+/// Provided by VK_KHR_display. `display_name` belongs to the driver and may be null.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayPropertiesKhr {
+    pub display: DisplayKhr,
+    pub display_name: *const c_char,
+    /// In millimeters.
+    pub physical_dimensions: Extend2D,
+    pub physical_resolution: Extend2D,
+    pub supported_transforms: SurfaceTransformFlagsKhr,
+    pub plane_reorder_possible: Bool32,
+    pub persistent_content: Bool32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayPlanePropertiesKhr {
+    /// `NULL_HANDLE` if the plane is not in use.
+    pub current_display: DisplayKhr,
+    pub current_stack_index: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayModeParametersKhr {
+    pub visible_region: Extend2D,
+    /// In millihertz.
+    pub refresh_rate: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayModePropertiesKhr {
+    pub display_mode: DisplayModeKhr,
+    pub parameters: DisplayModeParametersKhr,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayPlaneCapabilitiesKhr {
+    pub supported_alpha: DisplayPlaneAlphaFlagsKhr,
+    pub min_src_position: Offset2D,
+    pub max_src_position: Offset2D,
+    pub min_src_extent: Extend2D,
+    pub max_src_extent: Extend2D,
+    pub min_dst_position: Offset2D,
+    pub max_dst_position: Offset2D,
+    pub min_dst_extent: Extend2D,
+    pub max_dst_extent: Extend2D,
+}
+
+c_string!(DriverNameType, MAX_DRIVER_NAME_SIZE);
+
+c_string!(DriverInfoType, MAX_DRIVER_INFO_SIZE);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConformanceVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub subminor: u8,
+    pub patch: u8,
+}
+
+impl fmt::Display for ConformanceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.subminor, self.patch)
+    }
+}
+
+/// Provided by VK_KHR_driver_properties, and part of `PhysicalDeviceVulkan12Properties` since 1.2.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceDriverProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub driver_id: Raw<DriverId>,
+    pub driver_name: DriverNameType,
+    pub driver_info: DriverInfoType,
+    pub conformance_version: ConformanceVersion,
+}
+
+vk_structure!(PhysicalDeviceDriverProperties, PhysicalDeviceDriverProperties);
+
+/// Provided by VK_EXT_pci_bus_info.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDevicePciBusInfoPropertiesExt {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub pci_domain: u32,
+    pub pci_bus: u32,
+    pub pci_device: u32,
+    pub pci_function: u32,
+}
+
+vk_structure!(PhysicalDevicePciBusInfoPropertiesExt, PhysicalDevicePciBusInfoPropertiesExt);
+
+/// Provided by VK_EXT_physical_device_drm. The numbers are those of the device's DRM nodes.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceDrmPropertiesExt {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub has_primary: Bool32,
+    pub has_render: Bool32,
+    pub primary_major: i64,
+    pub primary_minor: i64,
+    pub render_major: i64,
+    pub render_minor: i64,
+}
+
+vk_structure!(PhysicalDeviceDrmPropertiesExt, PhysicalDeviceDrmPropertiesExt);
+
+/// Core since 1.1. The LUID is only meaningful if `device_luid_valid` is set.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceIdProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub device_uuid: [u8; UUID_SIZE],
+    pub driver_uuid: [u8; UUID_SIZE],
+    pub device_luid: [u8; LUID_SIZE],
+    pub device_node_mask: u32,
+    pub device_luid_valid: Bool32,
+}
+
+vk_structure!(PhysicalDeviceIdProperties, PhysicalDeviceIdProperties);
+
+/// Core since 1.1, like the structures up to `PhysicalDeviceMaintenance3Properties`. From 1.2
+/// on `PhysicalDeviceVulkan11Features` and `...Properties` report the same in one go.
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDevice16BitStorageFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub storage_buffer16_bit_access: Bool32,
+    pub uniform_and_storage_buffer16_bit_access: Bool32,
+    pub storage_push_constant16: Bool32,
+    pub storage_input_output16: Bool32,
+}
+
+vk_structure!(PhysicalDevice16BitStorageFeatures, PhysicalDevice16BitStorageFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceMultiviewFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub multiview: Bool32,
+    pub multiview_geometry_shader: Bool32,
+    pub multiview_tessellation_shader: Bool32,
+}
+
+vk_structure!(PhysicalDeviceMultiviewFeatures, PhysicalDeviceMultiviewFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVariablePointersFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub variable_pointers_storage_buffer: Bool32,
+    pub variable_pointers: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVariablePointersFeatures, PhysicalDeviceVariablePointersFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceProtectedMemoryFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub protected_memory: Bool32,
+}
+
+vk_structure!(PhysicalDeviceProtectedMemoryFeatures, PhysicalDeviceProtectedMemoryFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceSamplerYcbcrConversionFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub sampler_ycbcr_conversion: Bool32,
+}
+
+vk_structure!(PhysicalDeviceSamplerYcbcrConversionFeatures, PhysicalDeviceSamplerYcbcrConversionFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceShaderDrawParametersFeatures {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub shader_draw_parameters: Bool32,
+}
+
+vk_structure!(PhysicalDeviceShaderDrawParametersFeatures, PhysicalDeviceShaderDrawParametersFeatures);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceSubgroupProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub subgroup_size: u32,
+    pub supported_stages: ShaderStageFlags,
+    pub supported_operations: SubgroupFeatureFlags,
+    pub quad_operations_in_all_stages: Bool32,
+}
+
+vk_structure!(PhysicalDeviceSubgroupProperties, PhysicalDeviceSubgroupProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDevicePointClippingProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub point_clipping_behavior: Raw<PointClippingBehavior>,
+}
+
+vk_structure!(PhysicalDevicePointClippingProperties, PhysicalDevicePointClippingProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceMultiviewProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub max_multiview_view_count: u32,
+    pub max_multiview_instance_index: u32,
+}
+
+vk_structure!(PhysicalDeviceMultiviewProperties, PhysicalDeviceMultiviewProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceProtectedMemoryProperties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub protected_no_fault: Bool32,
+}
+
+vk_structure!(PhysicalDeviceProtectedMemoryProperties, PhysicalDeviceProtectedMemoryProperties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceMaintenance3Properties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub max_per_set_descriptors: u32,
+    pub max_memory_allocation_size: DeviceSize,
+}
+
+vk_structure!(PhysicalDeviceMaintenance3Properties, PhysicalDeviceMaintenance3Properties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan11Features {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub storage_buffer16_bit_access: Bool32,
+    pub uniform_and_storage_buffer16_bit_access: Bool32,
+    pub storage_push_constant16: Bool32,
+    pub storage_input_output16: Bool32,
+    pub multiview: Bool32,
+    pub multiview_geometry_shader: Bool32,
+    pub multiview_tessellation_shader: Bool32,
+    pub variable_pointers_storage_buffer: Bool32,
+    pub variable_pointers: Bool32,
+    pub protected_memory: Bool32,
+    pub sampler_ycbcr_conversion: Bool32,
+    pub shader_draw_parameters: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVulkan11Features, PhysicalDeviceVulkan11Features);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan11Properties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub device_uuid: [u8; UUID_SIZE],
+    pub driver_uuid: [u8; UUID_SIZE],
+    pub device_luid: [u8; LUID_SIZE],
+    pub device_node_mask: u32,
+    pub device_luid_valid: Bool32,
+    pub subgroup_size: u32,
+    pub subgroup_supported_stages: ShaderStageFlags,
+    pub subgroup_supported_operations: SubgroupFeatureFlags,
+    pub subgroup_quad_operations_in_all_stages: Bool32,
+    pub point_clipping_behavior: Raw<PointClippingBehavior>,
+    pub max_multiview_view_count: u32,
+    pub max_multiview_instance_index: u32,
+    pub protected_no_fault: Bool32,
+    pub max_per_set_descriptors: u32,
+    pub max_memory_allocation_size: DeviceSize,
+}
+
+vk_structure!(PhysicalDeviceVulkan11Properties, PhysicalDeviceVulkan11Properties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan12Features {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub sampler_mirror_clamp_to_edge: Bool32,
+    pub draw_indirect_count: Bool32,
+    pub storage_buffer8_bit_access: Bool32,
+    pub uniform_and_storage_buffer8_bit_access: Bool32,
+    pub storage_push_constant8: Bool32,
+    pub shader_buffer_int64_atomics: Bool32,
+    pub shader_shared_int64_atomics: Bool32,
+    pub shader_float16: Bool32,
+    pub shader_int8: Bool32,
+    pub descriptor_indexing: Bool32,
+    pub shader_input_attachment_array_dynamic_indexing: Bool32,
+    pub shader_uniform_texel_buffer_array_dynamic_indexing: Bool32,
+    pub shader_storage_texel_buffer_array_dynamic_indexing: Bool32,
+    pub shader_uniform_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_sampled_image_array_non_uniform_indexing: Bool32,
+    pub shader_storage_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_storage_image_array_non_uniform_indexing: Bool32,
+    pub shader_input_attachment_array_non_uniform_indexing: Bool32,
+    pub shader_uniform_texel_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_storage_texel_buffer_array_non_uniform_indexing: Bool32,
+    pub descriptor_binding_uniform_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_sampled_image_update_after_bind: Bool32,
+    pub descriptor_binding_storage_image_update_after_bind: Bool32,
+    pub descriptor_binding_storage_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_uniform_texel_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_storage_texel_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_update_unused_while_pending: Bool32,
+    pub descriptor_binding_partially_bound: Bool32,
+    pub descriptor_binding_variable_descriptor_count: Bool32,
+    pub runtime_descriptor_array: Bool32,
+    pub sampler_filter_minmax: Bool32,
+    pub scalar_block_layout: Bool32,
+    pub imageless_framebuffer: Bool32,
+    pub uniform_buffer_standard_layout: Bool32,
+    pub shader_subgroup_extended_types: Bool32,
+    pub separate_depth_stencil_layouts: Bool32,
+    pub host_query_reset: Bool32,
+    pub timeline_semaphore: Bool32,
+    pub buffer_device_address: Bool32,
+    pub buffer_device_address_capture_replay: Bool32,
+    pub buffer_device_address_multi_device: Bool32,
+    pub vulkan_memory_model: Bool32,
+    pub vulkan_memory_model_device_scope: Bool32,
+    pub vulkan_memory_model_availability_visibility_chains: Bool32,
+    pub shader_output_viewport_index: Bool32,
+    pub shader_output_layer: Bool32,
+    pub subgroup_broadcast_dynamic_id: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVulkan12Features, PhysicalDeviceVulkan12Features);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan12Properties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub driver_id: Raw<DriverId>,
+    pub driver_name: DriverNameType,
+    pub driver_info: DriverInfoType,
+    pub conformance_version: ConformanceVersion,
+    pub denorm_behavior_independence: Raw<ShaderFloatControlsIndependence>,
+    pub rounding_mode_independence: Raw<ShaderFloatControlsIndependence>,
+    pub shader_signed_zero_inf_nan_preserve_float16: Bool32,
+    pub shader_signed_zero_inf_nan_preserve_float32: Bool32,
+    pub shader_signed_zero_inf_nan_preserve_float64: Bool32,
+    pub shader_denorm_preserve_float16: Bool32,
+    pub shader_denorm_preserve_float32: Bool32,
+    pub shader_denorm_preserve_float64: Bool32,
+    pub shader_denorm_flush_to_zero_float16: Bool32,
+    pub shader_denorm_flush_to_zero_float32: Bool32,
+    pub shader_denorm_flush_to_zero_float64: Bool32,
+    pub shader_rounding_mode_rte_float16: Bool32,
+    pub shader_rounding_mode_rte_float32: Bool32,
+    pub shader_rounding_mode_rte_float64: Bool32,
+    pub shader_rounding_mode_rtz_float16: Bool32,
+    pub shader_rounding_mode_rtz_float32: Bool32,
+    pub shader_rounding_mode_rtz_float64: Bool32,
+    pub max_update_after_bind_descriptors_in_all_pools: u32,
+    pub shader_uniform_buffer_array_non_uniform_indexing_native: Bool32,
+    pub shader_sampled_image_array_non_uniform_indexing_native: Bool32,
+    pub shader_storage_buffer_array_non_uniform_indexing_native: Bool32,
+    pub shader_storage_image_array_non_uniform_indexing_native: Bool32,
+    pub shader_input_attachment_array_non_uniform_indexing_native: Bool32,
+    pub robust_buffer_access_update_after_bind: Bool32,
+    pub quad_divergent_implicit_lod: Bool32,
+    pub max_per_stage_descriptor_update_after_bind_samplers: u32,
+    pub max_per_stage_descriptor_update_after_bind_uniform_buffers: u32,
+    pub max_per_stage_descriptor_update_after_bind_storage_buffers: u32,
+    pub max_per_stage_descriptor_update_after_bind_sampled_images: u32,
+    pub max_per_stage_descriptor_update_after_bind_storage_images: u32,
+    pub max_per_stage_descriptor_update_after_bind_input_attachments: u32,
+    pub max_per_stage_update_after_bind_resources: u32,
+    pub max_descriptor_set_update_after_bind_samplers: u32,
+    pub max_descriptor_set_update_after_bind_uniform_buffers: u32,
+    pub max_descriptor_set_update_after_bind_uniform_buffers_dynamic: u32,
+    pub max_descriptor_set_update_after_bind_storage_buffers: u32,
+    pub max_descriptor_set_update_after_bind_storage_buffers_dynamic: u32,
+    pub max_descriptor_set_update_after_bind_sampled_images: u32,
+    pub max_descriptor_set_update_after_bind_storage_images: u32,
+    pub max_descriptor_set_update_after_bind_input_attachments: u32,
+    pub supported_depth_resolve_modes: ResolveModeFlags,
+    pub supported_stencil_resolve_modes: ResolveModeFlags,
+    pub independent_resolve_none: Bool32,
+    pub independent_resolve: Bool32,
+    pub filter_minmax_single_component_formats: Bool32,
+    pub filter_minmax_image_component_mapping: Bool32,
+    pub max_timeline_semaphore_value_difference: u64,
+    pub framebuffer_integer_color_sample_counts: SampleCountFlags,
+}
+
+vk_structure!(PhysicalDeviceVulkan12Properties, PhysicalDeviceVulkan12Properties);
+
+impl PhysicalDeviceVulkan11Properties {
+    /// The part that `PhysicalDeviceIdProperties` reports on its own.
+    pub fn id_properties(&self) -> PhysicalDeviceIdProperties {
+        PhysicalDeviceIdProperties {
+            device_uuid: self.device_uuid,
+            driver_uuid: self.driver_uuid,
+            device_luid: self.device_luid,
+            device_node_mask: self.device_node_mask,
+            device_luid_valid: self.device_luid_valid,
+            ..PhysicalDeviceIdProperties::default()
+        }
+    }
+}
+
+impl PhysicalDeviceVulkan12Properties {
+    /// The part that VK_KHR_driver_properties reports on its own.
+    pub fn driver_properties(&self) -> PhysicalDeviceDriverProperties {
+        PhysicalDeviceDriverProperties {
+            driver_id: self.driver_id,
+            driver_name: self.driver_name.clone(),
+            driver_info: self.driver_info.clone(),
+            conformance_version: self.conformance_version,
+            ..PhysicalDeviceDriverProperties::default()
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan13Features {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub robust_image_access: Bool32,
+    pub inline_uniform_block: Bool32,
+    pub descriptor_binding_inline_uniform_block_update_after_bind: Bool32,
+    pub pipeline_creation_cache_control: Bool32,
+    pub private_data: Bool32,
+    pub shader_demote_to_helper_invocation: Bool32,
+    pub shader_terminate_invocation: Bool32,
+    pub subgroup_size_control: Bool32,
+    pub compute_full_subgroups: Bool32,
+    pub synchronization2: Bool32,
+    pub texture_compression_astc_hdr: Bool32,
+    pub shader_zero_initialize_workgroup_memory: Bool32,
+    pub dynamic_rendering: Bool32,
+    pub shader_integer_dot_product: Bool32,
+    pub maintenance4: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVulkan13Features, PhysicalDeviceVulkan13Features);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan13Properties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub min_subgroup_size: u32,
+    pub max_subgroup_size: u32,
+    pub max_compute_workgroup_subgroups: u32,
+    pub required_subgroup_size_stages: ShaderStageFlags,
+    pub max_inline_uniform_block_size: u32,
+    pub max_per_stage_descriptor_inline_uniform_blocks: u32,
+    pub max_per_stage_descriptor_update_after_bind_inline_uniform_blocks: u32,
+    pub max_descriptor_set_inline_uniform_blocks: u32,
+    pub max_descriptor_set_update_after_bind_inline_uniform_blocks: u32,
+    pub max_inline_uniform_total_size: u32,
+    pub integer_dot_product8_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product8_bit_signed_accelerated: Bool32,
+    pub integer_dot_product8_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product4x8_bit_packed_unsigned_accelerated: Bool32,
+    pub integer_dot_product4x8_bit_packed_signed_accelerated: Bool32,
+    pub integer_dot_product4x8_bit_packed_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product16_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product16_bit_signed_accelerated: Bool32,
+    pub integer_dot_product16_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product32_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product32_bit_signed_accelerated: Bool32,
+    pub integer_dot_product32_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product64_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product64_bit_signed_accelerated: Bool32,
+    pub integer_dot_product64_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating8_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating8_bit_signed_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating8_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating4x8_bit_packed_unsigned_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating4x8_bit_packed_signed_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating4x8_bit_packed_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating16_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating16_bit_signed_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating16_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating32_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating32_bit_signed_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating32_bit_mixed_signedness_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating64_bit_unsigned_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating64_bit_signed_accelerated: Bool32,
+    pub integer_dot_product_accumulating_saturating64_bit_mixed_signedness_accelerated: Bool32,
+    pub storage_texel_buffer_offset_alignment_bytes: DeviceSize,
+    pub storage_texel_buffer_offset_single_texel_alignment: Bool32,
+    pub uniform_texel_buffer_offset_alignment_bytes: DeviceSize,
+    pub uniform_texel_buffer_offset_single_texel_alignment: Bool32,
+    pub max_buffer_size: DeviceSize,
+}
+
+vk_structure!(PhysicalDeviceVulkan13Properties, PhysicalDeviceVulkan13Properties);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan14Features {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub global_priority_query: Bool32,
+    pub shader_subgroup_rotate: Bool32,
+    pub shader_subgroup_rotate_clustered: Bool32,
+    pub shader_float_controls2: Bool32,
+    pub shader_expect_assume: Bool32,
+    pub rectangular_lines: Bool32,
+    pub bresenham_lines: Bool32,
+    pub smooth_lines: Bool32,
+    pub stippled_rectangular_lines: Bool32,
+    pub stippled_bresenham_lines: Bool32,
+    pub stippled_smooth_lines: Bool32,
+    pub vertex_attribute_instance_rate_divisor: Bool32,
+    pub vertex_attribute_instance_rate_zero_divisor: Bool32,
+    pub index_type_uint8: Bool32,
+    pub dynamic_rendering_local_read: Bool32,
+    pub maintenance5: Bool32,
+    pub maintenance6: Bool32,
+    pub pipeline_protected_access: Bool32,
+    pub pipeline_robustness: Bool32,
+    pub host_image_copy: Bool32,
+    pub push_descriptor: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVulkan14Features, PhysicalDeviceVulkan14Features);
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicalDeviceVulkan14Properties {
+    #[serde(skip)]
+    pub s_type: Raw<StructureType>,
+    #[serde(skip)]
+    pub p_next: *mut c_void,
+    pub line_sub_pixel_precision_bits: u32,
+    pub max_vertex_attrib_divisor: u32,
+    pub supports_non_zero_first_instance: Bool32,
+    pub max_push_descriptors: u32,
+    pub dynamic_rendering_local_read_depth_stencil_attachments: Bool32,
+    pub dynamic_rendering_local_read_multisampled_attachments: Bool32,
+    pub early_fragment_multisample_coverage_after_sample_counting: Bool32,
+    pub early_fragment_sample_mask_test_before_sample_counting: Bool32,
+    pub depth_stencil_swizzle_one_support: Bool32,
+    pub polygon_mode_point_size: Bool32,
+    pub non_strict_single_pixel_wide_lines_use_parallelogram: Bool32,
+    pub non_strict_wide_lines_use_parallelogram: Bool32,
+    pub block_texel_view_compatible_multiple_layers: Bool32,
+    pub max_combined_image_sampler_descriptor_count: u32,
+    pub fragment_shading_rate_clamp_combiner_inputs: Bool32,
+    pub default_robustness_storage_buffers: Raw<PipelineRobustnessBufferBehavior>,
+    pub default_robustness_uniform_buffers: Raw<PipelineRobustnessBufferBehavior>,
+    pub default_robustness_vertex_inputs: Raw<PipelineRobustnessBufferBehavior>,
+    pub default_robustness_images: Raw<PipelineRobustnessImageBehavior>,
+    pub copy_src_layout_count: u32,
+    #[serde(skip)]
+    pub p_copy_src_layouts: *mut Raw<ImageLayout>,
+    pub copy_dst_layout_count: u32,
+    #[serde(skip)]
+    pub p_copy_dst_layouts: *mut Raw<ImageLayout>,
+    pub optimal_tiling_layout_uuid: [u8; UUID_SIZE],
+    pub identical_memory_type_requirements: Bool32,
+}
+
+vk_structure!(PhysicalDeviceVulkan14Properties, PhysicalDeviceVulkan14Properties);
@@ -1,226 +1,1607 @@
 use lib;
-use std::default::{Default};
-use std::ffi::CStr;
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+use std::error;
+use std::ffi::{CStr, CString};
+use std::fmt;
 use std::io;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::rc::{Rc};
 use std::result;
 
 use vk;
 
-pub type Result<T> = result::Result<T, vk::Result>;
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// None of the candidate loader libraries could be opened.
+    LoaderNotFound(Vec<LoadFailure>),
+    /// The loader does not provide a command vkinfo needs.
+    MissingSymbol(&'static str),
+    /// A Vulkan command returned an error code.
+    Call { function: &'static str, result: vk::Result },
+    /// A layer requested for the instance is not installed.
+    LayerNotPresent(String),
+    /// An extension requested for the instance is provided neither by the implementation nor
+    /// by any of the requested layers.
+    ExtensionNotPresent(String),
+    /// A query that had failed on the machine a replayed report was saved on.
+    Saved(String),
+}
+
+impl Error {
+    /// The `vk::Result` returned by the failing command, if this is a call failure.
+    pub fn vk_result(&self) -> Option<vk::Result> {
+        match *self {
+            Error::Call { result, .. } => Some(result),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::LoaderNotFound(ref failures) => {
+                let reasons: Vec<String> = failures.iter().map(|failure| failure.to_string()).collect();
+                write!(f, "no Vulkan loader found ({})", reasons.join("; "))
+            }
+            Error::MissingSymbol(name) => write!(f, "Vulkan loader does not provide {}", name),
+            Error::Call { function, result } => write!(f, "{} failed: {:?}", function, result),
+            Error::LayerNotPresent(ref name) => write!(f, "instance layer {} is not available", name),
+            Error::ExtensionNotPresent(ref name) => write!(f, "instance extension {} is not available", name),
+            Error::Saved(ref message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Turns the result of `function` into an error unless it is `Success`.
+fn check(function: &'static str, result: vk::Raw<vk::Result>) -> Result<()> {
+    match result.get() {
+        vk::Result::Success => Ok(()),
+        result => Err(Error::Call { function, result }),
+    }
+}
+
+/// Runs Vulkan's two-call enumeration: ask for the number of elements, then fill a buffer of
+/// that size. If elements were added in between, the driver returns `Incomplete` and the whole
+/// enumeration starts over. Only the elements the driver reports as written are kept.
+unsafe fn enumerate<T, F>(function: &'static str, mut call: F) -> Result<Vec<T>>
+    where F: FnMut(*mut u32, *mut T) -> vk::Raw<vk::Result>
+{
+    loop {
+        let mut count: u32 = 0;
+        check(function, call(&mut count, ptr::null_mut()))?;
+
+        let capacity = count as usize;
+        let mut elements = Vec::with_capacity(capacity);
+        let result = call(&mut count, elements.as_mut_ptr());
+        if result.get() == vk::Result::Incomplete {
+            continue;
+        }
+        check(function, result)?;
+
+        elements.set_len(cmp::min(count as usize, capacity));
+        return Ok(elements);
+    }
+}
+
+/// `enumerate` for structures, which need their `s_type` set before the driver fills them in.
+unsafe fn enumerate_structures<T, F>(function: &'static str, mut call: F) -> Result<Vec<T>>
+    where T: vk::Structure, F: FnMut(*mut u32, *mut T) -> vk::Raw<vk::Result>
+{
+    loop {
+        let mut count: u32 = 0;
+        check(function, call(&mut count, ptr::null_mut()))?;
+
+        let mut elements: Vec<T> = (0..count).map(|_| T::default()).collect();
+        let result = call(&mut count, elements.as_mut_ptr());
+        if result.get() == vk::Result::Incomplete {
+            continue;
+        }
+        check(function, result)?;
+
+        elements.truncate(count as usize);
+        return Ok(elements);
+    }
+}
+
+/// `enumerate` for commands that cannot fail and therefore cannot report `Incomplete` either.
+unsafe fn enumerate_infallible<T, F>(mut call: F) -> Vec<T>
+    where F: FnMut(*mut u32, *mut T)
+{
+    let mut count: u32 = 0;
+    call(&mut count, ptr::null_mut());
+
+    let capacity = count as usize;
+    let mut elements = Vec::with_capacity(capacity);
+    call(&mut count, elements.as_mut_ptr());
+
+    elements.set_len(cmp::min(count as usize, capacity));
+    elements
+}
+
+/// Casts a struct that can be part of a `p_next` chain to its header.
+fn base_out<T: vk::Structure>(structure: &mut T) -> *mut vk::BaseOutStructure {
+    structure as *mut T as *mut vk::BaseOutStructure
+}
+
+/// Links `structures` into one `p_next` chain in the given order, runs `call` with it, and
+/// unlinks them again so the values can be returned without pointing at each other.
+unsafe fn with_chain<F: FnOnce()>(structures: &[*mut vk::BaseOutStructure], call: F) {
+    link(structures);
+    call();
+    unlink(structures);
+}
+
+unsafe fn link(structures: &[*mut vk::BaseOutStructure]) {
+    for pair in structures.windows(2) {
+        (*pair[0]).p_next = pair[1];
+    }
+}
+
+unsafe fn unlink(structures: &[*mut vk::BaseOutStructure]) {
+    for &structure in structures {
+        (*structure).p_next = ptr::null_mut();
+    }
+}
+
+#[cfg(windows)]
+const LOADER_NAMES: &[&str] = &["vulkan-1.dll"];
+#[cfg(target_os = "macos")]
+const LOADER_NAMES: &[&str] = &["libvulkan.1.dylib", "libvulkan.dylib"];
+#[cfg(not(any(windows, target_os = "macos")))]
+const LOADER_NAMES: &[&str] = &["libvulkan.so.1", "libvulkan.so"];
+
+/// A loader library that could not be opened, together with the reason.
+#[derive(Debug, Clone)]
+pub struct LoadFailure {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl fmt::Display for LoadFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.reason)
+    }
+}
 
 struct Library {
     #[allow(dead_code)]
     library: lib::Library,
-    get_instance_proc_addr: unsafe extern fn(*const c_void, *const c_char) -> *const c_void,
+    path: PathBuf,
+    failures: Vec<LoadFailure>,
+    get_instance_proc_addr: unsafe extern "system" fn(*const c_void, *const c_char) -> *const c_void,
 }
 
 impl Library {
-    fn new() -> io::Result<Library> {
-        let library = lib::Library::new("vulkan-1.dll")?;
+    /// Opens the first loader in `candidates` that exports `vkGetInstanceProcAddr`.
+    fn new(candidates: &[PathBuf]) -> Result<Library> {
+        let mut failures = Vec::new();
+
+        for path in candidates {
+            match Library::open(path) {
+                Ok((library, get_instance_proc_addr)) => {
+                    return Ok(Library {
+                        library,
+                        path: path.clone(),
+                        failures,
+                        get_instance_proc_addr,
+                    });
+                }
+                Err(err) => failures.push(LoadFailure { path: path.clone(), reason: err.to_string() }),
+            }
+        }
+
+        Err(Error::LoaderNotFound(failures))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn open(path: &Path) -> io::Result<(lib::Library, unsafe extern "system" fn(*const c_void, *const c_char) -> *const c_void)> {
+        let library = lib::Library::new(path)?;
 
         let get_instance_proc_addr = unsafe {
-            let f: lib::Symbol<unsafe extern fn(*const c_void, *const c_char) -> *const c_void> = library.get(b"vkGetInstanceProcAddr\0")?;
+            let f: lib::Symbol<unsafe extern "system" fn(*const c_void, *const c_char) -> *const c_void> = library.get(b"vkGetInstanceProcAddr\0")?;
             *f.into_raw().deref()
         };
 
-        Ok(Library {
-            library: library,
-            get_instance_proc_addr: get_instance_proc_addr,
-        })
+        Ok((library, get_instance_proc_addr))
     }
 
-    fn load<T>(&self, instance: vk::Instance, name: &[u8]) -> io::Result<T> {
+    fn load<T>(&self, instance: vk::Instance, name: &'static str) -> Result<T> {
+        let cname = CString::new(name).unwrap();
         unsafe {
-            let cname = CStr::from_bytes_with_nul_unchecked(name);
             let function = (self.get_instance_proc_addr)(instance, cname.as_ptr());
-            if function == ptr::null() {
-                return Err(io::Error::from(io::ErrorKind::NotFound));
+            if function.is_null() {
+                return Err(Error::MissingSymbol(name));
             }
             Ok(mem::transmute_copy(&function))
         }
     }
+
+    /// Like `load`, for functions that may legitimately be absent.
+    fn load_optional<T>(&self, instance: vk::Instance, name: &'static str) -> Option<T> {
+        self.load(instance, name).ok()
+    }
 }
 
 pub struct Entry {
     #[allow(dead_code)]
     library: Rc<Library>,
-    create_instance: unsafe extern fn(*const vk::InstanceCreateInfo, *const vk::AllocationCallbacks, *mut vk::Instance) -> vk::Result,
-    enumerate_instance_extension_properties: unsafe extern fn(*const c_char, *mut u32, *mut vk::ExtensionProperties) -> vk::Result,
-    enumerate_instance_layer_properties: unsafe extern fn(*mut u32, *mut vk::LayerProperties) -> vk::Result,
+    create_instance: unsafe extern "system" fn(*const vk::InstanceCreateInfo, *const vk::AllocationCallbacks, *mut vk::Instance) -> vk::Raw<vk::Result>,
+    enumerate_instance_extension_properties: unsafe extern "system" fn(*const c_char, *mut u32, *mut vk::ExtensionProperties) -> vk::Raw<vk::Result>,
+    enumerate_instance_layer_properties: unsafe extern "system" fn(*mut u32, *mut vk::LayerProperties) -> vk::Raw<vk::Result>,
+    enumerate_instance_version: Option<unsafe extern "system" fn(*mut u32) -> vk::Raw<vk::Result>>,
 }
 
 impl Entry {
 
-    pub fn new() -> io::Result<Entry> {
-        let library = Rc::new(Library::new()?);
+    /// Loads the platform's Vulkan loader, trying each of its usual names in turn.
+    pub fn new() -> Result<Entry> {
+        let candidates: Vec<PathBuf> = LOADER_NAMES.iter().map(PathBuf::from).collect();
+        Entry::from_library(Library::new(&candidates)?)
+    }
+
+    /// Loads the Vulkan loader (or an ICD exporting `vkGetInstanceProcAddr`) from `path`.
+    pub fn with_loader<P: AsRef<Path>>(path: P) -> Result<Entry> {
+        Entry::from_library(Library::new(&[path.as_ref().to_path_buf()])?)
+    }
+
+    fn from_library(library: Library) -> Result<Entry> {
+        let library = Rc::new(library);
 
-        let create_instance = library.load(ptr::null(), b"vkCreateInstance\0")?;
-        let enumerate_instance_extension_properties = library.load(ptr::null(), b"vkEnumerateInstanceExtensionProperties\0")?;
-        let enumerate_instance_layer_properties = library.load(ptr::null(), b"vkEnumerateInstanceLayerProperties\0")?;
+        let create_instance = library.load(ptr::null(), "vkCreateInstance")?;
+        let enumerate_instance_extension_properties = library.load(ptr::null(), "vkEnumerateInstanceExtensionProperties")?;
+        let enumerate_instance_layer_properties = library.load(ptr::null(), "vkEnumerateInstanceLayerProperties")?;
+        let enumerate_instance_version = library.load_optional(ptr::null(), "vkEnumerateInstanceVersion");
 
         Ok(Entry {
-            library: library,
-            create_instance: create_instance,
-            enumerate_instance_extension_properties: enumerate_instance_extension_properties,
-            enumerate_instance_layer_properties: enumerate_instance_layer_properties,
+            library,
+            create_instance,
+            enumerate_instance_extension_properties,
+            enumerate_instance_layer_properties,
+            enumerate_instance_version,
         })
     }
 
-    pub fn create_instance(&self) -> io::Result<Instance> {
+    /// Path of the loader library that was opened.
+    pub fn loader_path(&self) -> &Path {
+        &self.library.path
+    }
+
+    /// Candidates that were tried and rejected before `loader_path` was opened.
+    pub fn loader_failures(&self) -> &[LoadFailure] {
+        &self.library.failures
+    }
+
+    /// The highest Vulkan version the loader supports for instances. Loaders that predate
+    /// `vkEnumerateInstanceVersion` only support 1.0.
+    pub fn enumerate_instance_version(&self) -> Result<vk::Version> {
+        let enumerate_instance_version = match self.enumerate_instance_version {
+            Some(function) => function,
+            None => return Ok(vk::Version::from(vk::API_VERSION_1_0)),
+        };
+
+        let mut version = 0;
         unsafe {
-            let create_info = vk::InstanceCreateInfo {
-                s_type: vk::StructureType::InstanceCreateInfo,
-                p_next: ptr::null(),
-                flags: vk::InstanceCreateFlags::Reserved,
-                p_application_info: ptr::null(),
-                enabled_layer_count: 0,
-                pp_enabled_layer_names: ptr::null(),
-                enabled_extension_count: 0,
-                pp_enabled_extension_names: ptr::null(),
-            };
-            let mut instance: vk::Instance = ptr::null();
+            check("vkEnumerateInstanceVersion", enumerate_instance_version(&mut version))?;
+        }
+        Ok(vk::Version::from(version))
+    }
 
-            let err = (self.create_instance)(&create_info, ptr::null(), &mut instance);
-            if err != vk::Result::Success {
-                return Err(io::Error::from(io::ErrorKind::Other));
-            }
+    /// Creates an instance with default parameters, see `InstanceBuilder`.
+    pub fn create_instance(&self) -> Result<Instance> {
+        InstanceBuilder::new().build(self)
+    }
 
-            Instance::new(&self.library, instance)
+    /// Extensions provided by the implementation, or by `layer_name` if given.
+    pub fn enumerate_instance_extension_properties(&self, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        let layer_name = layer_name_c_string("vkEnumerateInstanceExtensionProperties", layer_name)?;
+        let layer_name = layer_name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+
+        unsafe {
+            enumerate("vkEnumerateInstanceExtensionProperties", |count, extensions| {
+                (self.enumerate_instance_extension_properties)(layer_name, count, extensions)
+            })
         }
     }
 
-    pub fn enumerate_instance_extension_properties(&self, layer_name: *const c_char) -> Result<Vec<vk::ExtensionProperties>> {
+    pub fn enumerate_instance_layer_properties(&self) -> Result<Vec<vk::LayerProperties>> {
         unsafe {
-            let mut count: u32 = 0;
+            enumerate("vkEnumerateInstanceLayerProperties", |count, layers| {
+                (self.enumerate_instance_layer_properties)(count, layers)
+            })
+        }
+    }
+}
 
-            let err = (self.enumerate_instance_extension_properties)(layer_name, &mut count, ptr::null_mut());
-            if err != vk::Result::Success {
-                return Err(err)
-            }
+/// Parameters for creating an `Instance`. Requested layers and extensions are checked
+/// against what the loader reports before the instance is created.
+#[derive(Debug, Clone)]
+pub struct InstanceBuilder {
+    application_name: String,
+    application_version: u32,
+    engine_name: String,
+    engine_version: u32,
+    api_version: u32,
+    layers: Vec<String>,
+    extensions: Vec<String>,
+}
 
-            let mut extensions = Vec::with_capacity(count as usize);
-            extensions.set_len(count as usize);
+impl Default for InstanceBuilder {
+    fn default() -> InstanceBuilder {
+        InstanceBuilder {
+            application_name: String::new(),
+            application_version: 0,
+            engine_name: String::new(),
+            engine_version: 0,
+            api_version: vk::API_VERSION_1_0,
+            layers: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+}
 
-            let err = (self.enumerate_instance_extension_properties)(layer_name, &mut count, extensions.as_mut_ptr());
-            if err != vk::Result::Success {
-                return Err(err)
-            }
+impl InstanceBuilder {
+    pub fn new() -> InstanceBuilder {
+        InstanceBuilder::default()
+    }
 
-            Ok(extensions)
-        }
+    pub fn application_name<S: Into<String>>(mut self, name: S) -> InstanceBuilder {
+        self.application_name = name.into();
+        self
     }
 
-    pub fn enumerate_instance_layer_properties(&self) -> Result<Vec<vk::LayerProperties>> {
-        unsafe {
-            let mut count: u32 = 0;
+    pub fn application_version(mut self, version: u32) -> InstanceBuilder {
+        self.application_version = version;
+        self
+    }
 
-            let err = (self.enumerate_instance_layer_properties)(&mut count, ptr::null_mut());
-            if err != vk::Result::Success {
-                return Err(err)
-            }
+    pub fn engine_name<S: Into<String>>(mut self, name: S) -> InstanceBuilder {
+        self.engine_name = name.into();
+        self
+    }
+
+    pub fn engine_version(mut self, version: u32) -> InstanceBuilder {
+        self.engine_version = version;
+        self
+    }
 
-            let mut layers = Vec::with_capacity(count as usize);
-            layers.set_len(count as usize);
+    /// The highest Vulkan version the instance should use, `vk::API_VERSION_1_0` by default.
+    /// It is lowered to what the loader supports if necessary.
+    pub fn api_version(mut self, version: u32) -> InstanceBuilder {
+        self.api_version = version;
+        self
+    }
+
+    pub fn layer<S: Into<String>>(mut self, name: S) -> InstanceBuilder {
+        self.layers.push(name.into());
+        self
+    }
 
-            let err = (self.enumerate_instance_layer_properties)(&mut count, layers.as_mut_ptr());
-            if err != vk::Result::Success {
-                return Err(err)
+    pub fn extension<S: Into<String>>(mut self, name: S) -> InstanceBuilder {
+        self.extensions.push(name.into());
+        self
+    }
+
+    pub fn build(&self, entry: &Entry) -> Result<Instance> {
+        self.check_availability(entry)?;
+
+        let loader_version = vk::Version { patch: 0, ..entry.enumerate_instance_version()? };
+        let api_version = cmp::min(self.api_version, u32::from(loader_version));
+
+        let application_name = c_string(&self.application_name);
+        let engine_name = c_string(&self.engine_name);
+        let layers: Vec<CString> = self.layers.iter().map(|name| c_string(name)).collect();
+        let extensions: Vec<CString> = self.extensions.iter().map(|name| c_string(name)).collect();
+        let layer_names: Vec<*const c_char> = layers.iter().map(|name| name.as_ptr()).collect();
+        let extension_names: Vec<*const c_char> = extensions.iter().map(|name| name.as_ptr()).collect();
+
+        let application_info = vk::ApplicationInfo {
+            s_type: vk::StructureType::ApplicationInfo.into(),
+            p_next: ptr::null(),
+            p_application_name: application_name.as_ptr(),
+            application_version: self.application_version,
+            p_engine_name: engine_name.as_ptr(),
+            engine_version: self.engine_version,
+            api_version,
+        };
+        let create_info = vk::InstanceCreateInfo {
+            s_type: vk::StructureType::InstanceCreateInfo.into(),
+            p_next: ptr::null(),
+            flags: vk::InstanceCreateFlags::Reserved,
+            p_application_info: &application_info,
+            enabled_layer_count: layer_names.len() as u32,
+            pp_enabled_layer_names: layer_names.as_ptr(),
+            enabled_extension_count: extension_names.len() as u32,
+            pp_enabled_extension_names: extension_names.as_ptr(),
+        };
+
+        unsafe {
+            let mut instance: vk::Instance = ptr::null();
+            check("vkCreateInstance", (entry.create_instance)(&create_info, ptr::null(), &mut instance))?;
+            Instance::new(&entry.library, instance, api_version, self.extensions.clone())
+        }
+    }
+
+    /// Fails with the first requested layer or extension the loader does not know about.
+    fn check_availability(&self, entry: &Entry) -> Result<()> {
+        if self.layers.is_empty() && self.extensions.is_empty() {
+            return Ok(());
+        }
+
+        let available_layers = entry.enumerate_instance_layer_properties()?;
+        for layer in &self.layers {
+            if !available_layers.iter().any(|available| available.layer_name.to_string_lossy() == *layer) {
+                return Err(Error::LayerNotPresent(layer.clone()));
             }
+        }
 
-            Ok(layers)
+        let mut available_extensions = entry.enumerate_instance_extension_properties(None)?;
+        for layer in &self.layers {
+            available_extensions.extend(entry.enumerate_instance_extension_properties(Some(layer))?);
+        }
+        for extension in &self.extensions {
+            if !available_extensions.iter().any(|available| available.extension_name.to_string_lossy() == *extension) {
+                return Err(Error::ExtensionNotPresent(extension.clone()));
+            }
         }
+
+        Ok(())
+    }
+}
+
+/// Converts a layer name for `function`. No layer can have a name with an embedded nul, so
+/// such names fail the way an unknown layer would.
+fn layer_name_c_string(function: &'static str, layer_name: Option<&str>) -> Result<Option<CString>> {
+    match layer_name {
+        Some(name) => CString::new(name).map(Some).map_err(|_| Error::Call { function, result: vk::Result::ErrorLayerNotPresent }),
+        None => Ok(None),
     }
 }
 
+/// Converts `s` for passing to Vulkan, cutting it off at an embedded nul.
+fn c_string(s: &str) -> CString {
+    CString::new(s.split('\0').next().unwrap_or("")).unwrap()
+}
+
+/// A Vulkan instance, destroyed when dropped.
 pub struct Instance {
     #[allow(dead_code)]
     library: Rc<Library>,
     instance: vk::Instance,
-    destroy_instance: unsafe extern fn(vk::Instance, *const vk::AllocationCallbacks),
-    enumerate_physical_devices: unsafe extern fn(vk::Instance, *mut u32, *mut vk::PhysicalDevice) -> vk::Result,
-    get_physical_device_features: unsafe extern fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceFeatures),
-    get_physical_device_properties: unsafe extern fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceProperties),
-    get_physical_device_queue_family_properties: unsafe extern fn(vk::PhysicalDevice, *mut u32, *mut vk::QueueFamilyProperties),
+    api_version: u32,
+    extensions: Vec<String>,
+    /// Names of the extensions each physical device supports, enumerated on first use.
+    device_extensions: RefCell<HashMap<vk::PhysicalDevice, Vec<String>>>,
+    destroy_instance: unsafe extern "system" fn(vk::Instance, *const vk::AllocationCallbacks),
+    enumerate_physical_devices: unsafe extern "system" fn(vk::Instance, *mut u32, *mut vk::PhysicalDevice) -> vk::Raw<vk::Result>,
+    get_physical_device_features: unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceFeatures),
+    get_physical_device_properties: unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceProperties),
+    get_physical_device_queue_family_properties: unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::QueueFamilyProperties),
+    get_physical_device_memory_properties: unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceMemoryProperties),
+    get_physical_device_format_properties: unsafe extern "system" fn(vk::PhysicalDevice, vk::Raw<vk::Format>, *mut vk::FormatProperties),
+    get_physical_device_sparse_image_format_properties: unsafe extern "system" fn(vk::PhysicalDevice, vk::Raw<vk::Format>, vk::Raw<vk::ImageType>, vk::SampleCountFlags, vk::ImageUsageFlags, vk::Raw<vk::ImageTiling>, *mut u32, *mut vk::SparseImageFormatProperties),
+    get_physical_device_image_format_properties: unsafe extern "system" fn(vk::PhysicalDevice, vk::Raw<vk::Format>, vk::Raw<vk::ImageType>, vk::Raw<vk::ImageTiling>, vk::ImageUsageFlags, vk::ImageCreateFlags, *mut vk::ImageFormatProperties) -> vk::Raw<vk::Result>,
+    enumerate_device_extension_properties: unsafe extern "system" fn(vk::PhysicalDevice, *const c_char, *mut u32, *mut vk::ExtensionProperties) -> vk::Raw<vk::Result>,
+    enumerate_device_layer_properties: unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::LayerProperties) -> vk::Raw<vk::Result>,
+    create_device: unsafe extern "system" fn(vk::PhysicalDevice, *const vk::DeviceCreateInfo, *const vk::AllocationCallbacks, *mut vk::Device) -> vk::Raw<vk::Result>,
+    destroy_device: unsafe extern "system" fn(vk::Device, *const vk::AllocationCallbacks),
+    get_physical_device_features2: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceFeatures2)>,
+    get_physical_device_properties2: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceProperties2)>,
+    get_physical_device_memory_properties2: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut vk::PhysicalDeviceMemoryProperties2)>,
+    get_physical_device_queue_family_properties2: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::QueueFamilyProperties2)>,
+    enumerate_physical_device_groups: Option<unsafe extern "system" fn(vk::Instance, *mut u32, *mut vk::PhysicalDeviceGroupProperties) -> vk::Raw<vk::Result>>,
+    destroy_surface: Option<unsafe extern "system" fn(vk::Instance, vk::SurfaceKhr, *const vk::AllocationCallbacks)>,
+    create_headless_surface: Option<unsafe extern "system" fn(vk::Instance, *const vk::HeadlessSurfaceCreateInfoExt, *const vk::AllocationCallbacks, *mut vk::SurfaceKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_surface_support: Option<unsafe extern "system" fn(vk::PhysicalDevice, u32, vk::SurfaceKhr, *mut vk::Bool32) -> vk::Raw<vk::Result>>,
+    get_physical_device_surface_capabilities: Option<unsafe extern "system" fn(vk::PhysicalDevice, vk::SurfaceKhr, *mut vk::SurfaceCapabilitiesKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_surface_formats: Option<unsafe extern "system" fn(vk::PhysicalDevice, vk::SurfaceKhr, *mut u32, *mut vk::SurfaceFormatKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_surface_present_modes: Option<unsafe extern "system" fn(vk::PhysicalDevice, vk::SurfaceKhr, *mut u32, *mut vk::Raw<vk::PresentModeKhr>) -> vk::Raw<vk::Result>>,
+    get_physical_device_display_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::DisplayPropertiesKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_display_plane_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::DisplayPlanePropertiesKhr) -> vk::Raw<vk::Result>>,
+    get_display_plane_supported_displays: Option<unsafe extern "system" fn(vk::PhysicalDevice, u32, *mut u32, *mut vk::DisplayKhr) -> vk::Raw<vk::Result>>,
+    get_display_mode_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, vk::DisplayKhr, *mut u32, *mut vk::DisplayModePropertiesKhr) -> vk::Raw<vk::Result>>,
+    get_display_plane_capabilities: Option<unsafe extern "system" fn(vk::PhysicalDevice, vk::DisplayModeKhr, u32, *mut vk::DisplayPlaneCapabilitiesKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_video_capabilities: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::VideoProfileInfoKhr, *mut vk::VideoCapabilitiesKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_video_format_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::PhysicalDeviceVideoFormatInfoKhr, *mut u32, *mut vk::VideoFormatPropertiesKhr) -> vk::Raw<vk::Result>>,
+    get_physical_device_image_format_properties2: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::PhysicalDeviceImageFormatInfo2, *mut vk::ImageFormatProperties2) -> vk::Raw<vk::Result>>,
+    get_physical_device_external_buffer_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::PhysicalDeviceExternalBufferInfo, *mut vk::ExternalBufferProperties)>,
+    get_physical_device_external_semaphore_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::PhysicalDeviceExternalSemaphoreInfo, *mut vk::ExternalSemaphoreProperties)>,
+    get_physical_device_external_fence_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *const vk::PhysicalDeviceExternalFenceInfo, *mut vk::ExternalFenceProperties)>,
+    get_physical_device_tool_properties: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::PhysicalDeviceToolProperties) -> vk::Raw<vk::Result>>,
+    get_physical_device_tool_properties_ext: Option<unsafe extern "system" fn(vk::PhysicalDevice, *mut u32, *mut vk::PhysicalDeviceToolProperties) -> vk::Raw<vk::Result>>,
 }
 
 impl Instance {
-    fn new(library: &Rc<Library>, instance: vk::Instance) -> io::Result<Instance> {
-        let destroy_instance = library.load(instance, b"vkDestroyInstance\0")?;
-        let enumerate_physical_devices = library.load(instance, b"vkEnumeratePhysicalDevices\0")?;
-        let get_physical_device_features = library.load(instance, b"vkGetPhysicalDeviceFeatures\0")?;
-        let get_physical_device_properties = library.load(instance, b"vkGetPhysicalDeviceProperties\0")?;
-        let get_physical_device_queue_family_properties = library.load(instance, b"vkGetPhysicalDeviceQueueFamilyProperties")?;
+    fn new(library: &Rc<Library>, instance: vk::Instance, api_version: u32, extensions: Vec<String>) -> Result<Instance> {
+        let destroy_instance: unsafe extern "system" fn(vk::Instance, *const vk::AllocationCallbacks) = library.load(instance, "vkDestroyInstance")?;
+
+        Instance::load(library, instance, api_version, extensions, destroy_instance).inspect_err(|_| unsafe {
+            destroy_instance(instance, ptr::null());
+        })
+    }
+
+    fn load(library: &Rc<Library>, instance: vk::Instance, api_version: u32, extensions: Vec<String>, destroy_instance: unsafe extern "system" fn(vk::Instance, *const vk::AllocationCallbacks)) -> Result<Instance> {
+        let enumerate_physical_devices = library.load(instance, "vkEnumeratePhysicalDevices")?;
+        let get_physical_device_features = library.load(instance, "vkGetPhysicalDeviceFeatures")?;
+        let get_physical_device_properties = library.load(instance, "vkGetPhysicalDeviceProperties")?;
+        let get_physical_device_queue_family_properties = library.load(instance, "vkGetPhysicalDeviceQueueFamilyProperties")?;
+        let get_physical_device_memory_properties = library.load(instance, "vkGetPhysicalDeviceMemoryProperties")?;
+        let get_physical_device_format_properties = library.load(instance, "vkGetPhysicalDeviceFormatProperties")?;
+        let get_physical_device_sparse_image_format_properties = library.load(instance, "vkGetPhysicalDeviceSparseImageFormatProperties")?;
+        let get_physical_device_image_format_properties = library.load(instance, "vkGetPhysicalDeviceImageFormatProperties")?;
+        let enumerate_device_extension_properties = library.load(instance, "vkEnumerateDeviceExtensionProperties")?;
+        let enumerate_device_layer_properties = library.load(instance, "vkEnumerateDeviceLayerProperties")?;
+        let create_device = library.load(instance, "vkCreateDevice")?;
+        let destroy_device = library.load(instance, "vkDestroyDevice")?;
+        let get_physical_device_features2 = library.load_optional(instance, "vkGetPhysicalDeviceFeatures2");
+        let get_physical_device_properties2 = library.load_optional(instance, "vkGetPhysicalDeviceProperties2");
+        let get_physical_device_memory_properties2 = library.load_optional(instance, "vkGetPhysicalDeviceMemoryProperties2");
+        let get_physical_device_queue_family_properties2 = library.load_optional(instance, "vkGetPhysicalDeviceQueueFamilyProperties2");
+        let enumerate_physical_device_groups = library.load_optional(instance, "vkEnumeratePhysicalDeviceGroups");
+        let destroy_surface = library.load_optional(instance, "vkDestroySurfaceKHR");
+        let create_headless_surface = library.load_optional(instance, "vkCreateHeadlessSurfaceEXT");
+        let get_physical_device_surface_support = library.load_optional(instance, "vkGetPhysicalDeviceSurfaceSupportKHR");
+        let get_physical_device_surface_capabilities = library.load_optional(instance, "vkGetPhysicalDeviceSurfaceCapabilitiesKHR");
+        let get_physical_device_surface_formats = library.load_optional(instance, "vkGetPhysicalDeviceSurfaceFormatsKHR");
+        let get_physical_device_surface_present_modes = library.load_optional(instance, "vkGetPhysicalDeviceSurfacePresentModesKHR");
+        let get_physical_device_display_properties = library.load_optional(instance, "vkGetPhysicalDeviceDisplayPropertiesKHR");
+        let get_physical_device_display_plane_properties = library.load_optional(instance, "vkGetPhysicalDeviceDisplayPlanePropertiesKHR");
+        let get_display_plane_supported_displays = library.load_optional(instance, "vkGetDisplayPlaneSupportedDisplaysKHR");
+        let get_display_mode_properties = library.load_optional(instance, "vkGetDisplayModePropertiesKHR");
+        let get_display_plane_capabilities = library.load_optional(instance, "vkGetDisplayPlaneCapabilitiesKHR");
+        let get_physical_device_video_capabilities = library.load_optional(instance, "vkGetPhysicalDeviceVideoCapabilitiesKHR");
+        let get_physical_device_video_format_properties = library.load_optional(instance, "vkGetPhysicalDeviceVideoFormatPropertiesKHR");
+        let get_physical_device_image_format_properties2 = library.load_optional(instance, "vkGetPhysicalDeviceImageFormatProperties2");
+        let get_physical_device_external_buffer_properties = library.load_optional(instance, "vkGetPhysicalDeviceExternalBufferProperties");
+        let get_physical_device_external_semaphore_properties = library.load_optional(instance, "vkGetPhysicalDeviceExternalSemaphoreProperties");
+        let get_physical_device_external_fence_properties = library.load_optional(instance, "vkGetPhysicalDeviceExternalFenceProperties");
+        let get_physical_device_tool_properties = library.load_optional(instance, "vkGetPhysicalDeviceToolProperties");
+        let get_physical_device_tool_properties_ext = library.load_optional(instance, "vkGetPhysicalDeviceToolPropertiesEXT");
 
         Ok(Instance {
             library: library.clone(),
-            instance: instance,
-            destroy_instance: destroy_instance,
-            enumerate_physical_devices: enumerate_physical_devices,
-            get_physical_device_features: get_physical_device_features,
-            get_physical_device_properties: get_physical_device_properties,
-            get_physical_device_queue_family_properties: get_physical_device_queue_family_properties,
+            instance,
+            api_version,
+            extensions,
+            device_extensions: RefCell::new(HashMap::new()),
+            destroy_instance,
+            enumerate_physical_devices,
+            get_physical_device_features,
+            get_physical_device_properties,
+            get_physical_device_queue_family_properties,
+            get_physical_device_memory_properties,
+            get_physical_device_format_properties,
+            get_physical_device_image_format_properties,
+            get_physical_device_sparse_image_format_properties,
+            enumerate_device_extension_properties,
+            enumerate_device_layer_properties,
+            create_device,
+            destroy_device,
+            get_physical_device_features2,
+            get_physical_device_properties2,
+            get_physical_device_memory_properties2,
+            get_physical_device_queue_family_properties2,
+            enumerate_physical_device_groups,
+            destroy_surface,
+            create_headless_surface,
+            get_physical_device_surface_support,
+            get_physical_device_surface_capabilities,
+            get_physical_device_surface_formats,
+            get_physical_device_surface_present_modes,
+            get_physical_device_display_properties,
+            get_physical_device_display_plane_properties,
+            get_display_plane_supported_displays,
+            get_display_mode_properties,
+            get_display_plane_capabilities,
+            get_physical_device_video_capabilities,
+            get_physical_device_video_format_properties,
+            get_physical_device_image_format_properties2,
+            get_physical_device_external_buffer_properties,
+            get_physical_device_external_semaphore_properties,
+            get_physical_device_external_fence_properties,
+            get_physical_device_tool_properties,
+            get_physical_device_tool_properties_ext,
         })
     }
 
-    pub fn destroy_instance(&self) {
+    /// The Vulkan version the instance was created for.
+    pub fn api_version(&self) -> u32 {
+        self.api_version
+    }
+
+    /// Whether `name` was among the extensions the instance was created with.
+    pub fn is_extension_enabled(&self, name: &str) -> bool {
+        self.extensions.iter().any(|extension| extension == name)
+    }
+
+    pub fn enumerate_physical_devices(&self) -> Result<Vec<PhysicalDevice<'_>>> {
+        let handles = unsafe {
+            enumerate("vkEnumeratePhysicalDevices", |count, devices| {
+                (self.enumerate_physical_devices)(self.instance, count, devices)
+            })?
+        };
+        Ok(handles.into_iter().map(|handle| PhysicalDevice { handle, instance: PhantomData }).collect())
+    }
+
+    /// Groups of physical devices that can be used together as one logical device. Before 1.1
+    /// every device is a group of its own, as 1.1 drivers without linked devices report too.
+    pub fn enumerate_physical_device_groups(&self) -> Result<Vec<PhysicalDeviceGroup<'_>>> {
+        let enumerate_physical_device_groups = match self.enumerate_physical_device_groups {
+            Some(function) if self.api_version >= vk::API_VERSION_1_1 => function,
+            _ => {
+                return Ok(self.enumerate_physical_devices()?.into_iter()
+                    .map(|physical_device| PhysicalDeviceGroup { physical_devices: vec![physical_device], subset_allocation: false })
+                    .collect());
+            }
+        };
+
+        let groups = unsafe {
+            enumerate_structures("vkEnumeratePhysicalDeviceGroups", |count, groups| {
+                enumerate_physical_device_groups(self.instance, count, groups)
+            })?
+        };
+        Ok(groups.iter().map(|group| PhysicalDeviceGroup {
+            physical_devices: group.physical_devices().iter().map(|&handle| PhysicalDevice { handle, instance: PhantomData }).collect(),
+            subset_allocation: group.subset_allocation != 0,
+        }).collect())
+    }
+
+    pub fn get_physical_device_features(&self, physical_device: PhysicalDevice) -> vk::PhysicalDeviceFeatures {
         unsafe {
-            (self.destroy_instance)(self.instance, ptr::null());
+            let mut features: vk::PhysicalDeviceFeatures = mem::zeroed();
+            (self.get_physical_device_features)(physical_device.handle, &mut features);
+            features
+        }
+    }
+
+    pub fn get_physical_device_properties(&self, physical_device: PhysicalDevice) -> vk::PhysicalDeviceProperties {
+        unsafe {
+            let mut properties: vk::PhysicalDeviceProperties = mem::zeroed();
+            (self.get_physical_device_properties)(physical_device.handle, &mut properties);
+            properties
+        }
+    }
+
+    pub fn get_physical_device_queue_family_properties(&self, physical_device: PhysicalDevice) -> Vec<vk::QueueFamilyProperties> {
+        unsafe {
+            enumerate_infallible(|count, properties| {
+                (self.get_physical_device_queue_family_properties)(physical_device.handle, count, properties)
+            })
+        }
+    }
+
+    pub fn get_physical_device_memory_properties(&self, physical_device: PhysicalDevice) -> vk::PhysicalDeviceMemoryProperties {
+        unsafe {
+            let mut properties: vk::PhysicalDeviceMemoryProperties = mem::zeroed();
+            (self.get_physical_device_memory_properties)(physical_device.handle, &mut properties);
+            properties
+        }
+    }
+
+    pub fn get_physical_device_format_properties(&self, physical_device: PhysicalDevice, format: vk::Format) -> vk::FormatProperties {
+        unsafe {
+            let mut properties: vk::FormatProperties = mem::zeroed();
+            (self.get_physical_device_format_properties)(physical_device.handle, format.into(), &mut properties);
+            properties
+        }
+    }
+
+    /// Limits for images created with the given parameters, or `None` if the combination is
+    /// not supported at all (`ErrorFormatNotSupported`).
+    pub fn get_physical_device_image_format_properties(&self, physical_device: PhysicalDevice, format: vk::Format, image_type: vk::ImageType, tiling: vk::ImageTiling, usage: vk::ImageUsageFlags, flags: vk::ImageCreateFlags) -> Result<Option<vk::ImageFormatProperties>> {
+        unsafe {
+            let mut properties: vk::ImageFormatProperties = mem::zeroed();
+            let result = (self.get_physical_device_image_format_properties)(physical_device.handle, format.into(), image_type.into(), tiling.into(), usage, flags, &mut properties);
+            if result == vk::Result::ErrorFormatNotSupported {
+                return Ok(None);
+            }
+            check("vkGetPhysicalDeviceImageFormatProperties", result)?;
+            Ok(Some(properties))
+        }
+    }
+
+    /// Sparse block shapes for images created with the given parameters, one per aspect. It is
+    /// empty if sparse images of that kind are not supported. `samples` must be a single bit.
+    pub fn get_physical_device_sparse_image_format_properties(&self, physical_device: PhysicalDevice, format: vk::Format, image_type: vk::ImageType, samples: vk::SampleCountFlags, usage: vk::ImageUsageFlags, tiling: vk::ImageTiling) -> Vec<vk::SparseImageFormatProperties> {
+        unsafe {
+            enumerate_infallible(|count, properties| {
+                (self.get_physical_device_sparse_image_format_properties)(physical_device.handle, format.into(), image_type.into(), samples, usage, tiling.into(), count, properties)
+            })
+        }
+    }
+
+    /// Extensions `physical_device` supports, or those provided by `layer_name` if given.
+    pub fn enumerate_device_extension_properties(&self, physical_device: PhysicalDevice, layer_name: Option<&str>) -> Result<Vec<vk::ExtensionProperties>> {
+        let layer_name = layer_name_c_string("vkEnumerateDeviceExtensionProperties", layer_name)?;
+        let layer_name = layer_name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+
+        unsafe {
+            enumerate("vkEnumerateDeviceExtensionProperties", |count, extensions| {
+                (self.enumerate_device_extension_properties)(physical_device.handle, layer_name, count, extensions)
+            })
+        }
+    }
+
+    /// Whether `physical_device` supports the extension `name`. The extension list is only
+    /// enumerated once per device; if that fails, the device is taken to have no extensions
+    /// from then on.
+    pub fn has_device_extension(&self, physical_device: PhysicalDevice, name: &str) -> bool {
+        if let Some(extensions) = self.device_extensions.borrow().get(&physical_device.handle) {
+            return extensions.iter().any(|extension| extension == name);
+        }
+        let extensions: Vec<String> = self.enumerate_device_extension_properties(physical_device, None).unwrap_or_default().iter()
+            .map(|extension| extension.extension_name.to_string_lossy())
+            .collect();
+        let supported = extensions.iter().any(|extension| extension == name);
+        self.device_extensions.borrow_mut().insert(physical_device.handle, extensions);
+        supported
+    }
+
+    /// Device layers, which current loaders report as the enabled instance layers.
+    pub fn enumerate_device_layer_properties(&self, physical_device: PhysicalDevice) -> Result<Vec<vk::LayerProperties>> {
+        unsafe {
+            enumerate("vkEnumerateDeviceLayerProperties", |count, layers| {
+                (self.enumerate_device_layer_properties)(physical_device.handle, count, layers)
+            })
+        }
+    }
+
+    /// The Vulkan version `physical_device` can be used with through this instance.
+    pub fn device_api_version(&self, physical_device: PhysicalDevice) -> u32 {
+        cmp::min(self.api_version, self.get_physical_device_properties(physical_device).api_version)
+    }
+
+    /// The core features together with those of every newer Vulkan version that both the
+    /// instance and the device support. Falls back to `vkGetPhysicalDeviceFeatures` before 1.1.
+    pub fn get_physical_device_features2(&self, physical_device: PhysicalDevice) -> Features2 {
+        let api_version = self.device_api_version(physical_device);
+        let get_physical_device_features2 = match self.get_physical_device_features2 {
+            Some(function) if api_version >= vk::API_VERSION_1_1 => function,
+            _ => return Features2 { features: self.get_physical_device_features(physical_device), ..Features2::default() },
+        };
+
+        let mut features = vk::PhysicalDeviceFeatures2::default();
+        let mut vulkan_11 = optional_structure(api_version >= vk::API_VERSION_1_2);
+        let mut vulkan_12 = optional_structure(api_version >= vk::API_VERSION_1_2);
+        let mut vulkan_13 = optional_structure(api_version >= vk::API_VERSION_1_3);
+        let mut vulkan_14 = optional_structure(api_version >= vk::API_VERSION_1_4);
+        // Before 1.2 the 1.1 features come in structures of their own.
+        let mut separate_11 = if vulkan_11.is_none() { Some(Vulkan11Features::default()) } else { None };
+
+        let mut chain = vec![base_out(&mut features)];
+        chain.extend(vulkan_11.as_mut().map(base_out));
+        chain.extend(separate_11.as_mut().into_iter().flat_map(Vulkan11Features::chain));
+        chain.extend(vulkan_12.as_mut().map(base_out));
+        chain.extend(vulkan_13.as_mut().map(base_out));
+        chain.extend(vulkan_14.as_mut().map(base_out));
+        unsafe {
+            with_chain(&chain, || get_physical_device_features2(physical_device.handle, &mut features));
+        }
+
+        let vulkan_11 = vulkan_11.or_else(|| separate_11.as_ref().map(Vulkan11Features::combine));
+        Features2 { features: features.features, vulkan_11, vulkan_12, vulkan_13, vulkan_14 }
+    }
+
+    /// The core properties together with those of every newer Vulkan version that both the
+    /// instance and the device support. Falls back to `vkGetPhysicalDeviceProperties` before 1.1.
+    pub fn get_physical_device_properties2(&self, physical_device: PhysicalDevice) -> Properties2 {
+        let api_version = self.device_api_version(physical_device);
+        let get_physical_device_properties2 = match self.get_physical_device_properties2 {
+            Some(function) if api_version >= vk::API_VERSION_1_1 => function,
+            _ => return Properties2 { properties: self.get_physical_device_properties(physical_device), ..Properties2::default() },
+        };
+
+        let mut properties = vk::PhysicalDeviceProperties2::default();
+        let mut vulkan_11 = optional_structure(api_version >= vk::API_VERSION_1_2);
+        let mut vulkan_12 = optional_structure(api_version >= vk::API_VERSION_1_2);
+        let mut vulkan_13 = optional_structure(api_version >= vk::API_VERSION_1_3);
+        let mut vulkan_14: Option<vk::PhysicalDeviceVulkan14Properties> = optional_structure(api_version >= vk::API_VERSION_1_4);
+        let mut separate_11 = if vulkan_11.is_none() { Some(Vulkan11Properties::default()) } else { None };
+        let has_extension = |name: &str| self.has_device_extension(physical_device, name);
+        // From 1.2 on the driver properties are part of the Vulkan 1.2 properties.
+        let mut driver = optional_structure(api_version < vk::API_VERSION_1_2 && has_extension("VK_KHR_driver_properties"));
+        let mut pci_bus_info = optional_structure(has_extension("VK_EXT_pci_bus_info"));
+        let mut drm = optional_structure(has_extension("VK_EXT_physical_device_drm"));
+        // Likewise, from 1.2 on the IDs are part of the Vulkan 1.1 properties.
+        let mut id = optional_structure(api_version < vk::API_VERSION_1_2);
+
+        let mut chain = vec![base_out(&mut properties)];
+        chain.extend(vulkan_11.as_mut().map(base_out));
+        chain.extend(separate_11.as_mut().into_iter().flat_map(Vulkan11Properties::chain));
+        chain.extend(vulkan_12.as_mut().map(base_out));
+        chain.extend(vulkan_13.as_mut().map(base_out));
+        chain.extend(vulkan_14.as_mut().map(base_out));
+        chain.extend(driver.as_mut().map(base_out));
+        chain.extend(pci_bus_info.as_mut().map(base_out));
+        chain.extend(drm.as_mut().map(base_out));
+        chain.extend(id.as_mut().map(base_out));
+        unsafe {
+            with_chain(&chain, || get_physical_device_properties2(physical_device.handle, &mut properties));
+        }
+
+        // The copy layouts are an array the driver only fills once there is room for it, so
+        // they take a second call with the counts it reported in the first.
+        let mut copy_src_layouts = Vec::new();
+        let mut copy_dst_layouts = Vec::new();
+        if let Some(ref mut vulkan_14) = vulkan_14 {
+            if vulkan_14.copy_src_layout_count > 0 || vulkan_14.copy_dst_layout_count > 0 {
+                copy_src_layouts = vec![vk::Raw::default(); vulkan_14.copy_src_layout_count as usize];
+                copy_dst_layouts = vec![vk::Raw::default(); vulkan_14.copy_dst_layout_count as usize];
+                vulkan_14.p_copy_src_layouts = copy_src_layouts.as_mut_ptr();
+                vulkan_14.p_copy_dst_layouts = copy_dst_layouts.as_mut_ptr();
+                let chain = [base_out(&mut properties), base_out(vulkan_14)];
+                unsafe {
+                    with_chain(&chain, || get_physical_device_properties2(physical_device.handle, &mut properties));
+                }
+                copy_src_layouts.truncate(vulkan_14.copy_src_layout_count as usize);
+                copy_dst_layouts.truncate(vulkan_14.copy_dst_layout_count as usize);
+                vulkan_14.p_copy_src_layouts = ptr::null_mut();
+                vulkan_14.p_copy_dst_layouts = ptr::null_mut();
+            }
+        }
+
+        let vulkan_11 = vulkan_11.or_else(|| separate_11.as_ref().zip(id.as_ref()).map(|(separate_11, id)| separate_11.combine(id)));
+        Properties2 {
+            properties: properties.properties,
+            driver: driver.or_else(|| vulkan_12.as_ref().map(vk::PhysicalDeviceVulkan12Properties::driver_properties)),
+            pci_bus_info,
+            drm,
+            id: id.or_else(|| vulkan_11.as_ref().map(vk::PhysicalDeviceVulkan11Properties::id_properties)),
+            vulkan_11,
+            vulkan_12,
+            vulkan_13,
+            vulkan_14,
+            copy_src_layouts: copy_src_layouts.into_iter().map(vk::Raw::get).collect(),
+            copy_dst_layouts: copy_dst_layouts.into_iter().map(vk::Raw::get).collect(),
+        }
+    }
+
+    /// Only the PCI bus and DRM node properties of `get_physical_device_properties2`, which
+    /// are all it takes to find the device in sysfs.
+    pub fn get_physical_device_bus_properties(&self, physical_device: PhysicalDevice) -> (Option<vk::PhysicalDevicePciBusInfoPropertiesExt>, Option<vk::PhysicalDeviceDrmPropertiesExt>) {
+        let get_physical_device_properties2 = match self.get_physical_device_properties2 {
+            Some(function) if self.device_api_version(physical_device) >= vk::API_VERSION_1_1 => function,
+            _ => return (None, None),
+        };
+
+        let mut properties = vk::PhysicalDeviceProperties2::default();
+        let mut pci_bus_info = optional_structure(self.has_device_extension(physical_device, "VK_EXT_pci_bus_info"));
+        let mut drm = optional_structure(self.has_device_extension(physical_device, "VK_EXT_physical_device_drm"));
+        if pci_bus_info.is_none() && drm.is_none() {
+            return (None, None);
+        }
+
+        let mut chain = vec![base_out(&mut properties)];
+        chain.extend(pci_bus_info.as_mut().map(base_out));
+        chain.extend(drm.as_mut().map(base_out));
+        unsafe {
+            with_chain(&chain, || get_physical_device_properties2(physical_device.handle, &mut properties));
         }
+        (pci_bus_info, drm)
     }
 
-    pub fn enumerate_physical_devices(&self) -> Result<Vec<vk::PhysicalDevice>> {
+    /// The queue families with what the device's extensions add to them: global priorities
+    /// (VK_KHR_global_priority or Vulkan 1.4), checkpoint stages
+    /// (VK_NV_device_diagnostic_checkpoints) and video codec operations (VK_KHR_video_queue).
+    /// Falls back to `vkGetPhysicalDeviceQueueFamilyProperties` before 1.1.
+    pub fn get_physical_device_queue_family_properties2(&self, physical_device: PhysicalDevice) -> Vec<QueueFamilyProperties2> {
+        let api_version = self.device_api_version(physical_device);
+        let get_physical_device_queue_family_properties2 = match self.get_physical_device_queue_family_properties2 {
+            Some(function) if api_version >= vk::API_VERSION_1_1 => function,
+            _ => {
+                return self.get_physical_device_queue_family_properties(physical_device).into_iter()
+                    .map(|properties| QueueFamilyProperties2 { properties, ..QueueFamilyProperties2::default() })
+                    .collect();
+            }
+        };
+        let has_extension = |name: &str| self.has_device_extension(physical_device, name);
+        let has_global_priority = api_version >= vk::API_VERSION_1_4 || has_extension("VK_KHR_global_priority") || has_extension("VK_EXT_global_priority_query");
+        let has_checkpoints = has_extension("VK_NV_device_diagnostic_checkpoints");
+        let has_video = has_extension("VK_KHR_video_queue");
+
+        let mut count: u32 = 0;
         unsafe {
-            let mut count: u32 = 0;
+            get_physical_device_queue_family_properties2(physical_device.handle, &mut count, ptr::null_mut());
+        }
 
-            let err = (self.enumerate_physical_devices)(self.instance, &mut count, ptr::null_mut());
-            if err != vk::Result::Success {
-                return Err(err);
+        // Every element carries a chain of its own.
+        let mut families: Vec<vk::QueueFamilyProperties2> = (0..count).map(|_| vk::QueueFamilyProperties2::default()).collect();
+        let mut global_priorities: Vec<Option<vk::QueueFamilyGlobalPriorityPropertiesKhr>> = (0..count).map(|_| optional_structure(has_global_priority)).collect();
+        let mut checkpoints: Vec<Option<vk::QueueFamilyCheckpointPropertiesNv>> = (0..count).map(|_| optional_structure(has_checkpoints)).collect();
+        let mut videos: Vec<Option<vk::QueueFamilyVideoPropertiesKhr>> = (0..count).map(|_| optional_structure(has_video)).collect();
+        let chains: Vec<Vec<*mut vk::BaseOutStructure>> = (0..count as usize).map(|index| {
+            let mut chain = vec![base_out(&mut families[index])];
+            chain.extend(global_priorities[index].as_mut().map(base_out));
+            chain.extend(checkpoints[index].as_mut().map(base_out));
+            chain.extend(videos[index].as_mut().map(base_out));
+            chain
+        }).collect();
+        unsafe {
+            for chain in &chains {
+                link(chain);
+            }
+            get_physical_device_queue_family_properties2(physical_device.handle, &mut count, families.as_mut_ptr());
+            for chain in &chains {
+                unlink(chain);
             }
+        }
+
+        families.iter().zip(global_priorities).zip(checkpoints).zip(videos)
+            .take(count as usize)
+            .map(|(((family, global_priority), checkpoint), video)| QueueFamilyProperties2 {
+                properties: family.queue_family_properties.clone(),
+                global_priorities: global_priority.map(|global_priority| global_priority.priorities().iter().map(|priority| priority.get()).collect()),
+                checkpoint_execution_stage_mask: checkpoint.map(|checkpoint| checkpoint.checkpoint_execution_stage_mask),
+                video_codec_operations: video.map(|video| video.video_codec_operations),
+            })
+            .collect()
+    }
+
+    /// How much of each memory heap is in use and how much the process may use, or `None` if
+    /// the device does not support VK_EXT_memory_budget (or the instance is older than 1.1).
+    pub fn get_physical_device_memory_budget(&self, physical_device: PhysicalDevice) -> Option<Vec<HeapBudget>> {
+        let get_physical_device_memory_properties2 = match self.get_physical_device_memory_properties2 {
+            Some(function) if self.device_api_version(physical_device) >= vk::API_VERSION_1_1 => function,
+            _ => return None,
+        };
+        if !self.has_device_extension(physical_device, "VK_EXT_memory_budget") {
+            return None;
+        }
 
-            let mut devices = Vec::with_capacity(count as usize);
-            devices.set_len(count as usize);
+        let mut properties = vk::PhysicalDeviceMemoryProperties2::default();
+        let mut budget = vk::PhysicalDeviceMemoryBudgetPropertiesExt::default();
+        unsafe {
+            with_chain(&[base_out(&mut properties), base_out(&mut budget)], || get_physical_device_memory_properties2(physical_device.handle, &mut properties));
+        }
+
+        Some(properties.memory_properties.memory_heaps().iter().enumerate().map(|(index, heap)| HeapBudget {
+            size: heap.size,
+            usage: budget.heap_usage[index],
+            budget: budget.heap_budget[index],
+        }).collect())
+    }
 
-            let err = (self.enumerate_physical_devices)(self.instance, &mut count, devices.as_mut_ptr());
-            if err != vk::Result::Success {
-                return Err(err);
+    /// Creates a surface that is not tied to any window system. Needs VK_EXT_headless_surface
+    /// and VK_KHR_surface to be enabled on the instance.
+    pub fn create_headless_surface(&self) -> Result<Surface<'_>> {
+        for &extension in &["VK_KHR_surface", "VK_EXT_headless_surface"] {
+            if !self.is_extension_enabled(extension) {
+                return Err(Error::ExtensionNotPresent(extension.to_string()));
             }
+        }
+        let create_headless_surface = required(self.create_headless_surface, "vkCreateHeadlessSurfaceEXT")?;
+        required(self.destroy_surface, "vkDestroySurfaceKHR")?;
 
-            Ok(devices)
+        let create_info = vk::HeadlessSurfaceCreateInfoExt {
+            s_type: vk::StructureType::HeadlessSurfaceCreateInfoExt.into(),
+            p_next: ptr::null(),
+            flags: 0,
+        };
+        let mut handle = vk::NULL_HANDLE;
+        unsafe {
+            check("vkCreateHeadlessSurfaceEXT", create_headless_surface(self.instance, &create_info, ptr::null(), &mut handle))?;
         }
+        Ok(Surface { handle, instance: self })
     }
 
-    pub fn get_physical_device_features(&self, physical_device: vk::PhysicalDevice) -> vk::PhysicalDeviceFeatures {
+    /// Whether queue family `queue_family_index` of `physical_device` can present to `surface`.
+    pub fn get_physical_device_surface_support(&self, physical_device: PhysicalDevice, queue_family_index: u32, surface: &Surface) -> Result<bool> {
+        let get_physical_device_surface_support = required(self.get_physical_device_surface_support, "vkGetPhysicalDeviceSurfaceSupportKHR")?;
+        let mut supported: vk::Bool32 = 0;
         unsafe {
-            let mut features: vk::PhysicalDeviceFeatures;
-            features = mem::uninitialized();
-            (self.get_physical_device_features)(physical_device, &mut features);
-            features
+            check("vkGetPhysicalDeviceSurfaceSupportKHR", get_physical_device_surface_support(physical_device.handle, queue_family_index, surface.handle, &mut supported))?;
         }
+        Ok(supported != 0)
     }
 
-    pub fn get_physical_device_properties(&self, physical_device: vk::PhysicalDevice) -> vk::PhysicalDeviceProperties {
+    pub fn get_physical_device_surface_capabilities(&self, physical_device: PhysicalDevice, surface: &Surface) -> Result<vk::SurfaceCapabilitiesKhr> {
+        let get_physical_device_surface_capabilities = required(self.get_physical_device_surface_capabilities, "vkGetPhysicalDeviceSurfaceCapabilitiesKHR")?;
+        let mut capabilities = vk::SurfaceCapabilitiesKhr::default();
         unsafe {
-            let mut properties: vk::PhysicalDeviceProperties;
-            properties = mem::uninitialized();
-            (self.get_physical_device_properties)(physical_device, &mut properties);
-            properties
+            check("vkGetPhysicalDeviceSurfaceCapabilitiesKHR", get_physical_device_surface_capabilities(physical_device.handle, surface.handle, &mut capabilities))?;
         }
+        Ok(capabilities)
     }
 
-    pub fn get_physical_device_queue_family_properties(&self, physical_device: vk::PhysicalDevice) -> Vec<vk::QueueFamilyProperties> {
+    pub fn get_physical_device_surface_formats(&self, physical_device: PhysicalDevice, surface: &Surface) -> Result<Vec<vk::SurfaceFormatKhr>> {
+        let get_physical_device_surface_formats = required(self.get_physical_device_surface_formats, "vkGetPhysicalDeviceSurfaceFormatsKHR")?;
         unsafe {
-            let mut count: u32 = 0;
-            (self.get_physical_device_queue_family_properties)(physical_device, &mut count, ptr::null_mut());
-            let mut properties = Vec::with_capacity(count as usize);
-            properties.set_len(count as usize);
-            (self.get_physical_device_queue_family_properties)(physical_device, &mut count, properties.as_mut_ptr());
-            properties
+            enumerate("vkGetPhysicalDeviceSurfaceFormatsKHR", |count, formats| {
+                get_physical_device_surface_formats(physical_device.handle, surface.handle, count, formats)
+            })
+        }
+    }
+
+    pub fn get_physical_device_surface_present_modes(&self, physical_device: PhysicalDevice, surface: &Surface) -> Result<Vec<vk::PresentModeKhr>> {
+        let get_physical_device_surface_present_modes = required(self.get_physical_device_surface_present_modes, "vkGetPhysicalDeviceSurfacePresentModesKHR")?;
+        let present_modes = unsafe {
+            enumerate("vkGetPhysicalDeviceSurfacePresentModesKHR", |count, present_modes| {
+                get_physical_device_surface_present_modes(physical_device.handle, surface.handle, count, present_modes)
+            })?
+        };
+        Ok(present_modes.into_iter().map(vk::Raw::get).collect())
+    }
+
+    /// The displays attached to `physical_device`, through VK_KHR_display.
+    pub fn get_physical_device_display_properties(&self, physical_device: PhysicalDevice) -> Result<Vec<DisplayProperties>> {
+        let get_physical_device_display_properties = required(self.get_physical_device_display_properties, "vkGetPhysicalDeviceDisplayPropertiesKHR")?;
+        let properties = unsafe {
+            enumerate("vkGetPhysicalDeviceDisplayPropertiesKHR", |count, properties| {
+                get_physical_device_display_properties(physical_device.handle, count, properties)
+            })?
+        };
+        Ok(properties.iter().map(|properties| DisplayProperties {
+            display: properties.display,
+            display_name: unsafe { properties.display_name.as_ref().map(|name| CStr::from_ptr(name).to_string_lossy().into_owned()) },
+            physical_dimensions: properties.physical_dimensions,
+            physical_resolution: properties.physical_resolution,
+            supported_transforms: properties.supported_transforms,
+            plane_reorder_possible: properties.plane_reorder_possible != 0,
+            persistent_content: properties.persistent_content != 0,
+        }).collect())
+    }
+
+    pub fn get_physical_device_display_plane_properties(&self, physical_device: PhysicalDevice) -> Result<Vec<vk::DisplayPlanePropertiesKhr>> {
+        let get_physical_device_display_plane_properties = required(self.get_physical_device_display_plane_properties, "vkGetPhysicalDeviceDisplayPlanePropertiesKHR")?;
+        unsafe {
+            enumerate("vkGetPhysicalDeviceDisplayPlanePropertiesKHR", |count, properties| {
+                get_physical_device_display_plane_properties(physical_device.handle, count, properties)
+            })
+        }
+    }
+
+    /// The displays plane `plane_index` can be shown on.
+    pub fn get_display_plane_supported_displays(&self, physical_device: PhysicalDevice, plane_index: u32) -> Result<Vec<vk::DisplayKhr>> {
+        let get_display_plane_supported_displays = required(self.get_display_plane_supported_displays, "vkGetDisplayPlaneSupportedDisplaysKHR")?;
+        unsafe {
+            enumerate("vkGetDisplayPlaneSupportedDisplaysKHR", |count, displays| {
+                get_display_plane_supported_displays(physical_device.handle, plane_index, count, displays)
+            })
+        }
+    }
+
+    pub fn get_display_mode_properties(&self, physical_device: PhysicalDevice, display: vk::DisplayKhr) -> Result<Vec<vk::DisplayModePropertiesKhr>> {
+        let get_display_mode_properties = required(self.get_display_mode_properties, "vkGetDisplayModePropertiesKHR")?;
+        unsafe {
+            enumerate("vkGetDisplayModePropertiesKHR", |count, properties| {
+                get_display_mode_properties(physical_device.handle, display, count, properties)
+            })
+        }
+    }
+
+    /// What plane `plane_index` can do when showing `mode`.
+    pub fn get_display_plane_capabilities(&self, physical_device: PhysicalDevice, mode: vk::DisplayModeKhr, plane_index: u32) -> Result<vk::DisplayPlaneCapabilitiesKhr> {
+        let get_display_plane_capabilities = required(self.get_display_plane_capabilities, "vkGetDisplayPlaneCapabilitiesKHR")?;
+        let mut capabilities = vk::DisplayPlaneCapabilitiesKhr::default();
+        unsafe {
+            check("vkGetDisplayPlaneCapabilitiesKHR", get_display_plane_capabilities(physical_device.handle, mode, plane_index, &mut capabilities))?;
+        }
+        Ok(capabilities)
+    }
+
+    /// What buffers created with `flags` and `usage` can do with memory of `handle_type`.
+    /// Like the other external handle queries, this needs Vulkan 1.1 on both the instance
+    /// and the device.
+    pub fn get_physical_device_external_buffer_properties(&self, physical_device: PhysicalDevice, flags: vk::BufferCreateFlags, usage: vk::BufferUsageFlags, handle_type: vk::ExternalMemoryHandleTypeFlags) -> Result<vk::ExternalMemoryProperties> {
+        let get_physical_device_external_buffer_properties = required(self.get_physical_device_external_buffer_properties, "vkGetPhysicalDeviceExternalBufferProperties")?;
+        let info = vk::PhysicalDeviceExternalBufferInfo { flags, usage, handle_type, ..Default::default() };
+        let mut properties = vk::ExternalBufferProperties::default();
+        unsafe {
+            get_physical_device_external_buffer_properties(physical_device.handle, &info, &mut properties);
+        }
+        Ok(properties.external_memory_properties)
+    }
+
+    /// What images created with the given parameters can do with memory of `handle_type`, or
+    /// `None` if such images are not supported at all (`ErrorFormatNotSupported`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_physical_device_external_image_format_properties(&self, physical_device: PhysicalDevice, format: vk::Format, image_type: vk::ImageType, tiling: vk::ImageTiling, usage: vk::ImageUsageFlags, flags: vk::ImageCreateFlags, handle_type: vk::ExternalMemoryHandleTypeFlags) -> Result<Option<vk::ExternalMemoryProperties>> {
+        let get_physical_device_image_format_properties2 = required(self.get_physical_device_image_format_properties2, "vkGetPhysicalDeviceImageFormatProperties2")?;
+        let external_info = vk::PhysicalDeviceExternalImageFormatInfo { handle_type, ..Default::default() };
+        let info = vk::PhysicalDeviceImageFormatInfo2 {
+            p_next: &external_info as *const vk::PhysicalDeviceExternalImageFormatInfo as *const c_void,
+            format: format.into(),
+            image_type: image_type.into(),
+            tiling: tiling.into(),
+            usage,
+            flags,
+            ..Default::default()
+        };
+
+        let mut properties = vk::ImageFormatProperties2::default();
+        let mut external_properties = vk::ExternalImageFormatProperties::default();
+        let mut result = vk::Result::Success.into();
+        unsafe {
+            with_chain(&[base_out(&mut properties), base_out(&mut external_properties)], || {
+                result = get_physical_device_image_format_properties2(physical_device.handle, &info, &mut properties);
+            });
+        }
+        if result == vk::Result::ErrorFormatNotSupported {
+            return Ok(None);
+        }
+        check("vkGetPhysicalDeviceImageFormatProperties2", result)?;
+        Ok(Some(external_properties.external_memory_properties))
+    }
+
+    pub fn get_physical_device_external_semaphore_properties(&self, physical_device: PhysicalDevice, handle_type: vk::ExternalSemaphoreHandleTypeFlags) -> Result<vk::ExternalSemaphoreProperties> {
+        let get_physical_device_external_semaphore_properties = required(self.get_physical_device_external_semaphore_properties, "vkGetPhysicalDeviceExternalSemaphoreProperties")?;
+        let info = vk::PhysicalDeviceExternalSemaphoreInfo { handle_type, ..Default::default() };
+        let mut properties = vk::ExternalSemaphoreProperties::default();
+        unsafe {
+            get_physical_device_external_semaphore_properties(physical_device.handle, &info, &mut properties);
+        }
+        Ok(properties)
+    }
+
+    pub fn get_physical_device_external_fence_properties(&self, physical_device: PhysicalDevice, handle_type: vk::ExternalFenceHandleTypeFlags) -> Result<vk::ExternalFenceProperties> {
+        let get_physical_device_external_fence_properties = required(self.get_physical_device_external_fence_properties, "vkGetPhysicalDeviceExternalFenceProperties")?;
+        let info = vk::PhysicalDeviceExternalFenceInfo { handle_type, ..Default::default() };
+        let mut properties = vk::ExternalFenceProperties::default();
+        unsafe {
+            get_physical_device_external_fence_properties(physical_device.handle, &info, &mut properties);
+        }
+        Ok(properties)
+    }
+
+    /// Creates a logical device with one queue of the first queue family and `extensions`
+    /// enabled. The device is destroyed when dropped.
+    pub fn create_device(&self, physical_device: PhysicalDevice, extensions: &[&str]) -> Result<Device<'_>> {
+        let extensions: Vec<CString> = extensions.iter().map(|name| c_string(name)).collect();
+        let extension_names: Vec<*const c_char> = extensions.iter().map(|name| name.as_ptr()).collect();
+        let priority = 1.0f32;
+        let queue_create_info = vk::DeviceQueueCreateInfo {
+            s_type: vk::StructureType::DeviceQueueCreateInfo.into(),
+            p_next: ptr::null(),
+            flags: 0,
+            queue_family_index: 0,
+            queue_count: 1,
+            p_queue_priorities: &priority,
+        };
+        let create_info = vk::DeviceCreateInfo {
+            s_type: vk::StructureType::DeviceCreateInfo.into(),
+            p_next: ptr::null(),
+            flags: 0,
+            queue_create_info_count: 1,
+            p_queue_create_infos: &queue_create_info,
+            enabled_layer_count: 0,
+            pp_enabled_layer_names: ptr::null(),
+            enabled_extension_count: extension_names.len() as u32,
+            pp_enabled_extension_names: extension_names.as_ptr(),
+            p_enabled_features: ptr::null(),
+        };
+
+        let mut handle: vk::Device = ptr::null();
+        unsafe {
+            check("vkCreateDevice", (self.create_device)(physical_device.handle, &create_info, ptr::null(), &mut handle))?;
+        }
+        Ok(Device { handle, instance: self })
+    }
+
+    /// The tools active on the device, through Vulkan 1.3 if both the instance and the device
+    /// support it and through VK_EXT_tooling_info otherwise.
+    pub fn get_physical_device_tool_properties(&self, physical_device: PhysicalDevice) -> Result<Vec<vk::PhysicalDeviceToolProperties>> {
+        let (function, name) = if self.device_api_version(physical_device) >= vk::API_VERSION_1_3 {
+            (self.get_physical_device_tool_properties, "vkGetPhysicalDeviceToolProperties")
+        } else {
+            (self.get_physical_device_tool_properties_ext, "vkGetPhysicalDeviceToolPropertiesEXT")
+        };
+        let get_physical_device_tool_properties = required(function, name)?;
+        unsafe {
+            enumerate_structures(name, |count, tools| get_physical_device_tool_properties(physical_device.handle, count, tools))
         }
     }
+
+    /// Limits for video sessions using `profile`, or `None` if the device does not support
+    /// the profile.
+    pub fn get_physical_device_video_capabilities(&self, physical_device: PhysicalDevice, profile: &VideoProfile) -> Result<Option<VideoCapabilities>> {
+        use vk::VideoCodecOperationFlagsKhr as Operation;
+
+        let get_physical_device_video_capabilities = required(self.get_physical_device_video_capabilities, "vkGetPhysicalDeviceVideoCapabilitiesKHR")?;
+        let is = |operation: Operation| profile.operation == operation;
+
+        let mut capabilities = vk::VideoCapabilitiesKhr::default();
+        let mut decode: Option<vk::VideoDecodeCapabilitiesKhr> = optional_structure(profile.is_decode());
+        let mut encode: Option<vk::VideoEncodeCapabilitiesKhr> = optional_structure(profile.is_encode());
+        let mut decode_h264: Option<vk::VideoDecodeH264CapabilitiesKhr> = optional_structure(is(Operation::DecodeH264Bit));
+        let mut decode_h265: Option<vk::VideoDecodeH265CapabilitiesKhr> = optional_structure(is(Operation::DecodeH265Bit));
+        let mut decode_av1: Option<vk::VideoDecodeAv1CapabilitiesKhr> = optional_structure(is(Operation::DecodeAv1Bit));
+        let mut decode_vp9: Option<vk::VideoDecodeVp9CapabilitiesKhr> = optional_structure(is(Operation::DecodeVp9Bit));
+        let mut encode_h264: Option<vk::VideoEncodeH264CapabilitiesKhr> = optional_structure(is(Operation::EncodeH264Bit));
+        let mut encode_h265: Option<vk::VideoEncodeH265CapabilitiesKhr> = optional_structure(is(Operation::EncodeH265Bit));
+        let mut encode_av1: Option<vk::VideoEncodeAv1CapabilitiesKhr> = optional_structure(is(Operation::EncodeAv1Bit));
+
+        let mut chain = vec![base_out(&mut capabilities)];
+        chain.extend(decode.as_mut().map(base_out));
+        chain.extend(encode.as_mut().map(base_out));
+        chain.extend(decode_h264.as_mut().map(base_out));
+        chain.extend(decode_h265.as_mut().map(base_out));
+        chain.extend(decode_av1.as_mut().map(base_out));
+        chain.extend(decode_vp9.as_mut().map(base_out));
+        chain.extend(encode_h264.as_mut().map(base_out));
+        chain.extend(encode_h265.as_mut().map(base_out));
+        chain.extend(encode_av1.as_mut().map(base_out));
+
+        let mut result = vk::Result::Success.into();
+        unsafe {
+            with_video_profile(profile, |profile| {
+                with_chain(&chain, || result = get_physical_device_video_capabilities(physical_device.handle, profile, &mut capabilities));
+            });
+        }
+        if is_unsupported_video_profile(result.get()) {
+            return Ok(None);
+        }
+        check("vkGetPhysicalDeviceVideoCapabilitiesKHR", result)?;
+
+        let max_level = decode_h264.map(|codec| codec.max_level_idc)
+            .or(decode_h265.map(|codec| codec.max_level_idc))
+            .or(decode_av1.map(|codec| codec.max_level))
+            .or(decode_vp9.map(|codec| codec.max_level))
+            .or(encode_h264.map(|codec| codec.max_level_idc))
+            .or(encode_h265.map(|codec| codec.max_level_idc))
+            .or(encode_av1.map(|codec| codec.max_level))
+            .unwrap_or_default();
+
+        Ok(Some(VideoCapabilities { capabilities, decode, encode, max_level }))
+    }
+
+    /// Formats that images with `image_usage` can have in video sessions using `profile`.
+    /// It is empty if the device does not support the profile or the usage.
+    pub fn get_physical_device_video_format_properties(&self, physical_device: PhysicalDevice, profile: &VideoProfile, image_usage: vk::ImageUsageFlags) -> Result<Vec<vk::VideoFormatPropertiesKhr>> {
+        let get_physical_device_video_format_properties = required(self.get_physical_device_video_format_properties, "vkGetPhysicalDeviceVideoFormatPropertiesKHR")?;
+
+        let properties = with_video_profile(profile, |profile| {
+            let profile_list = vk::VideoProfileListInfoKhr {
+                profile_count: 1,
+                p_profiles: profile,
+                ..vk::VideoProfileListInfoKhr::default()
+            };
+            let format_info = vk::PhysicalDeviceVideoFormatInfoKhr {
+                p_next: &profile_list as *const vk::VideoProfileListInfoKhr as *const c_void,
+                image_usage,
+                ..vk::PhysicalDeviceVideoFormatInfoKhr::default()
+            };
+            unsafe {
+                enumerate_structures("vkGetPhysicalDeviceVideoFormatPropertiesKHR", |count, properties| {
+                    get_physical_device_video_format_properties(physical_device.handle, &format_info, count, properties)
+                })
+            }
+        });
+
+        match properties {
+            Err(Error::Call { result, .. }) if is_unsupported_video_profile(result) || result == vk::Result::ErrorImageUsageNotSupportedKhr => Ok(Vec::new()),
+            properties => properties,
+        }
+    }
+}
+
+/// Calls `call` with `profile` as a `vk::VideoProfileInfoKhr` that carries the codec's
+/// profile structure.
+fn with_video_profile<R, F: FnOnce(&vk::VideoProfileInfoKhr) -> R>(profile: &VideoProfile, call: F) -> R {
+    use vk::VideoCodecOperationFlagsKhr as Operation;
+
+    let decode_h264 = vk::VideoDecodeH264ProfileInfoKhr { std_profile_idc: profile.std_profile, ..Default::default() };
+    let decode_h265 = vk::VideoDecodeH265ProfileInfoKhr { std_profile_idc: profile.std_profile, ..Default::default() };
+    let decode_av1 = vk::VideoDecodeAv1ProfileInfoKhr { std_profile: profile.std_profile, ..Default::default() };
+    let decode_vp9 = vk::VideoDecodeVp9ProfileInfoKhr { std_profile: profile.std_profile, ..Default::default() };
+    let encode_h264 = vk::VideoEncodeH264ProfileInfoKhr { std_profile_idc: profile.std_profile, ..Default::default() };
+    let encode_h265 = vk::VideoEncodeH265ProfileInfoKhr { std_profile_idc: profile.std_profile, ..Default::default() };
+    let encode_av1 = vk::VideoEncodeAv1ProfileInfoKhr { std_profile: profile.std_profile, ..Default::default() };
+
+    let codec_profile: *const c_void = match profile.operation {
+        Operation::DecodeH264Bit => &decode_h264 as *const vk::VideoDecodeH264ProfileInfoKhr as *const c_void,
+        Operation::DecodeH265Bit => &decode_h265 as *const vk::VideoDecodeH265ProfileInfoKhr as *const c_void,
+        Operation::DecodeAv1Bit => &decode_av1 as *const vk::VideoDecodeAv1ProfileInfoKhr as *const c_void,
+        Operation::DecodeVp9Bit => &decode_vp9 as *const vk::VideoDecodeVp9ProfileInfoKhr as *const c_void,
+        Operation::EncodeH264Bit => &encode_h264 as *const vk::VideoEncodeH264ProfileInfoKhr as *const c_void,
+        Operation::EncodeH265Bit => &encode_h265 as *const vk::VideoEncodeH265ProfileInfoKhr as *const c_void,
+        Operation::EncodeAv1Bit => &encode_av1 as *const vk::VideoEncodeAv1ProfileInfoKhr as *const c_void,
+        _ => ptr::null(),
+    };
+
+    call(&vk::VideoProfileInfoKhr {
+        p_next: codec_profile,
+        video_codec_operation: profile.operation,
+        chroma_subsampling: profile.chroma_subsampling,
+        luma_bit_depth: profile.luma_bit_depth,
+        chroma_bit_depth: profile.chroma_bit_depth,
+        ..vk::VideoProfileInfoKhr::default()
+    })
+}
+
+/// Whether a video query failed because the device does not support the profile, rather
+/// than because something went wrong.
+fn is_unsupported_video_profile(result: vk::Result) -> bool {
+    matches!(result, vk::Result::ErrorFormatNotSupported
+        | vk::Result::ErrorVideoPictureLayoutNotSupportedKhr
+        | vk::Result::ErrorVideoProfileOperationNotSupportedKhr
+        | vk::Result::ErrorVideoProfileFormatNotSupportedKhr
+        | vk::Result::ErrorVideoProfileCodecNotSupportedKhr
+        | vk::Result::ErrorVideoStdVersionNotSupportedKhr)
+}
+
+/// An optional function that a call cannot do without.
+fn required<T>(function: Option<T>, name: &'static str) -> Result<T> {
+    function.ok_or(Error::MissingSymbol(name))
+}
+
+/// One memory heap's entry in `Instance::get_physical_device_memory_budget`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeapBudget {
+    pub size: vk::DeviceSize,
+    pub usage: vk::DeviceSize,
+    pub budget: vk::DeviceSize,
+}
+
+/// One queue family's entry in `Instance::get_physical_device_queue_family_properties2`. The
+/// additions are `None` if the device does not support the extension they come from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueueFamilyProperties2 {
+    #[serde(flatten)]
+    pub properties: vk::QueueFamilyProperties,
+    pub global_priorities: Option<Vec<vk::QueueGlobalPriorityKhr>>,
+    pub checkpoint_execution_stage_mask: Option<vk::PipelineStageFlags>,
+    pub video_codec_operations: Option<vk::VideoCodecOperationFlagsKhr>,
+}
+
+/// `vk::DisplayPropertiesKhr` with the name copied out of the driver's memory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayProperties {
+    pub display: vk::DisplayKhr,
+    pub display_name: Option<String>,
+    /// In millimeters.
+    pub physical_dimensions: vk::Extend2D,
+    pub physical_resolution: vk::Extend2D,
+    pub supported_transforms: vk::SurfaceTransformFlagsKhr,
+    pub plane_reorder_possible: bool,
+    pub persistent_content: bool,
+}
+
+/// A video profile as `Instance::get_physical_device_video_capabilities` takes it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoProfile {
+    /// Exactly one codec operation.
+    pub operation: vk::VideoCodecOperationFlagsKhr,
+    /// The codec's `StdVideo*ProfileIdc` or `StdVideo*Profile`.
+    pub std_profile: i32,
+    pub chroma_subsampling: vk::VideoChromaSubsamplingFlagsKhr,
+    pub luma_bit_depth: vk::VideoComponentBitDepthFlagsKhr,
+    /// Empty for monochrome profiles.
+    pub chroma_bit_depth: vk::VideoComponentBitDepthFlagsKhr,
+}
+
+impl VideoProfile {
+    pub fn is_decode(&self) -> bool {
+        self.operation.intersects(vk::VideoCodecOperationFlagsKhr::DecodeH264Bit | vk::VideoCodecOperationFlagsKhr::DecodeH265Bit | vk::VideoCodecOperationFlagsKhr::DecodeAv1Bit | vk::VideoCodecOperationFlagsKhr::DecodeVp9Bit)
+    }
+
+    pub fn is_encode(&self) -> bool {
+        self.operation.intersects(vk::VideoCodecOperationFlagsKhr::EncodeH264Bit | vk::VideoCodecOperationFlagsKhr::EncodeH265Bit | vk::VideoCodecOperationFlagsKhr::EncodeAv1Bit)
+    }
+}
+
+/// Result of `Instance::get_physical_device_video_capabilities`. Either `decode` or `encode`
+/// is set, depending on the profile's operation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoCapabilities {
+    pub capabilities: vk::VideoCapabilitiesKhr,
+    pub decode: Option<vk::VideoDecodeCapabilitiesKhr>,
+    pub encode: Option<vk::VideoEncodeCapabilitiesKhr>,
+    /// The highest level of the codec's standard, e.g. a `StdVideoH264LevelIdc`.
+    pub max_level: i32,
+}
+
+fn optional_structure<T: vk::Structure>(present: bool) -> Option<T> {
+    if present { Some(T::default()) } else { None }
+}
+
+/// The structures a Vulkan 1.1 device reports its 1.1 features in, which 1.2 combines into
+/// `PhysicalDeviceVulkan11Features`.
+#[derive(Default)]
+struct Vulkan11Features {
+    storage_16bit: vk::PhysicalDevice16BitStorageFeatures,
+    multiview: vk::PhysicalDeviceMultiviewFeatures,
+    variable_pointers: vk::PhysicalDeviceVariablePointersFeatures,
+    protected_memory: vk::PhysicalDeviceProtectedMemoryFeatures,
+    sampler_ycbcr_conversion: vk::PhysicalDeviceSamplerYcbcrConversionFeatures,
+    shader_draw_parameters: vk::PhysicalDeviceShaderDrawParametersFeatures,
+}
+
+impl Vulkan11Features {
+    fn chain(&mut self) -> Vec<*mut vk::BaseOutStructure> {
+        vec![
+            base_out(&mut self.storage_16bit),
+            base_out(&mut self.multiview),
+            base_out(&mut self.variable_pointers),
+            base_out(&mut self.protected_memory),
+            base_out(&mut self.sampler_ycbcr_conversion),
+            base_out(&mut self.shader_draw_parameters),
+        ]
+    }
+
+    fn combine(&self) -> vk::PhysicalDeviceVulkan11Features {
+        vk::PhysicalDeviceVulkan11Features {
+            storage_buffer16_bit_access: self.storage_16bit.storage_buffer16_bit_access,
+            uniform_and_storage_buffer16_bit_access: self.storage_16bit.uniform_and_storage_buffer16_bit_access,
+            storage_push_constant16: self.storage_16bit.storage_push_constant16,
+            storage_input_output16: self.storage_16bit.storage_input_output16,
+            multiview: self.multiview.multiview,
+            multiview_geometry_shader: self.multiview.multiview_geometry_shader,
+            multiview_tessellation_shader: self.multiview.multiview_tessellation_shader,
+            variable_pointers_storage_buffer: self.variable_pointers.variable_pointers_storage_buffer,
+            variable_pointers: self.variable_pointers.variable_pointers,
+            protected_memory: self.protected_memory.protected_memory,
+            sampler_ycbcr_conversion: self.sampler_ycbcr_conversion.sampler_ycbcr_conversion,
+            shader_draw_parameters: self.shader_draw_parameters.shader_draw_parameters,
+            ..vk::PhysicalDeviceVulkan11Features::default()
+        }
+    }
+}
+
+/// The structures a Vulkan 1.1 device reports its 1.1 properties in, apart from the IDs,
+/// which 1.2 combines into `PhysicalDeviceVulkan11Properties`.
+#[derive(Default)]
+struct Vulkan11Properties {
+    subgroup: vk::PhysicalDeviceSubgroupProperties,
+    point_clipping: vk::PhysicalDevicePointClippingProperties,
+    multiview: vk::PhysicalDeviceMultiviewProperties,
+    protected_memory: vk::PhysicalDeviceProtectedMemoryProperties,
+    maintenance_3: vk::PhysicalDeviceMaintenance3Properties,
+}
+
+impl Vulkan11Properties {
+    fn chain(&mut self) -> Vec<*mut vk::BaseOutStructure> {
+        vec![
+            base_out(&mut self.subgroup),
+            base_out(&mut self.point_clipping),
+            base_out(&mut self.multiview),
+            base_out(&mut self.protected_memory),
+            base_out(&mut self.maintenance_3),
+        ]
+    }
+
+    fn combine(&self, id: &vk::PhysicalDeviceIdProperties) -> vk::PhysicalDeviceVulkan11Properties {
+        vk::PhysicalDeviceVulkan11Properties {
+            device_uuid: id.device_uuid,
+            driver_uuid: id.driver_uuid,
+            device_luid: id.device_luid,
+            device_node_mask: id.device_node_mask,
+            device_luid_valid: id.device_luid_valid,
+            subgroup_size: self.subgroup.subgroup_size,
+            subgroup_supported_stages: self.subgroup.supported_stages,
+            subgroup_supported_operations: self.subgroup.supported_operations,
+            subgroup_quad_operations_in_all_stages: self.subgroup.quad_operations_in_all_stages,
+            point_clipping_behavior: self.point_clipping.point_clipping_behavior,
+            max_multiview_view_count: self.multiview.max_multiview_view_count,
+            max_multiview_instance_index: self.multiview.max_multiview_instance_index,
+            protected_no_fault: self.protected_memory.protected_no_fault,
+            max_per_set_descriptors: self.maintenance_3.max_per_set_descriptors,
+            max_memory_allocation_size: self.maintenance_3.max_memory_allocation_size,
+            ..vk::PhysicalDeviceVulkan11Properties::default()
+        }
+    }
+}
+
+/// Result of `Instance::get_physical_device_features2`. A version's features are `None` if
+/// the instance or the device does not support that version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Features2 {
+    pub features: vk::PhysicalDeviceFeatures,
+    pub vulkan_11: Option<vk::PhysicalDeviceVulkan11Features>,
+    pub vulkan_12: Option<vk::PhysicalDeviceVulkan12Features>,
+    pub vulkan_13: Option<vk::PhysicalDeviceVulkan13Features>,
+    pub vulkan_14: Option<vk::PhysicalDeviceVulkan14Features>,
+}
+
+/// Result of `Instance::get_physical_device_properties2`. A version's properties are `None`
+/// if the instance or the device does not support that version.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Properties2 {
+    pub properties: vk::PhysicalDeviceProperties,
+    /// Taken from `vulkan_12` where available, otherwise from VK_KHR_driver_properties.
+    pub driver: Option<vk::PhysicalDeviceDriverProperties>,
+    /// Present if the device supports VK_EXT_pci_bus_info.
+    pub pci_bus_info: Option<vk::PhysicalDevicePciBusInfoPropertiesExt>,
+    /// Present if the device supports VK_EXT_physical_device_drm.
+    pub drm: Option<vk::PhysicalDeviceDrmPropertiesExt>,
+    /// Taken from `vulkan_11` where available; missing before 1.1.
+    pub id: Option<vk::PhysicalDeviceIdProperties>,
+    pub vulkan_11: Option<vk::PhysicalDeviceVulkan11Properties>,
+    pub vulkan_12: Option<vk::PhysicalDeviceVulkan12Properties>,
+    pub vulkan_13: Option<vk::PhysicalDeviceVulkan13Properties>,
+    pub vulkan_14: Option<vk::PhysicalDeviceVulkan14Properties>,
+    /// The layouts `vulkan_14.p_copy_src_layouts` pointed to.
+    pub copy_src_layouts: Vec<vk::ImageLayout>,
+    /// The layouts `vulkan_14.p_copy_dst_layouts` pointed to.
+    pub copy_dst_layouts: Vec<vk::ImageLayout>,
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        unsafe {
+            (self.destroy_instance)(self.instance, ptr::null());
+        }
+    }
+}
+
+/// A physical device enumerated from an `Instance`. It borrows the instance, so it cannot be
+/// used after the instance has been destroyed.
+#[derive(Clone, Copy)]
+pub struct PhysicalDevice<'a> {
+    handle: vk::PhysicalDevice,
+    instance: PhantomData<&'a Instance>,
+}
+
+impl<'a> PhysicalDevice<'a> {
+    pub fn handle(&self) -> vk::PhysicalDevice {
+        self.handle
+    }
+}
+
+/// A logical device created by an `Instance`, destroyed when dropped.
+pub struct Device<'a> {
+    handle: vk::Device,
+    instance: &'a Instance,
+}
+
+impl<'a> Device<'a> {
+    pub fn handle(&self) -> vk::Device {
+        self.handle
+    }
+}
+
+impl<'a> Drop for Device<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            (self.instance.destroy_device)(self.handle, ptr::null());
+        }
+    }
+}
+
+/// A surface created by an `Instance`, destroyed when dropped.
+pub struct Surface<'a> {
+    handle: vk::SurfaceKhr,
+    instance: &'a Instance,
+}
+
+impl<'a> Surface<'a> {
+    pub fn handle(&self) -> vk::SurfaceKhr {
+        self.handle
+    }
+}
+
+impl<'a> Drop for Surface<'a> {
+    fn drop(&mut self) {
+        // Creating the surface made sure vkDestroySurfaceKHR is there.
+        if let Some(destroy_surface) = self.instance.destroy_surface {
+            unsafe {
+                destroy_surface(self.instance.instance, self.handle, ptr::null());
+            }
+        }
+    }
+}
+
+/// Result of `Instance::enumerate_physical_device_groups`.
+#[derive(Clone)]
+pub struct PhysicalDeviceGroup<'a> {
+    pub physical_devices: Vec<PhysicalDevice<'a>>,
+    /// Whether memory can be allocated on a subset of the devices only.
+    pub subset_allocation: bool,
 }
@@ -0,0 +1,98 @@
+//! The `--watch` mode, which polls VK_EXT_memory_budget for one device.
+
+use serde_json;
+use std::error::Error;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use format_size;
+use options::{Format, Options};
+use vkinfo::vulkan::{HeapBudget, Instance};
+
+#[derive(Debug, Serialize)]
+struct Sample<'a> {
+    timestamp_ms: u128,
+    device: usize,
+    device_name: &'a str,
+    heaps: Vec<HeapSample>,
+}
+
+#[derive(Debug, Serialize)]
+struct HeapSample {
+    heap: usize,
+    size: u64,
+    usage: u64,
+    budget: u64,
+    /// Changes since the previous sample, zero in the first one.
+    usage_delta: i64,
+    budget_delta: i64,
+}
+
+fn delta(current: u64, previous: u64) -> i64 {
+    current as i64 - previous as i64
+}
+
+fn format_delta(delta: i64) -> String {
+    format!("{}{}", if delta < 0 { "-" } else { "+" }, format_size(delta.unsigned_abs()))
+}
+
+pub fn watch(instance: &Instance, options: &Options) -> Result<(), Box<dyn Error>> {
+    let physical_devices = instance.enumerate_physical_devices()?;
+    let physical_device = *physical_devices.get(options.device)
+        .ok_or_else(|| format!("no physical device {} (found {})", options.device, physical_devices.len()))?;
+    let device_name = instance.get_physical_device_properties(physical_device).device_name.to_string_lossy();
+
+    if options.format == Format::Text {
+        println!("\nMemory budget of {} (device {})", device_name, options.device);
+    }
+
+    let start = Instant::now();
+    let mut previous: Option<Vec<HeapBudget>> = None;
+    let mut sample = 0;
+    while options.samples.is_none_or(|samples| sample < samples) {
+        if sample > 0 {
+            thread::sleep(options.interval);
+        }
+        sample += 1;
+
+        let budgets = instance.get_physical_device_memory_budget(physical_device)
+            .ok_or_else(|| format!("{} does not support VK_EXT_memory_budget", device_name))?;
+        let heaps: Vec<HeapSample> = budgets.iter().enumerate().map(|(index, heap)| {
+            let before = previous.as_ref().and_then(|previous| previous.get(index)).unwrap_or(heap);
+            HeapSample {
+                heap: index,
+                size: heap.size,
+                usage: heap.usage,
+                budget: heap.budget,
+                usage_delta: delta(heap.usage, before.usage),
+                budget_delta: delta(heap.budget, before.budget),
+            }
+        }).collect();
+
+        match options.format {
+            Format::Json => {
+                let sample = Sample {
+                    timestamp_ms: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis(),
+                    device: options.device,
+                    device_name: &device_name,
+                    heaps,
+                };
+                println!("{}", serde_json::to_string(&sample)?);
+            }
+            _ => {
+                println!("{:.1} s", start.elapsed().as_secs_f64());
+                for heap in &heaps {
+                    if previous.is_none() {
+                        println!("  heap {}: usage {}, budget {}, size {}", heap.heap, format_size(heap.usage), format_size(heap.budget), format_size(heap.size));
+                    } else {
+                        println!("  heap {}: usage {} ({}), budget {} ({}), size {}", heap.heap, format_size(heap.usage), format_delta(heap.usage_delta), format_size(heap.budget), format_delta(heap.budget_delta), format_size(heap.size));
+                    }
+                }
+            }
+        }
+
+        previous = Some(budgets);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,635 @@
+extern crate serde_json;
+extern crate serde_yaml;
+extern crate vkinfo;
+extern crate vkinfo_mock_icd;
+
+mod common;
+
+use std::process::Command;
+
+use vkinfo_mock_icd::config::CreateInfo;
+
+#[test]
+fn prints_layers_and_devices() {
+    let output = common::vkinfo("cli_report.yaml", common::STANDARD_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(&format!("Loaded {}", common::mock_icd_path().display())), "{}", stdout);
+    assert!(stdout.contains("Instance version: 1.3.250\n"), "{}", stdout);
+    assert!(stdout.contains("Found 1 layers"), "{}", stdout);
+    assert!(stdout.contains("VK_KHR_surface"), "{}", stdout);
+    assert!(stdout.contains("VK_LAYER_MOCK_validation"), "{}", stdout);
+    assert!(stdout.contains("VK_EXT_debug_utils"), "{}", stdout);
+    assert!(stdout.contains("Found 2 devices"), "{}", stdout);
+    assert!(stdout.contains("device_name: Mock Discrete GPU"), "{}", stdout);
+    assert!(stdout.contains("device_name: Mock CPU"), "{}", stdout);
+    assert!(stdout.contains("geometry_shader: 1"), "{}", stdout);
+    assert!(stdout.contains("\nQueue Families\n  0: 4 queues, GraphicsBit | ComputeBit | TransferBit\n    Timestamp valid bits: 64\n    Min image transfer granularity: 1x1x1\n  1: 2 queues, TransferBit\n"), "{}", stdout);
+    assert!(stdout.contains("\nVulkan 1.3 Features\n"), "{}", stdout);
+    assert!(stdout.contains("dynamic_rendering: 1"), "{}", stdout);
+    assert!(stdout.contains("driver_id: MesaRadv"), "{}", stdout);
+    assert_eq!(stdout.matches("\nVulkan 1.1 Properties\n").count(), 1, "{}", stdout);
+    assert!(!stdout.contains("Vulkan 1.4"), "{}", stdout);
+    assert!(stdout.contains("\nDriver\n  API version: 1.3.0\n  Driver version: Mesa 24.2.3\n  Driver ID: MesaRadv\n  Driver name: radv\n  Driver info: Mesa 24.2.3\n  Conformance version: 1.3.8.0\n"), "{}", stdout);
+    assert!(stdout.contains("\nDriver\n  API version: 1.0.0\n  Driver version: Mesa 0.0.0\n\n"), "{}", stdout);
+    assert!(stdout.contains("Memory Heaps\n  0: 8.00 GiB DeviceLocalBit\n  1: 16.00 GiB (empty)\n"), "{}", stdout);
+    assert!(stdout.contains("  1: heap 1 HostVisibleBit | HostCoherentBit\n"), "{}", stdout);
+    assert!(stdout.contains("Found 2 supported formats"), "{}", stdout);
+    assert!(stdout.contains("  D32Sfloat\n    linear: (empty)\n    optimal: SampledImageBit | DepthStencilAttachmentBit | BlitSrcBit\n    buffer: (empty)\n"), "{}", stdout);
+    assert!(stdout.contains("Found 2 sparse format/sample count pairs"), "{}", stdout);
+    assert!(stdout.contains("  R8g8b8a8Unorm Count4Bit\n    aspect ColorBit, granularity 64x64x1, flags SingleMiptailBit\n"), "{}", stdout);
+    assert!(stdout.contains("Found 2 device extensions"), "{}", stdout);
+    assert!(stdout.contains("extension_name: VK_KHR_swapchain"), "{}", stdout);
+    assert!(stdout.contains("Found 1 device layers"), "{}", stdout);
+    assert!(stdout.contains("extension_name: VK_EXT_validation_cache"), "{}", stdout);
+    assert!(!stdout.contains("displays: "), "{}", stdout);
+}
+
+#[test]
+fn prints_empty_driver() {
+    let output = common::vkinfo("cli_empty.yaml", "{}").output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("Instance version: 1.0.0\n"), "{}", stdout);
+    assert!(stdout.contains("Found 0 layers"), "{}", stdout);
+    assert!(stdout.contains("Found 0 devices"), "{}", stdout);
+}
+
+#[test]
+fn prints_unknown_device_types() {
+    let config = "
+physical_devices:
+  - properties:
+      device_type: {Unknown: 1000}
+      device_name: Future GPU
+";
+    let output = common::vkinfo("cli_unknown_type.yaml", config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("device_type:\n  Unknown: 1000\n"), "{}", stdout);
+}
+
+#[test]
+fn enumerations_survive_count_changes() {
+    let config = format!("{}\ncount_drift: {{vkEnumerateInstanceLayerProperties: -1, vkEnumeratePhysicalDevices: 3}}\n", common::STANDARD_CONFIG);
+    let output = common::vkinfo("cli_count_drift.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("Found 1 layers"), "{}", stdout);
+    assert!(stdout.contains("Found 2 devices"), "{}", stdout);
+}
+
+#[test]
+fn loader_from_environment() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo"))
+        .env("VKINFO_LOADER", common::mock_icd_path())
+        .env(vkinfo_mock_icd::config::CONFIG_ENV, common::write_config("cli_env.yaml", common::STANDARD_CONFIG))
+        .output().unwrap();
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(common::stdout(&output).contains(&format!("Loaded {}", common::mock_icd_path().display())));
+}
+
+#[test]
+fn missing_loader_fails() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo"))
+        .args(["--loader", "/nonexistent/libvulkan.so.1"])
+        .output().unwrap();
+
+    let stderr = common::stderr(&output);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("vkinfo: no Vulkan loader found (/nonexistent/libvulkan.so.1: "), "{}", stderr);
+}
+
+#[test]
+fn broken_driver_reports_the_failing_call() {
+    let output = common::vkinfo("cli_broken.yaml", "failures: {vkEnumeratePhysicalDevices: ErrorInitializationFailed}").output().unwrap();
+    let stderr = common::stderr(&output);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr, "vkinfo: vkEnumeratePhysicalDevices failed: ErrorInitializationFailed\n");
+}
+
+#[test]
+fn instance_is_destroyed_on_errors() {
+    let log = common::call_log("cli_destroy.log");
+    let config = format!("call_log: {}\nfailures: {{vkEnumeratePhysicalDevices: ErrorDeviceLost}}", log.display());
+    let output = common::vkinfo("cli_destroy.yaml", &config).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkDestroyInstance\n");
+}
+
+#[test]
+fn missing_replay_file_fails() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--replay", "/nonexistent/report.yaml"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(common::stderr(&output).starts_with("vkinfo: "));
+}
+
+#[test]
+fn rejects_unknown_arguments() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).arg("--bogus").output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("unknown argument '--bogus'"));
+}
+
+#[test]
+fn instance_flags_reach_the_driver() {
+    let log = common::call_log("cli_instance_flags.log");
+    let config = format!("{}\ncreate_info_log: {}\n", common::STANDARD_CONFIG, log.display());
+    let output = common::vkinfo("cli_instance_flags.yaml", &config)
+        .args(["--api-version", "1.2", "--layer", "VK_LAYER_MOCK_validation", "--extension", "VK_EXT_debug_utils"])
+        .args(["--app-name", "probe", "--app-version", "2.1.5", "--engine-name", "none", "--engine-version", "42"])
+        .output().unwrap();
+    assert!(output.status.success(), "{}", common::stderr(&output));
+
+    let create_info: CreateInfo = serde_yaml::from_str(&common::logged_calls(&log)).unwrap();
+    assert_eq!(create_info.application_name.as_deref(), Some("probe"));
+    assert_eq!(create_info.application_version, (2 << 22) | (1 << 12) | 5);
+    assert_eq!(create_info.engine_name.as_deref(), Some("none"));
+    assert_eq!(create_info.engine_version, 42);
+    assert_eq!(create_info.api_version, (1 << 22) | (2 << 12));
+    assert_eq!(create_info.layers, ["VK_LAYER_MOCK_validation"]);
+    // The headless surface extensions are added since the driver offers them.
+    assert_eq!(create_info.extensions, ["VK_EXT_debug_utils", "VK_KHR_surface", "VK_EXT_headless_surface"]);
+}
+
+#[test]
+fn prints_headless_surface() {
+    let log = common::call_log("cli_surface.log");
+    let config = format!("{}\ncall_log: {}\n", common::STANDARD_CONFIG, log.display());
+    let output = common::vkinfo("cli_surface.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "\nHeadless Surface\n",
+        "  Image count: 2 to unlimited\n",
+        "  Current extent: set by the swapchain\n",
+        "  Image extent: 1x1 to 16384x16384\n",
+        "  Max image array layers: 1\n",
+        "  Supported transforms: IdentityBit\n",
+        "  Current transform: IdentityBit\n",
+        "  Supported composite alpha: OpaqueBit\n",
+        "  Supported usage: TransferSrcBit | TransferDstBit | SampledBit | StorageBit | ColorAttachmentBit\n",
+        "  Formats:\n",
+        "    B8g8r8a8Srgb SrgbNonlinear\n",
+        "    R8g8b8a8Unorm SrgbNonlinear\n",
+        "  Present modes: [Fifo, Mailbox, Immediate]\n",
+        "  Queue family 0 can present: true\n",
+        "  Queue family 1 can present: false\n")), "{}", stdout);
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkCreateHeadlessSurfaceEXT\nvkDestroySurfaceKHR\nvkDestroyInstance\n");
+
+    let output = common::vkinfo("cli_surface.yaml", &config).args(["--format", "yaml"]).output().unwrap();
+    assert!(common::stdout(&output).contains("present_modes:\n        - Fifo\n"), "{}", common::stdout(&output));
+}
+
+#[test]
+fn surface_failures_are_reported() {
+    for failure in &["vkCreateHeadlessSurfaceEXT", "vkGetPhysicalDeviceSurfaceCapabilitiesKHR", "vkGetPhysicalDeviceSurfaceSupportKHR"] {
+        let config = format!("{}\nfailures: {{{}: ErrorSurfaceLostKhr}}\n", common::STANDARD_CONFIG, failure);
+        let output = common::vkinfo("cli_surface_fails.yaml", &config).output().unwrap();
+        let stdout = common::stdout(&output);
+
+        assert!(output.status.success(), "{}: {}", failure, common::stderr(&output));
+        assert!(stdout.contains(&format!("\nHeadless Surface\n  unavailable: {} failed: ErrorSurfaceLostKhr\n", failure)), "{}", stdout);
+        assert!(stdout.contains("\nMemory Heaps\n"), "{}", stdout);
+
+        let saved = common::vkinfo("cli_surface_fails.yaml", &config).args(["--format", "json"]).output().unwrap();
+        let report: vkinfo::report::Report = serde_json::from_str(&common::stdout(&saved)).unwrap();
+        assert!(report.physical_devices[0].surface.is_none());
+        assert_eq!(report.physical_devices[0].errors["surface"], format!("{} failed: ErrorSurfaceLostKhr", failure));
+
+        let path = common::write_config("cli_surface_fails_saved.json", &common::stdout(&saved));
+        let replayed = Command::new(env!("CARGO_BIN_EXE_vkinfo")).arg("--replay").arg(&path).output().unwrap();
+        assert!(common::stdout(&replayed).contains(&format!("\nHeadless Surface\n  unavailable: {} failed: ErrorSurfaceLostKhr\n", failure)), "{}", common::stdout(&replayed));
+    }
+}
+
+#[test]
+fn surface_is_left_out_without_headless_surface() {
+    let config = common::STANDARD_CONFIG.replace("VK_EXT_headless_surface", "VK_EXT_debug_report");
+    let output = common::vkinfo("cli_no_surface.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(!stdout.contains("Headless Surface"), "{}", stdout);
+}
+
+#[test]
+fn prints_displays() {
+    let output = common::vkinfo("cli_displays.yaml", common::DISPLAY_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "\nFound 2 displays: \n",
+        "  Display 0: HDMI-A-1\n",
+        "    Physical size: 600x340 mm, resolution 3840x2160\n",
+        "    Supported transforms: IdentityBit\n",
+        "    Plane reorder possible: false, persistent content: false\n",
+        "    Mode 0: 3840x2160 @ 60.000 Hz\n",
+        "      Plane 0: alpha OpaqueBit\n",
+        "        source position (0, 0) to (0, 0), extent 0x0 to 4096x4096\n",
+        "        destination position (0, 0) to (3840, 2160), extent 1x1 to 3840x2160\n",
+        "      Plane 1: alpha GlobalBit | PerPixelBit\n")), "{}", stdout);
+    assert!(stdout.contains("    Mode 1: 1920x1080 @ 59.940 Hz\n"), "{}", stdout);
+    assert!(stdout.contains("  Display 1: unnamed\n"), "{}", stdout);
+    assert!(stdout.contains(concat!(
+        "\nFound 2 display planes: \n",
+        "  Plane 0: current display 0, stack index 0, displays [0]\n",
+        "  Plane 1: current display none, stack index 1, displays [0, 1]\n")), "{}", stdout);
+}
+
+#[test]
+fn display_failures_are_reported() {
+    for failure in &["vkGetPhysicalDeviceDisplayPropertiesKHR", "vkGetDisplayPlaneCapabilitiesKHR"] {
+        let config = format!("{}\nfailures: {{{}: ErrorOutOfHostMemory}}\n", common::DISPLAY_CONFIG, failure);
+        let output = common::vkinfo("cli_displays_fail.yaml", &config).output().unwrap();
+        let stdout = common::stdout(&output);
+
+        assert!(output.status.success(), "{}: {}", failure, common::stderr(&output));
+        assert!(stdout.contains(&format!("\nDisplays\n  unavailable: {} failed: ErrorOutOfHostMemory\n", failure)), "{}", stdout);
+        assert!(!stdout.contains("displays: "), "{}", stdout);
+        assert!(stdout.contains("\nMemory Heaps\n"), "{}", stdout);
+    }
+}
+
+#[test]
+fn prints_video_profiles() {
+    let output = common::vkinfo("cli_video.yaml", common::VIDEO_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "\nVideo Profiles\n",
+        "  Codec         Profile                  Chroma Bits Max extent  DPB  Refs Queues  Formats\n",
+        "  H.264 decode  High                     4:2:0  8    4096x4096   17   16   1       G8B8r82plane420Unorm\n",
+        "  H.265 decode  Main 10                  4:2:0  10   8192x4352   16   15   1       G10x6B10x6r10x62plane420Unorm3pack16, G8B8r82plane420Unorm (DPB: G10x6B10x6r10x62plane420Unorm3pack16)\n",
+        "  H.264 encode  Main                     4:2:0  8    1920x1088   4    2    2       G8B8r82plane420Unorm\n")), "{}", stdout);
+    assert!(!stdout.contains("AV1"), "{}", stdout);
+
+    let output = common::vkinfo("cli_video.yaml", common::VIDEO_CONFIG).args(["--format", "yaml"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_yaml::from_str(&common::stdout(&output)).unwrap();
+    let profiles = report.physical_devices[0].video_profiles.as_ref().unwrap();
+    assert_eq!(profiles.len(), 3);
+    assert_eq!(profiles[1].capabilities.max_level, 186);
+    assert_eq!(profiles[1].dpb_formats, [vkinfo::vk::Format::G10x6B10x6r10x62plane420Unorm3pack16]);
+    assert_eq!(profiles[2].queue_families, [2]);
+}
+
+#[test]
+fn prints_external_handles_and_device_ids() {
+    let output = common::vkinfo("cli_external.yaml", common::EXTERNAL_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "  Device UUID: 00112233-4455-6677-8899-aabbccddeeff\n",
+        "  Driver UUID: 01020304-0506-0708-090a-0b0c0d0e0f10\n",
+        "  Device LUID: none\n")), "{}", stdout);
+    assert!(stdout.contains(concat!(
+        "\nExternal Handles\n",
+        "  Handle type                      Buffer                     Image                      Semaphore       Fence\n",
+        "  OpaqueFdBit                      export, import             export, import, dedicated  export, import  no\n",
+        "  OpaqueWin32Bit                   no                         no                         no              no\n",
+        "  OpaqueWin32KmtBit                no                         no                         no              no\n",
+        "  D3d11TextureBit                  no                         no                         -               -\n",
+        "  D3d11TextureKmtBit               no                         no                         -               -\n",
+        "  D3d12HeapBit                     no                         no                         -               -\n",
+        "  D3d12ResourceBit                 no                         no                         -               -\n",
+        "  DmaBufBitExt                     export, import             no                         -               -\n",
+        "  D3d12FenceBit                    -                          -                          no              -\n",
+        "  SyncFdBit                        -                          -                          export          export, import\n",
+        "\n")), "{}", stdout);
+    assert!(!stdout.contains("HostAllocationBitExt"), "{}", stdout);
+
+    let output = common::vkinfo("cli_external.yaml", common::EXTERNAL_CONFIG).args(["--format", "yaml"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_yaml::from_str(&common::stdout(&output)).unwrap();
+    let device = &report.physical_devices[0];
+    assert_eq!(device.id_properties.as_ref().unwrap().driver_uuid[0], 1);
+    let handles = device.external_handles.as_ref().unwrap();
+    assert_eq!(handles.buffers.len(), 8);
+    assert_eq!(handles.images[0].properties.unwrap().external_memory_features, vkinfo::vk::ExternalMemoryFeatureFlags::all());
+    assert_eq!(handles.semaphores.len(), 5);
+    assert_eq!(handles.fences.len(), 4);
+}
+
+#[test]
+fn external_handle_types_the_driver_fails_on_are_unknown() {
+    let config = format!("{}\nfailures: {{vkGetPhysicalDeviceImageFormatProperties2: ErrorOutOfHostMemory}}\n", common::EXTERNAL_CONFIG);
+    let output = common::vkinfo("cli_external_fails.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("  OpaqueFdBit                      export, import             unknown                    export, import  no\n"), "{}", stdout);
+
+    let output = common::vkinfo("cli_external_fails.yaml", &config).args(["--format", "yaml"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_yaml::from_str(&common::stdout(&output)).unwrap();
+    let handles = report.physical_devices[0].external_handles.as_ref().unwrap();
+    assert!(handles.images.iter().all(|image| image.properties.is_none()));
+    assert!(handles.buffers.iter().all(|buffer| buffer.properties.is_some()));
+}
+
+#[test]
+fn prints_active_tools_against_the_instance_layers() {
+    let log = common::call_log("cli_tooling.log");
+    let config = format!("{}\ncall_log: {}\n", common::TOOLING_CONFIG, log.display());
+    let output = common::vkinfo("cli_tooling.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "\nActive Tools\n",
+        "  Khronos Validation Layer 1.3.280\n",
+        "    Purposes: ValidationBit | DebugReportingBitExt\n",
+        "    Description: LunarG validation layer\n",
+        "    Layer: VK_LAYER_KHRONOS_validation (instance layer)\n",
+        "  Driver Profiler 2\n",
+        "    Purposes: ProfilingBit\n",
+        "    Description: Built into the driver\n",
+        "    Layer: none\n")), "{}", stdout);
+    assert!(stdout.contains(concat!(
+        "\nActive Tools\n",
+        "  RenderDoc 1.31\n",
+        "    Purposes: TracingBit\n",
+        "    Description: Frame capture\n",
+        "    Layer: VK_LAYER_RENDERDOC_Capture (not among the instance layers)\n")), "{}", stdout);
+    // Only the 1.2 device needs a logical device for VK_EXT_tooling_info.
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkCreateDevice\nvkDestroyDevice\nvkDestroyInstance\n");
+
+    let output = common::vkinfo("cli_tooling.yaml", common::TOOLING_CONFIG).args(["--format", "json"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_json::from_str(&common::stdout(&output)).unwrap();
+    assert_eq!(report.physical_devices[0].tools.as_ref().unwrap().len(), 2);
+    assert_eq!(report.physical_devices[1].tools.as_ref().unwrap()[0].name.to_string_lossy(), "RenderDoc");
+}
+
+#[test]
+fn tool_failures_are_reported() {
+    for failure in &["vkCreateDevice", "vkGetPhysicalDeviceToolPropertiesEXT"] {
+        let config = format!("{}\nfailures: {{{}: ErrorInitializationFailed}}\n", common::TOOLING_CONFIG, failure);
+        let output = common::vkinfo("cli_tooling_fails.yaml", &config).output().unwrap();
+        let stdout = common::stdout(&output);
+
+        assert!(output.status.success(), "{}: {}", failure, common::stderr(&output));
+        assert!(stdout.contains("\nActive Tools\n  Khronos Validation Layer 1.3.280\n"), "{}: {}", failure, stdout);
+        assert!(stdout.contains(&format!("\nActive Tools\n  unavailable: {} failed: ErrorInitializationFailed\n", failure)), "{}: {}", failure, stdout);
+        assert!(!stdout.contains("RenderDoc"), "{}: {}", failure, stdout);
+    }
+
+    let config = format!("{}\nfailures: {{vkGetPhysicalDeviceToolProperties: ErrorOutOfHostMemory}}\n", common::TOOLING_CONFIG);
+    let output = common::vkinfo("cli_tooling_fails.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nActive Tools\n  unavailable: vkGetPhysicalDeviceToolProperties failed: ErrorOutOfHostMemory\n"), "{}", stdout);
+    assert!(!stdout.contains("Khronos Validation Layer"), "{}", stdout);
+
+    let output = common::vkinfo("cli_tooling_fails.yaml", &config).args(["--format", "json"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_json::from_str(&common::stdout(&output)).unwrap();
+    assert_eq!(report.physical_devices[0].errors["tools"], "vkGetPhysicalDeviceToolProperties failed: ErrorOutOfHostMemory");
+}
+
+#[test]
+fn video_profile_failures_are_reported_and_unsupported_profiles_skipped() {
+    let config = format!("{}\nfailures: {{vkGetPhysicalDeviceVideoCapabilitiesKHR: ErrorOutOfHostMemory}}\n", common::VIDEO_CONFIG);
+    let output = common::vkinfo("cli_video_fails.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nVideo Profiles\n  unavailable: vkGetPhysicalDeviceVideoCapabilitiesKHR failed: ErrorOutOfHostMemory\n"), "{}", stdout);
+
+    let config = format!("{}\nfailures: {{vkGetPhysicalDeviceVideoCapabilitiesKHR: ErrorVideoProfileCodecNotSupportedKhr}}\n", common::VIDEO_CONFIG);
+    let output = common::vkinfo("cli_video_unsupported.yaml", &config).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nVideo Profiles\n  none\n"), "{}", stdout);
+}
+
+#[test]
+fn prints_queue_family_additions_and_unknown_flags() {
+    let output = common::vkinfo("cli_queue_families.yaml", common::QUEUE_FAMILY_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains(concat!(
+        "  0: 1 queues, GraphicsBit | ComputeBit | TransferBit | ProtectedBit\n",
+        "    Timestamp valid bits: 64\n",
+        "    Min image transfer granularity: 1x1x1\n",
+        "    Global priorities: [Low, Medium, High, Realtime]\n",
+        "    Checkpoint execution stages: TopOfPipeBit | BottomOfPipeBit\n",
+        "    Video codec operations: (empty)\n")), "{}", stdout);
+    assert!(stdout.contains("  1: 2 queues, VideoDecodeBitKhr | 0x10000\n"), "{}", stdout);
+    assert!(stdout.contains("    Video codec operations: DecodeH264Bit | DecodeH265Bit\n"), "{}", stdout);
+}
+
+#[test]
+fn unavailable_layer_fails() {
+    let output = common::vkinfo("cli_missing_layer.yaml", common::STANDARD_CONFIG).args(["--layer", "VK_LAYER_missing"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(common::stderr(&output), "vkinfo: instance layer VK_LAYER_missing is not available\n");
+}
+
+#[test]
+fn rejects_malformed_versions() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--api-version", "1.x"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("invalid version '1.x' for '--api-version'"));
+}
+
+#[test]
+fn sweeps_image_formats() {
+    let output = common::vkinfo("cli_image_formats.yaml", common::STANDARD_CONFIG)
+        .args(["--image-formats", "--vk-format", "VK_FORMAT_R8G8B8A8_UNORM", "--image-type", "2d", "--usage", "sampled", "--usage", "sampled,input-attachment"])
+        .output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nImage format properties of Mock Discrete GPU\n"), "{}", stdout);
+    assert!(stdout.contains("  R8g8b8a8Unorm Type2d Optimal SampledBit: max extent 16384x16384x1, 15 mip levels, 2048 array layers, samples Count1Bit | Count4Bit, max resource size 1.00 TiB\n"), "{}", stdout);
+    assert!(stdout.contains("  R8g8b8a8Unorm Type2d Optimal SampledBit | InputAttachmentBit: unsupported\n"), "{}", stdout);
+    assert!(stdout.contains("  R8g8b8a8Unorm Type2d Linear SampledBit: unsupported\n"), "{}", stdout);
+    assert!(!stdout.contains("Found"), "{}", stdout);
+}
+
+#[test]
+fn image_format_sweep_defaults_to_supported_formats() {
+    let output = common::vkinfo("cli_image_formats_default.yaml", common::STANDARD_CONFIG).args(["--image-formats", "--format", "yaml"]).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    // Two optimal formats and one linear format, each as 1D, 2D and 3D images.
+    assert_eq!(stdout.matches("- device: Mock Discrete GPU").count(), 9, "{}", stdout);
+    assert_eq!(stdout.matches("properties: ~").count(), 8, "{}", stdout);
+    assert!(!stdout.contains("Mock CPU"), "{}", stdout);
+}
+
+#[test]
+fn rejects_image_format_sweep_of_replays() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--image-formats", "--replay", "report.yaml"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("--image-formats needs a driver"));
+}
+
+#[test]
+fn nests_devices_under_their_groups() {
+    let output = common::vkinfo("cli_device_groups.yaml", common::DEVICE_GROUP_CONFIG).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nFound 2 device groups: \n\nDevice group 0: subset allocation true\n  0: Linked GPU A\n  2: Linked GPU B\n\nDevice 0: Linked GPU A\n"), "{}", stdout);
+    assert!(stdout.contains("\nDevice group 1: subset allocation false\n  1: Single GPU\n\nDevice 1: Single GPU\n"), "{}", stdout);
+    assert!(stdout.find("\nDevice 2: Linked GPU B\n") < stdout.find("\nDevice group 1:"), "{}", stdout);
+
+    let output = common::vkinfo("cli_device_groups.yaml", common::DEVICE_GROUP_CONFIG).args(["--format", "yaml"]).output().unwrap();
+    let report: vkinfo::report::Report = serde_yaml::from_str(&common::stdout(&output)).unwrap();
+    assert_eq!(report.device_groups.len(), 2);
+    assert_eq!(report.device_groups[0].physical_devices[1], vkinfo::report::GroupMember { index: 2, device_name: "Linked GPU B".to_string() });
+    assert!(report.device_groups[0].subset_allocation);
+}
+
+const WATCH_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 3, patch: 0}
+physical_devices:
+  - properties:
+      api_version: 4198400
+      device_name: Mock Budget GPU
+    extensions:
+      - extension_name: VK_EXT_memory_budget
+        spec_version: 1
+    memory_heaps:
+      - size: 8589934592
+        flags: {bits: 1}
+      - size: 17179869184
+    memory_budgets:
+      - heap_usage: [1073741824, 0]
+        heap_budget: [6442450944, 12884901888]
+      - heap_usage: [1342177280, 1024]
+        heap_budget: [6174015488, 12884901888]
+  - properties:
+      device_name: Mock CPU
+"#;
+
+#[test]
+fn watches_memory_budget() {
+    let output = common::vkinfo("cli_watch.yaml", WATCH_CONFIG).args(["--watch", "--interval", "0.01", "--samples", "3"]).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nMemory budget of Mock Budget GPU (device 0)\n"), "{}", stdout);
+    assert!(stdout.contains("\n  heap 0: usage 1.00 GiB, budget 6.00 GiB, size 8.00 GiB\n  heap 1: usage 0 B, budget 12.00 GiB, size 16.00 GiB\n"), "{}", stdout);
+    assert!(stdout.contains("\n  heap 0: usage 1.25 GiB (+256.00 MiB), budget 5.75 GiB (-256.00 MiB), size 8.00 GiB\n  heap 1: usage 1.00 KiB (+1.00 KiB), budget 12.00 GiB (+0 B), size 16.00 GiB\n"), "{}", stdout);
+    assert!(stdout.contains("\n  heap 0: usage 1.25 GiB (+0 B), budget 5.75 GiB (+0 B), size 8.00 GiB\n"), "{}", stdout);
+    assert!(!stdout.contains("Found"), "{}", stdout);
+}
+
+#[test]
+fn watch_prints_json_lines() {
+    let output = common::vkinfo("cli_watch_json.yaml", WATCH_CONFIG).args(["--watch", "--interval", "0.01", "--samples", "2", "--format", "json"]).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    let samples: Vec<serde_json::Value> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(samples.len(), 2, "{}", stdout);
+    assert_eq!(samples[0]["device_name"], "Mock Budget GPU");
+    assert_eq!(samples[0]["heaps"][0]["usage"], 1073741824);
+    assert_eq!(samples[0]["heaps"][0]["usage_delta"], 0);
+    assert_eq!(samples[1]["heaps"][0]["usage_delta"], 268435456);
+    assert_eq!(samples[1]["heaps"][0]["budget_delta"], -268435456);
+    assert!(samples[1]["timestamp_ms"].as_u64().unwrap() >= samples[0]["timestamp_ms"].as_u64().unwrap());
+}
+
+#[test]
+fn watch_needs_a_device_with_memory_budget() {
+    let output = common::vkinfo("cli_watch_cpu.yaml", WATCH_CONFIG).args(["--watch", "--device", "1", "--samples", "1"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(common::stderr(&output).contains("Mock CPU does not support VK_EXT_memory_budget"), "{}", common::stderr(&output));
+
+    let output = common::vkinfo("cli_watch_missing.yaml", WATCH_CONFIG).args(["--watch", "--device", "2"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(common::stderr(&output).contains("no physical device 2 (found 2)"), "{}", common::stderr(&output));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--watch", "--format", "yaml"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("--watch prints text or json"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--watch", "--interval", "-1"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("invalid interval '-1' for '--interval'"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_vkinfo")).args(["--watch", "--interval", "0"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(common::stderr(&output).contains("invalid interval '0' for '--interval'"));
+}
+
+#[cfg(unix)]
+#[test]
+fn locates_devices_in_sysfs() {
+    let sysfs = common::sysfs_fixture("cli_sysfs");
+    let config = r#"
+physical_devices:
+  - properties:
+      api_version: 4198400
+      device_name: Mock PCI GPU
+    extensions:
+      - extension_name: VK_EXT_pci_bus_info
+        spec_version: 2
+    pci_bus_info: {pci_domain: 0, pci_bus: 3, pci_device: 0, pci_function: 0}
+  - properties:
+      device_name: Mock CPU
+"#;
+    let config = format!("instance_version: {{major: 1, minor: 1, patch: 0}}\n{}", config);
+    let output = common::vkinfo("cli_sysfs.yaml", &config).arg("--sysfs-root").arg(&sysfs).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.contains("\nLocation\n  PCI address: 0000:03:00.0\n  Kernel driver: amdgpu\n  Primary node: /dev/dri/card1\n  Render node: /dev/dri/renderD128\n"), "{}", stdout);
+    assert_eq!(stdout.matches("\nLocation\n").count(), 1, "{}", stdout);
+
+    let output = common::vkinfo("cli_sysfs.yaml", &config).arg("--sysfs-root").arg(&sysfs).args(["--format", "yaml"]).output().unwrap();
+    assert!(common::stdout(&output).contains("pci_address: \"0000:03:00.0\""), "{}", common::stdout(&output));
+}
+
+fn report_body(stdout: &str) -> &str {
+    &stdout[stdout.find("Instance version").unwrap()..]
+}
+
+#[test]
+fn replays_saved_reports() {
+    let live = common::vkinfo("cli_replay.yaml", common::STANDARD_CONFIG).output().unwrap();
+    assert!(live.status.success(), "{}", common::stderr(&live));
+
+    for &format in &["yaml", "json"] {
+        let saved = common::vkinfo("cli_replay.yaml", common::STANDARD_CONFIG).args(["--format", format]).output().unwrap();
+        assert!(saved.status.success(), "{}", common::stderr(&saved));
+        let path = common::write_config(&format!("cli_replay_saved.{}", format), &common::stdout(&saved));
+
+        let replayed = Command::new(env!("CARGO_BIN_EXE_vkinfo")).arg("--replay").arg(&path).output().unwrap();
+        let stdout = common::stdout(&replayed);
+
+        assert!(replayed.status.success(), "{}", common::stderr(&replayed));
+        assert!(stdout.starts_with(&format!("Replaying {}", path.display())), "{}", stdout);
+        assert_eq!(report_body(&stdout), report_body(&common::stdout(&live)));
+    }
+}
+
+#[test]
+fn structured_output_has_no_progress_lines() {
+    let output = common::vkinfo("cli_json.yaml", common::STANDARD_CONFIG).args(["--format", "json"]).output().unwrap();
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success(), "{}", common::stderr(&output));
+    assert!(stdout.starts_with('{'), "{}", stdout);
+    assert!(stdout.contains("\"device_name\": \"Mock Discrete GPU\""), "{}", stdout);
+}
@@ -0,0 +1,455 @@
+#![allow(dead_code)]
+
+use std::env;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Mutex, MutexGuard};
+
+use vkinfo_mock_icd::config::CONFIG_ENV;
+
+/// A Vulkan 1.3 driver with the headless surface extensions, one layer providing its own
+/// extension and two devices. The first device supports Vulkan 1.3 with some of its newer
+/// features and properties, and has two memory heaps, two formats (one of which it can make 2D
+/// optimal and sparse images of), a surface its first queue family can present to, two
+/// extensions and sees the layer too. The second is a Vulkan 1.0 CPU.
+pub const STANDARD_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 3, patch: 250}
+extensions:
+  - extension_name: VK_KHR_surface
+    spec_version: 25
+  - extension_name: VK_EXT_headless_surface
+    spec_version: 1
+layers:
+  - properties:
+      layer_name: VK_LAYER_MOCK_validation
+      spec_version: 4198400
+      implementation_version: 1
+      description: Mock validation layer
+    extensions:
+      - extension_name: VK_EXT_debug_utils
+        spec_version: 2
+physical_devices:
+  - properties:
+      api_version: 4206592
+      driver_version: 100671491
+      vendor_id: 4098
+      device_id: 29695
+      device_type: DiscreteGpu
+      device_name: Mock Discrete GPU
+      limit:
+        max_image_dimension_2d: 16384
+    features:
+      geometry_shader: 1
+      sampler_anisotropy: 1
+    vulkan_11_features:
+      multiview: 1
+      shader_draw_parameters: 1
+    vulkan_12_features:
+      timeline_semaphore: 1
+      buffer_device_address: 1
+    vulkan_13_features:
+      dynamic_rendering: 1
+      synchronization2: 1
+    vulkan_11_properties:
+      subgroup_size: 32
+      subgroup_supported_stages: {bits: 63}
+      subgroup_supported_operations: {bits: 255}
+      point_clipping_behavior: UserClipPlanesOnly
+      max_memory_allocation_size: 4294967296
+    vulkan_12_properties:
+      driver_id: MesaRadv
+      driver_name: radv
+      driver_info: Mesa 24.2.3
+      conformance_version: {major: 1, minor: 3, subminor: 8, patch: 0}
+      max_timeline_semaphore_value_difference: 4294967295
+    vulkan_13_properties:
+      max_inline_uniform_block_size: 4096
+    queue_families:
+      - queue_flags: {bits: 7}
+        queue_count: 4
+        timestamp_valid_bits: 64
+        min_image_transfer_granularity: {width: 1, height: 1, depth: 1}
+      - queue_flags: {bits: 4}
+        queue_count: 2
+    surface:
+      capabilities:
+        min_image_count: 2
+        current_extent: {width: 4294967295, height: 4294967295}
+        min_image_extent: {width: 1, height: 1}
+        max_image_extent: {width: 16384, height: 16384}
+        max_image_array_layers: 1
+        supported_transforms: {bits: 1}
+        current_transform: {bits: 1}
+        supported_composite_alpha: {bits: 1}
+        supported_usage_flags: {bits: 31}
+      formats:
+        - format: B8g8r8a8Srgb
+          color_space: SrgbNonlinear
+        - format: R8g8b8a8Unorm
+          color_space: SrgbNonlinear
+      present_modes: [Fifo, Mailbox, Immediate]
+      queue_family_support: [true, false]
+    memory_heaps:
+      - size: 8589934592
+        flags: {bits: 1}
+      - size: 17179869184
+    memory_types:
+      - property_flags: {bits: 1}
+        heap_index: 0
+      - property_flags: {bits: 6}
+        heap_index: 1
+      - property_flags: {bits: 7}
+        heap_index: 0
+    formats:
+      - format: R8g8b8a8Unorm
+        properties:
+          linear_tiling_features: {bits: 3073}
+          optimal_tiling_features: {bits: 7555}
+          buffer_features: {bits: 88}
+      - format: D32Sfloat
+        properties:
+          optimal_tiling_features: {bits: 1537}
+    image_formats:
+      - format: R8g8b8a8Unorm
+        image_type: Type2d
+        tiling: Optimal
+        usage: {bits: 31}
+        properties:
+          max_extent: {width: 16384, height: 16384, depth: 1}
+          max_mip_levels: 15
+          max_array_layers: 2048
+          sample_counts: {bits: 5}
+          max_resource_size: 1099511627776
+    sparse_formats:
+      - format: R8g8b8a8Unorm
+        samples: {bits: 1}
+        properties:
+          - aspect_mask: {bits: 1}
+            image_granularity: {width: 128, height: 128, depth: 1}
+      - format: R8g8b8a8Unorm
+        samples: {bits: 4}
+        properties:
+          - aspect_mask: {bits: 1}
+            image_granularity: {width: 64, height: 64, depth: 1}
+            flags: {bits: 1}
+    extensions:
+      - extension_name: VK_KHR_swapchain
+        spec_version: 70
+      - extension_name: VK_KHR_maintenance1
+        spec_version: 2
+    layers:
+      - properties:
+          layer_name: VK_LAYER_MOCK_validation
+          description: Mock validation layer
+        extensions:
+          - extension_name: VK_EXT_validation_cache
+            spec_version: 1
+  - properties:
+      api_version: 4194304
+      vendor_id: 65541
+      device_type: Cpu
+      device_name: Mock CPU
+    queue_families:
+      - queue_flags: {bits: 3}
+        queue_count: 1
+"#;
+
+/// A device driving two displays directly. The first display has two modes and is shown by
+/// the first plane; the second plane can show either display but is not in use.
+pub const DISPLAY_CONFIG: &str = r#"
+extensions:
+  - extension_name: VK_KHR_surface
+    spec_version: 25
+  - extension_name: VK_KHR_display
+    spec_version: 23
+physical_devices:
+  - properties:
+      device_name: Mock Display GPU
+    displays:
+      - name: HDMI-A-1
+        physical_dimensions: {width: 600, height: 340}
+        physical_resolution: {width: 3840, height: 2160}
+        supported_transforms: {bits: 1}
+        modes:
+          - {visible_region: {width: 3840, height: 2160}, refresh_rate: 60000}
+          - {visible_region: {width: 1920, height: 1080}, refresh_rate: 59940}
+      - physical_resolution: {width: 800, height: 480}
+        persistent_content: true
+        modes:
+          - {visible_region: {width: 800, height: 480}, refresh_rate: 60000}
+    display_planes:
+      - current_display: 0
+        supported_displays: [0]
+        capabilities:
+          supported_alpha: {bits: 1}
+          max_src_extent: {width: 4096, height: 4096}
+          max_dst_position: {x: 3840, y: 2160}
+          min_dst_extent: {width: 1, height: 1}
+          max_dst_extent: {width: 3840, height: 2160}
+      - current_stack_index: 1
+        supported_displays: [0, 1]
+        capabilities:
+          supported_alpha: {bits: 6}
+"#;
+
+/// A Vulkan 1.3 device whose queue families report global priorities, checkpoint stages and
+/// video codec operations, the second with a flag bit vkinfo does not know.
+pub const QUEUE_FAMILY_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 3, patch: 0}
+physical_devices:
+  - properties:
+      api_version: 4206592
+      device_name: Mock Video GPU
+    extensions:
+      - extension_name: VK_KHR_global_priority
+      - extension_name: VK_NV_device_diagnostic_checkpoints
+      - extension_name: VK_KHR_video_queue
+    queue_families:
+      - queue_flags: {bits: 23}
+        queue_count: 1
+        timestamp_valid_bits: 64
+        min_image_transfer_granularity: {width: 1, height: 1, depth: 1}
+        global_priorities: [Low, Medium, High, Realtime]
+        checkpoint_execution_stage_mask: {bits: 8193}
+      - queue_flags: {bits: 65568}
+        queue_count: 2
+        global_priorities: [Medium]
+        video_codec_operations: {bits: 3}
+"#;
+
+/// A device with a decode and an encode queue family. The AV1 profile is not offered by
+/// any queue family and must not show up.
+pub const VIDEO_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 3, patch: 0}
+physical_devices:
+  - properties:
+      api_version: 4206592
+      device_name: Mock Video GPU
+    extensions:
+      - extension_name: VK_KHR_video_queue
+    queue_families:
+      - queue_flags: {bits: 7}
+        queue_count: 1
+      - queue_flags: {bits: 32}
+        queue_count: 1
+        video_codec_operations: {bits: 3}
+      - queue_flags: {bits: 64}
+        queue_count: 1
+        video_codec_operations: {bits: 65536}
+    video_profiles:
+      - profile: {operation: {bits: 1}, std_profile: 100, chroma_subsampling: {bits: 2}, luma_bit_depth: {bits: 1}, chroma_bit_depth: {bits: 1}}
+        capabilities:
+          capabilities:
+            max_coded_extent: {width: 4096, height: 4096}
+            max_dpb_slots: 17
+            max_active_reference_pictures: 16
+            std_header_version: {extension_name: VK_STD_vulkan_video_codec_h264_decode, spec_version: 4194304}
+          decode: {flags: {bits: 1}}
+          max_level: 15
+        picture_formats: [G8B8r82plane420Unorm]
+        dpb_formats: [G8B8r82plane420Unorm]
+      - profile: {operation: {bits: 2}, std_profile: 2, chroma_subsampling: {bits: 2}, luma_bit_depth: {bits: 4}, chroma_bit_depth: {bits: 4}}
+        capabilities:
+          capabilities:
+            max_coded_extent: {width: 8192, height: 4352}
+            max_dpb_slots: 16
+            max_active_reference_pictures: 15
+          decode: {flags: {bits: 2}}
+          max_level: 186
+        picture_formats: [G10x6B10x6r10x62plane420Unorm3pack16, G8B8r82plane420Unorm]
+        dpb_formats: [G10x6B10x6r10x62plane420Unorm3pack16]
+      - profile: {operation: {bits: 65536}, std_profile: 77, chroma_subsampling: {bits: 2}, luma_bit_depth: {bits: 1}, chroma_bit_depth: {bits: 1}}
+        capabilities:
+          capabilities:
+            max_coded_extent: {width: 1920, height: 1088}
+            max_dpb_slots: 4
+            max_active_reference_pictures: 2
+          encode: {rate_control_modes: {bits: 6}, max_bitrate: 50000000, max_quality_levels: 4}
+          max_level: 11
+        picture_formats: [G8B8r82plane420Unorm]
+        dpb_formats: [G8B8r82plane420Unorm]
+      - profile: {operation: {bits: 4}, std_profile: 0, chroma_subsampling: {bits: 2}, luma_bit_depth: {bits: 1}, chroma_bit_depth: {bits: 1}}
+        capabilities:
+          capabilities:
+            max_coded_extent: {width: 8192, height: 8192}
+"#;
+
+/// A Vulkan 1.1 device that can share memory, semaphores and fences through file descriptors.
+pub const EXTERNAL_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 1, patch: 0}
+physical_devices:
+  - properties:
+      api_version: 4198400
+      device_name: Mock External GPU
+    extensions:
+      - extension_name: VK_EXT_external_memory_dma_buf
+        spec_version: 1
+    id_properties:
+      device_uuid: [0, 17, 34, 51, 68, 85, 102, 119, 136, 153, 170, 187, 204, 221, 238, 255]
+      driver_uuid: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+      device_luid: [1, 0, 0, 0, 0, 0, 0, 0]
+      device_node_mask: 0
+      device_luid_valid: 0
+    image_formats:
+      - format: R8g8b8a8Unorm
+        properties:
+          max_extent: {width: 16384, height: 16384, depth: 1}
+          max_mip_levels: 15
+          max_array_layers: 2048
+          sample_counts: {bits: 1}
+        external_memory:
+          - handle_type: {bits: 1}
+            external_memory_features: {bits: 7}
+            compatible_handle_types: {bits: 1}
+    external_buffers:
+      - handle_type: {bits: 1}
+        external_memory_features: {bits: 6}
+        compatible_handle_types: {bits: 1}
+      - handle_type: {bits: 512}
+        external_memory_features: {bits: 6}
+        export_from_imported_handle_types: {bits: 512}
+        compatible_handle_types: {bits: 512}
+    external_semaphores:
+      - handle_type: {bits: 1}
+        external_semaphore_features: {bits: 3}
+        compatible_handle_types: {bits: 1}
+      - handle_type: {bits: 16}
+        external_semaphore_features: {bits: 1}
+        compatible_handle_types: {bits: 16}
+    external_fences:
+      - handle_type: {bits: 8}
+        external_fence_features: {bits: 3}
+        compatible_handle_types: {bits: 8}
+"#;
+
+/// A Vulkan 1.3 device and a 1.2 device with VK_EXT_tooling_info that only reports its tools
+/// once a device has the extension enabled. RenderDoc's layer is not an instance layer.
+pub const TOOLING_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 3, patch: 0}
+layers:
+  - properties:
+      layer_name: VK_LAYER_KHRONOS_validation
+      description: Khronos validation layer
+physical_devices:
+  - properties:
+      api_version: 4206592
+      device_name: Mock 1.3 GPU
+    tools:
+      - name: Khronos Validation Layer
+        version: "1.3.280"
+        purposes: {bits: 33}
+        description: LunarG validation layer
+        layer: VK_LAYER_KHRONOS_validation
+      - name: Driver Profiler
+        version: "2"
+        purposes: {bits: 2}
+        description: Built into the driver
+  - properties:
+      api_version: 4202496
+      device_name: Mock 1.2 GPU
+    extensions:
+      - extension_name: VK_EXT_tooling_info
+    queue_families:
+      - queue_flags: {bits: 1}
+        queue_count: 1
+    tools:
+      - name: RenderDoc
+        version: "1.31"
+        purposes: {bits: 4}
+        description: Frame capture
+        layer: VK_LAYER_RENDERDOC_Capture
+    tools_need_device: true
+"#;
+
+/// Three devices of which the first and the last are linked.
+pub const DEVICE_GROUP_CONFIG: &str = r#"
+instance_version: {major: 1, minor: 1, patch: 0}
+physical_devices:
+  - properties:
+      device_name: Linked GPU A
+  - properties:
+      device_name: Single GPU
+  - properties:
+      device_name: Linked GPU B
+device_groups:
+  - physical_devices: [0, 2]
+    subset_allocation: true
+  - physical_devices: [1]
+"#;
+
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Path of the mock driver built alongside the tests.
+pub fn mock_icd_path() -> PathBuf {
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    deps.join(format!("{}vkinfo_mock_icd{}", DLL_PREFIX, DLL_SUFFIX))
+}
+
+/// Writes a mock driver configuration (or a saved report) and returns its path.
+pub fn write_config(name: &str, yaml: &str) -> PathBuf {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, yaml).unwrap();
+    path
+}
+
+/// Points the in-process mock driver at `yaml` until the returned guard is dropped.
+pub fn use_config(name: &str, yaml: &str) -> MutexGuard<'static, ()> {
+    let guard = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    env::set_var(CONFIG_ENV, write_config(name, yaml));
+    guard
+}
+
+/// A `vkinfo` command that loads the mock driver configured by `yaml`.
+pub fn vkinfo(name: &str, yaml: &str) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_vkinfo"));
+    command.arg("--loader").arg(mock_icd_path())
+        .env(CONFIG_ENV, write_config(name, yaml))
+        .env_remove("VKINFO_LOADER");
+    command
+}
+
+/// A sysfs tree with one GPU at PCI address 0000:03:00.0, bound to `amdgpu`, whose nodes are
+/// card1 (226:1) and renderD128 (226:128).
+#[cfg(unix)]
+pub fn sysfs_fixture(name: &str) -> PathBuf {
+    use std::os::unix::fs::symlink;
+
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&root);
+    let device = root.join("devices/pci0000:00/0000:03:00.0");
+    fs::create_dir_all(device.join("drm/card1")).unwrap();
+    fs::create_dir_all(device.join("drm/renderD128")).unwrap();
+    fs::create_dir_all(root.join("bus/pci/devices")).unwrap();
+    fs::create_dir_all(root.join("bus/pci/drivers/amdgpu")).unwrap();
+    symlink("../../../devices/pci0000:00/0000:03:00.0", root.join("bus/pci/devices/0000:03:00.0")).unwrap();
+    symlink("../../../../bus/pci/drivers/amdgpu", device.join("driver")).unwrap();
+    for &(node, dev) in &[("card1", "226:1"), ("renderD128", "226:128")] {
+        fs::create_dir_all(root.join("class/drm")).unwrap();
+        fs::write(device.join("drm").join(node).join("dev"), format!("{}\n", dev)).unwrap();
+        symlink(format!("../../devices/pci0000:00/0000:03:00.0/drm/{}", node), root.join("class/drm").join(node)).unwrap();
+        symlink("../../../0000:03:00.0", device.join("drm").join(node).join("device")).unwrap();
+    }
+    root
+}
+
+/// A fresh file for the mock driver's `call_log`.
+pub fn call_log(name: &str) -> PathBuf {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// The commands recorded in a `call_log`, one per line.
+pub fn logged_calls(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
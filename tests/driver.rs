@@ -0,0 +1,36 @@
+extern crate vkinfo;
+
+use vkinfo::driver::{decode_driver_version, describe_driver_version};
+use vkinfo::vk;
+
+const NVIDIA: u32 = 0x10de;
+const INTEL: u32 = 0x8086;
+const AMD: u32 = 0x1002;
+
+#[test]
+fn nvidia_versions_are_10_8_8_6_bits() {
+    let version = (550 << 22) | (54 << 14) | (14 << 6);
+    assert_eq!(describe_driver_version(NVIDIA, Some(vk::DriverId::NvidiaProprietary), version), "NVIDIA 550.54.14");
+    assert_eq!(decode_driver_version(NVIDIA, None, version | 3), "550.54.14.3");
+}
+
+#[test]
+fn intel_windows_versions_are_18_14_bits() {
+    let version = (101 << 14) | 5333;
+    assert_eq!(describe_driver_version(INTEL, Some(vk::DriverId::IntelProprietaryWindows), version), "Intel 101.5333");
+}
+
+#[test]
+fn other_drivers_use_vulkan_versions() {
+    let version = vk::make_api_version(0, 24, 2, 3);
+    assert_eq!(describe_driver_version(AMD, Some(vk::DriverId::MesaRadv), version), "Mesa 24.2.3");
+    assert_eq!(describe_driver_version(INTEL, Some(vk::DriverId::IntelOpenSourceMesa), version), "Mesa 24.2.3");
+    assert_eq!(describe_driver_version(AMD, None, version), "AMD 24.2.3");
+    assert_eq!(describe_driver_version(0x1234, None, version), "24.2.3");
+}
+
+#[test]
+fn unknown_driver_ids_fall_back_to_the_vendor() {
+    let version = (550 << 22) | (54 << 14) | (14 << 6);
+    assert_eq!(describe_driver_version(NVIDIA, Some(vk::DriverId::Unknown(1000)), version), "NVIDIA 550.54.14");
+}
@@ -0,0 +1,752 @@
+extern crate serde_yaml;
+extern crate vkinfo;
+extern crate vkinfo_mock_icd;
+
+mod common;
+
+use vkinfo::video;
+use vkinfo::vk;
+use vkinfo::vulkan::{Entry, Error, HeapBudget, InstanceBuilder, VideoProfile};
+use vkinfo_mock_icd::config::CreateInfo;
+
+#[test]
+fn loads_explicit_loader() {
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    assert_eq!(entry.loader_path(), common::mock_icd_path().as_path());
+    assert!(entry.loader_failures().is_empty());
+}
+
+#[test]
+fn missing_loader_names_the_path() {
+    let err = Entry::with_loader("/nonexistent/libvulkan.so.1").err().unwrap();
+
+    match err {
+        Error::LoaderNotFound(ref failures) => assert_eq!(failures[0].path.to_str(), Some("/nonexistent/libvulkan.so.1")),
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert!(err.to_string().starts_with("no Vulkan loader found (/nonexistent/libvulkan.so.1: "), "{}", err);
+}
+
+#[test]
+fn enumerates_layers_and_their_extensions() {
+    let _config = common::use_config("entry_layers.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let extensions = entry.enumerate_instance_extension_properties(None).unwrap();
+    assert_eq!(extensions.len(), 2);
+    assert_eq!(extensions[0].extension_name.to_string_lossy(), "VK_KHR_surface");
+    assert_eq!(extensions[0].spec_version, 25);
+    assert_eq!(extensions[1].extension_name.to_string_lossy(), "VK_EXT_headless_surface");
+
+    let layers = entry.enumerate_instance_layer_properties().unwrap();
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].layer_name.to_string_lossy(), "VK_LAYER_MOCK_validation");
+    assert_eq!(layers[0].description.to_string_lossy(), "Mock validation layer");
+
+    let layer_extensions = entry.enumerate_instance_extension_properties(Some("VK_LAYER_MOCK_validation")).unwrap();
+    assert_eq!(layer_extensions.len(), 1);
+    assert_eq!(layer_extensions[0].extension_name.to_string_lossy(), "VK_EXT_debug_utils");
+}
+
+#[test]
+fn unknown_layer_is_reported() {
+    let _config = common::use_config("entry_unknown_layer.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let err = entry.enumerate_instance_extension_properties(Some("VK_LAYER_missing")).err().unwrap();
+    assert_eq!(err.vk_result(), Some(vk::Result::ErrorLayerNotPresent));
+    assert_eq!(err.to_string(), "vkEnumerateInstanceExtensionProperties failed: ErrorLayerNotPresent");
+}
+
+#[test]
+fn queries_physical_devices() {
+    let _config = common::use_config("entry_devices.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+    assert_eq!(physical_devices.len(), 2);
+
+    let properties = instance.get_physical_device_properties(physical_devices[0]);
+    assert_eq!(properties.device_name.to_string_lossy(), "Mock Discrete GPU");
+    assert_eq!(properties.device_type, vk::PhysicalDeviceType::DiscreteGpu);
+    assert_eq!(properties.vendor_id, 4098);
+    assert_eq!(properties.limit.max_image_dimension_2d, 16384);
+
+    let features = instance.get_physical_device_features(physical_devices[0]);
+    assert_eq!(features.geometry_shader, 1);
+    assert_eq!(features.tessellation_shader, 0);
+
+    let queue_families = instance.get_physical_device_queue_family_properties(physical_devices[0]);
+    assert_eq!(queue_families.len(), 2);
+    assert_eq!(queue_families[0].queue_flags, vk::QueueFlags::GraphicsBit | vk::QueueFlags::ComputeBit | vk::QueueFlags::TransferBit);
+    assert_eq!(queue_families[0].queue_count, 4);
+
+    let properties = instance.get_physical_device_properties(physical_devices[1]);
+    assert_eq!(properties.device_type, vk::PhysicalDeviceType::Cpu);
+}
+
+#[test]
+fn queries_features_and_properties_of_newer_versions() {
+    let _config = common::use_config("entry_features2.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_4).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    // The instance is limited to the loader's 1.3, and so is the device.
+    let features = instance.get_physical_device_features2(physical_devices[0]);
+    assert_eq!(features.features.geometry_shader, 1);
+    assert_eq!(features.vulkan_11.as_ref().unwrap().multiview, 1);
+    assert_eq!(features.vulkan_12.as_ref().unwrap().timeline_semaphore, 1);
+    assert_eq!(features.vulkan_13.as_ref().unwrap().dynamic_rendering, 1);
+    assert!(features.vulkan_14.is_none());
+
+    let properties = instance.get_physical_device_properties2(physical_devices[0]);
+    assert_eq!(properties.properties.device_name.to_string_lossy(), "Mock Discrete GPU");
+    let vulkan_11 = properties.vulkan_11.as_ref().unwrap();
+    assert_eq!(vulkan_11.subgroup_size, 32);
+    assert_eq!(vulkan_11.point_clipping_behavior, vk::PointClippingBehavior::UserClipPlanesOnly);
+    assert!(vulkan_11.p_next.is_null());
+    assert_eq!(properties.vulkan_12.as_ref().unwrap().driver_id, vk::DriverId::MesaRadv);
+    let driver = properties.driver.as_ref().unwrap();
+    assert_eq!(driver.driver_id, vk::DriverId::MesaRadv);
+    assert_eq!(driver.driver_name.to_string_lossy(), "radv");
+    assert_eq!(driver.conformance_version.to_string(), "1.3.8.0");
+    assert_eq!(properties.vulkan_13.as_ref().unwrap().max_inline_uniform_block_size, 4096);
+    assert!(properties.vulkan_14.is_none());
+
+    // The 1.0 device gets none of them.
+    let features = instance.get_physical_device_features2(physical_devices[1]);
+    assert!(features.vulkan_11.is_none());
+    let properties = instance.get_physical_device_properties2(physical_devices[1]);
+    assert_eq!(properties.properties.device_name.to_string_lossy(), "Mock CPU");
+    assert!(properties.vulkan_11.is_none());
+    assert!(properties.driver.is_none());
+}
+
+#[test]
+fn queries_driver_properties_through_the_extension_before_1_2() {
+    let _config = common::use_config("entry_driver_properties.yaml", r#"
+instance_version: {major: 1, minor: 3, patch: 250}
+physical_devices:
+  - properties:
+      api_version: 4198400
+      vendor_id: 4318
+      device_name: Mock 1.1 GPU
+    extensions:
+      - extension_name: VK_KHR_driver_properties
+        spec_version: 1
+    driver_properties:
+      driver_id: NvidiaProprietary
+      driver_name: NVIDIA
+      driver_info: "550.54.14"
+      conformance_version: {major: 1, minor: 3, subminor: 7, patch: 2}
+  - properties:
+      api_version: 4198400
+      device_name: Mock 1.1 GPU without the extension
+    driver_properties:
+      driver_id: MesaLlvmpipe
+"#);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_3).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let properties = instance.get_physical_device_properties2(physical_devices[0]);
+    assert!(properties.vulkan_12.is_none());
+    let driver = properties.driver.unwrap();
+    assert_eq!(driver.driver_id, vk::DriverId::NvidiaProprietary);
+    assert_eq!(driver.driver_info.to_string_lossy(), "550.54.14");
+    assert!(driver.p_next.is_null());
+
+    assert!(instance.get_physical_device_properties2(physical_devices[1]).driver.is_none());
+}
+
+#[test]
+fn newer_versions_need_the_instance_to_ask_for_them() {
+    let _config = common::use_config("entry_features2_1_1.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let features = instance.get_physical_device_features2(physical_devices[0]);
+    assert_eq!(features.features.geometry_shader, 1);
+    assert!(features.vulkan_12.is_none());
+    assert!(instance.get_physical_device_properties2(physical_devices[0]).vulkan_12.is_none());
+}
+
+#[test]
+fn gathers_vulkan_1_1_from_its_own_structures_before_1_2() {
+    let _config = common::use_config("entry_features2_separate_1_1.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    // The combined 1.1 structures were only added in 1.2.
+    let features = instance.get_physical_device_features2(physical_devices[0]).vulkan_11.unwrap();
+    assert_eq!(features.multiview, 1);
+    assert_eq!(features.shader_draw_parameters, 1);
+    assert_eq!(features.storage_buffer16_bit_access, 0);
+    assert!(features.p_next.is_null());
+
+    let properties = instance.get_physical_device_properties2(physical_devices[0]);
+    let vulkan_11 = properties.vulkan_11.as_ref().unwrap();
+    assert_eq!(vulkan_11.subgroup_size, 32);
+    assert_eq!(vulkan_11.subgroup_supported_operations, vk::SubgroupFeatureFlags::from_bits_truncate(255));
+    assert_eq!(vulkan_11.point_clipping_behavior, vk::PointClippingBehavior::UserClipPlanesOnly);
+    assert_eq!(vulkan_11.max_memory_allocation_size, 4294967296);
+    assert_eq!(vulkan_11.device_uuid, properties.id.as_ref().unwrap().device_uuid);
+    assert!(vulkan_11.p_next.is_null());
+
+    // The 1.0 device still gets none.
+    assert!(instance.get_physical_device_features2(physical_devices[1]).vulkan_11.is_none());
+}
+
+#[test]
+fn queries_vulkan_1_4_copy_layouts() {
+    let _config = common::use_config("entry_features2_1_4.yaml", r#"
+instance_version: {major: 1, minor: 4, patch: 300}
+physical_devices:
+  - properties:
+      api_version: 4210688
+      device_name: Mock 1.4 GPU
+    vulkan_14_features:
+      maintenance5: 1
+    vulkan_14_properties:
+      max_push_descriptors: 32
+    copy_src_layouts: [General, TransferSrcOptimal]
+    copy_dst_layouts: [General]
+"#);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_4).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    assert_eq!(instance.get_physical_device_features2(physical_devices[0]).vulkan_14.unwrap().maintenance5, 1);
+
+    let properties = instance.get_physical_device_properties2(physical_devices[0]);
+    let vulkan_14 = properties.vulkan_14.as_ref().unwrap();
+    assert_eq!(vulkan_14.max_push_descriptors, 32);
+    assert_eq!(vulkan_14.copy_src_layout_count, 2);
+    assert!(vulkan_14.p_copy_src_layouts.is_null());
+    assert_eq!(properties.copy_src_layouts, [vk::ImageLayout::General, vk::ImageLayout::TransferSrcOptimal]);
+    assert_eq!(properties.copy_dst_layouts, [vk::ImageLayout::General]);
+}
+
+#[test]
+fn enumerates_physical_device_groups() {
+    let config = format!("{}count_drift: {{vkEnumeratePhysicalDeviceGroups: -1}}\n", common::DEVICE_GROUP_CONFIG);
+    let _config = common::use_config("entry_device_groups.yaml", &config);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let groups = instance.enumerate_physical_device_groups().unwrap();
+    assert_eq!(groups.len(), 2);
+    let handles: Vec<_> = groups[0].physical_devices.iter().map(|physical_device| physical_device.handle()).collect();
+    assert_eq!(handles, [physical_devices[0].handle(), physical_devices[2].handle()]);
+    assert!(groups[0].subset_allocation);
+    assert_eq!(groups[1].physical_devices[0].handle(), physical_devices[1].handle());
+    assert!(!groups[1].subset_allocation);
+}
+
+#[test]
+fn physical_devices_are_their_own_groups_before_1_1() {
+    let _config = common::use_config("entry_device_groups_1_0.yaml", common::DEVICE_GROUP_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+
+    let groups = instance.enumerate_physical_device_groups().unwrap();
+    assert_eq!(groups.len(), 3);
+    assert!(groups.iter().all(|group| group.physical_devices.len() == 1 && !group.subset_allocation));
+}
+
+#[test]
+fn queries_queue_family_additions() {
+    let _config = common::use_config("entry_queue_families.yaml", common::QUEUE_FAMILY_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_3).build(&entry).unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+
+    let queue_families = instance.get_physical_device_queue_family_properties2(physical_device);
+    assert_eq!(queue_families.len(), 2);
+    assert_eq!(queue_families[0].properties.queue_flags, vk::QueueFlags::GraphicsBit | vk::QueueFlags::ComputeBit | vk::QueueFlags::TransferBit | vk::QueueFlags::ProtectedBit);
+    assert_eq!(queue_families[0].global_priorities.as_deref(), Some(&[vk::QueueGlobalPriorityKhr::Low, vk::QueueGlobalPriorityKhr::Medium, vk::QueueGlobalPriorityKhr::High, vk::QueueGlobalPriorityKhr::Realtime][..]));
+    assert_eq!(queue_families[0].checkpoint_execution_stage_mask, Some(vk::PipelineStageFlags::TopOfPipeBit | vk::PipelineStageFlags::BottomOfPipeBit));
+    assert_eq!(queue_families[0].video_codec_operations, Some(vk::VideoCodecOperationFlagsKhr::empty()));
+    assert_eq!(queue_families[1].properties.queue_flags.bits(), 0x10020);
+    assert_eq!(queue_families[1].properties.queue_count, 2);
+    assert_eq!(queue_families[1].video_codec_operations, Some(vk::VideoCodecOperationFlagsKhr::DecodeH264Bit | vk::VideoCodecOperationFlagsKhr::DecodeH265Bit));
+    drop(instance);
+
+    // A 1.0 instance only gets the core properties.
+    let instance = entry.create_instance().unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+    let queue_families = instance.get_physical_device_queue_family_properties2(physical_device);
+    assert_eq!(queue_families[1].properties.queue_count, 2);
+    assert!(queue_families.iter().all(|family| family.global_priorities.is_none() && family.checkpoint_execution_stage_mask.is_none() && family.video_codec_operations.is_none()));
+}
+
+#[test]
+fn queries_memory_properties() {
+    let _config = common::use_config("entry_memory.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let memory_properties = instance.get_physical_device_memory_properties(physical_devices[0]);
+    let heaps = memory_properties.memory_heaps();
+    assert_eq!(heaps.len(), 2);
+    assert_eq!(heaps[0].size, 8 << 30);
+    assert_eq!(heaps[0].flags, vk::MemoryHeapFlags::DeviceLocalBit);
+    assert_eq!(heaps[1].flags, vk::MemoryHeapFlags::empty());
+
+    let types = memory_properties.memory_types();
+    assert_eq!(types.len(), 3);
+    assert_eq!(types[1].property_flags, vk::MemoryPropertyFlags::HostVisibleBit | vk::MemoryPropertyFlags::HostCoherentBit);
+    assert_eq!(types[1].heap_index, 1);
+
+    assert!(instance.get_physical_device_memory_properties(physical_devices[1]).memory_heaps().is_empty());
+}
+
+#[test]
+fn queries_memory_budget() {
+    let _config = common::use_config("entry_memory_budget.yaml", r#"
+instance_version: {major: 1, minor: 1, patch: 0}
+physical_devices:
+  - properties:
+      api_version: 4198400
+    extensions:
+      - extension_name: VK_EXT_memory_budget
+    memory_heaps:
+      - size: 4096
+    memory_budgets:
+      - {heap_usage: [1024], heap_budget: [3072]}
+      - {heap_usage: [2048], heap_budget: [2048]}
+  - properties:
+      api_version: 4198400
+    memory_heaps:
+      - size: 4096
+"#);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let budget = |usage, budget| vec![HeapBudget { size: 4096, usage, budget }];
+    assert_eq!(instance.get_physical_device_memory_budget(physical_devices[0]), Some(budget(1024, 3072)));
+    assert_eq!(instance.get_physical_device_memory_budget(physical_devices[0]), Some(budget(2048, 2048)));
+    assert_eq!(instance.get_physical_device_memory_budget(physical_devices[0]), Some(budget(2048, 2048)));
+    assert_eq!(instance.get_physical_device_memory_budget(physical_devices[1]), None);
+    drop(instance);
+
+    // vkGetPhysicalDeviceMemoryProperties2 is not available to 1.0 instances.
+    let instance = entry.create_instance().unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+    assert_eq!(instance.get_physical_device_memory_budget(physical_devices[0]), None);
+}
+
+#[test]
+fn queries_headless_surface() {
+    let log = common::call_log("entry_surface.log");
+    let _config = common::use_config("entry_surface.yaml", &format!("{}\ncall_log: {}\n", common::STANDARD_CONFIG, log.display()));
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().extension("VK_KHR_surface").extension("VK_EXT_headless_surface").build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+    assert!(instance.is_extension_enabled("VK_EXT_headless_surface"));
+
+    let surface = instance.create_headless_surface().unwrap();
+    let capabilities = instance.get_physical_device_surface_capabilities(physical_devices[0], &surface).unwrap();
+    assert_eq!(capabilities.min_image_count, 2);
+    assert_eq!(capabilities.max_image_extent.width, 16384);
+    assert_eq!(capabilities.supported_composite_alpha, vk::CompositeAlphaFlagsKhr::OpaqueBit);
+    let formats = instance.get_physical_device_surface_formats(physical_devices[0], &surface).unwrap();
+    assert_eq!(formats.iter().map(|format| format.format.get()).collect::<Vec<_>>(), [vk::Format::B8g8r8a8Srgb, vk::Format::R8g8b8a8Unorm]);
+    assert_eq!(formats[0].color_space, vk::ColorSpaceKhr::SrgbNonlinear);
+    assert_eq!(instance.get_physical_device_surface_present_modes(physical_devices[0], &surface).unwrap(), [vk::PresentModeKhr::Fifo, vk::PresentModeKhr::Mailbox, vk::PresentModeKhr::Immediate]);
+    assert!(instance.get_physical_device_surface_support(physical_devices[0], 0, &surface).unwrap());
+    assert!(!instance.get_physical_device_surface_support(physical_devices[0], 1, &surface).unwrap());
+    assert!(instance.get_physical_device_surface_formats(physical_devices[1], &surface).unwrap().is_empty());
+
+    drop(surface);
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkCreateHeadlessSurfaceEXT\nvkDestroySurfaceKHR\n");
+}
+
+#[test]
+fn headless_surface_needs_the_extensions() {
+    let _config = common::use_config("entry_surface_missing.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().extension("VK_KHR_surface").build(&entry).unwrap();
+
+    assert!(!instance.is_extension_enabled("VK_EXT_headless_surface"));
+    let err = instance.create_headless_surface().err().unwrap();
+    assert_eq!(err.to_string(), "instance extension VK_EXT_headless_surface is not available");
+}
+
+#[test]
+fn queries_displays_and_planes() {
+    let _config = common::use_config("entry_displays.yaml", common::DISPLAY_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().extension("VK_KHR_surface").extension("VK_KHR_display").build(&entry).unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+
+    let displays = instance.get_physical_device_display_properties(physical_device).unwrap();
+    assert_eq!(displays.len(), 2);
+    assert_eq!(displays[0].display_name.as_deref(), Some("HDMI-A-1"));
+    assert_eq!(displays[0].physical_dimensions.width, 600);
+    assert_eq!(displays[1].display_name, None);
+    assert!(displays[1].persistent_content);
+
+    let modes = instance.get_display_mode_properties(physical_device, displays[0].display).unwrap();
+    assert_eq!(modes.iter().map(|mode| mode.parameters.refresh_rate).collect::<Vec<_>>(), [60000, 59940]);
+
+    let planes = instance.get_physical_device_display_plane_properties(physical_device).unwrap();
+    assert_eq!(planes[0].current_display, displays[0].display);
+    assert_eq!(planes[1].current_display, vk::NULL_HANDLE);
+    assert_eq!(planes[1].current_stack_index, 1);
+    assert_eq!(instance.get_display_plane_supported_displays(physical_device, 1).unwrap(), [displays[0].display, displays[1].display]);
+
+    let capabilities = instance.get_display_plane_capabilities(physical_device, modes[0].display_mode, 0).unwrap();
+    assert_eq!(capabilities.supported_alpha, vk::DisplayPlaneAlphaFlagsKhr::OpaqueBit);
+    assert_eq!(capabilities.max_dst_extent.width, 3840);
+}
+
+#[test]
+fn queries_video_profiles() {
+    let _config = common::use_config("entry_video.yaml", common::VIDEO_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_3).build(&entry).unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+
+    let profiles = video::standard_profiles(vk::VideoCodecOperationFlagsKhr::DecodeH264Bit | vk::VideoCodecOperationFlagsKhr::DecodeH265Bit);
+    assert_eq!(profiles.len(), (4 + 5) * 4 * 3);
+    let high = profiles.iter().find(|profile| profile.std_profile == 100 && profile.chroma_subsampling == vk::VideoChromaSubsamplingFlagsKhr::Type420Bit && profile.luma_bit_depth == vk::VideoComponentBitDepthFlagsKhr::Depth8Bit).unwrap();
+    assert!(profiles.iter().filter(|profile| profile.chroma_subsampling == vk::VideoChromaSubsamplingFlagsKhr::MonochromeBit).all(|profile| profile.chroma_bit_depth.is_empty()));
+
+    let capabilities = instance.get_physical_device_video_capabilities(physical_device, high).unwrap().unwrap();
+    assert_eq!(capabilities.capabilities.max_coded_extent.width, 4096);
+    assert_eq!(capabilities.capabilities.max_dpb_slots, 17);
+    assert_eq!(capabilities.capabilities.std_header_version.extension_name.to_string_lossy(), "VK_STD_vulkan_video_codec_h264_decode");
+    assert_eq!(capabilities.decode.unwrap().flags, vk::VideoDecodeCapabilityFlagsKhr::DpbAndOutputCoincideBit);
+    assert!(capabilities.encode.is_none());
+    assert_eq!(capabilities.max_level, 15);
+
+    let formats = instance.get_physical_device_video_format_properties(physical_device, high, vk::ImageUsageFlags::VideoDecodeDstBitKhr).unwrap();
+    assert_eq!(formats.iter().map(|properties| properties.format.get()).collect::<Vec<_>>(), [vk::Format::G8B8r82plane420Unorm]);
+
+    let baseline = VideoProfile { std_profile: 66, ..*high };
+    assert!(instance.get_physical_device_video_capabilities(physical_device, &baseline).unwrap().is_none());
+    assert!(instance.get_physical_device_video_format_properties(physical_device, &baseline, vk::ImageUsageFlags::VideoDecodeDstBitKhr).unwrap().is_empty());
+
+    let encode = VideoProfile { operation: vk::VideoCodecOperationFlagsKhr::EncodeH264Bit, std_profile: 77, ..*high };
+    let capabilities = instance.get_physical_device_video_capabilities(physical_device, &encode).unwrap().unwrap();
+    assert_eq!(capabilities.encode.unwrap().max_bitrate, 50000000);
+    assert_eq!(capabilities.max_level, 11);
+}
+
+#[test]
+fn queries_external_handle_properties() {
+    let _config = common::use_config("entry_external.yaml", common::EXTERNAL_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+
+    let id = instance.get_physical_device_properties2(physical_device).id.unwrap();
+    assert_eq!(id.device_uuid[1], 0x11);
+    assert_eq!(id.driver_uuid[15], 16);
+    assert_eq!(id.device_luid_valid, 0);
+
+    let usage = vk::BufferUsageFlags::TransferSrcBit | vk::BufferUsageFlags::TransferDstBit;
+    let buffer = instance.get_physical_device_external_buffer_properties(physical_device, vk::BufferCreateFlags::empty(), usage, vk::ExternalMemoryHandleTypeFlags::DmaBufBitExt).unwrap();
+    assert_eq!(buffer.external_memory_features, vk::ExternalMemoryFeatureFlags::ExportableBit | vk::ExternalMemoryFeatureFlags::ImportableBit);
+    assert_eq!(buffer.export_from_imported_handle_types, vk::ExternalMemoryHandleTypeFlags::DmaBufBitExt);
+    let buffer = instance.get_physical_device_external_buffer_properties(physical_device, vk::BufferCreateFlags::empty(), usage, vk::ExternalMemoryHandleTypeFlags::OpaqueWin32Bit).unwrap();
+    assert!(buffer.external_memory_features.is_empty());
+
+    let image = |handle_type| instance.get_physical_device_external_image_format_properties(physical_device, vk::Format::R8g8b8a8Unorm, vk::ImageType::Type2d, vk::ImageTiling::Optimal, vk::ImageUsageFlags::SampledBit, vk::ImageCreateFlags::empty(), handle_type).unwrap();
+    assert_eq!(image(vk::ExternalMemoryHandleTypeFlags::OpaqueFdBit).unwrap().external_memory_features, vk::ExternalMemoryFeatureFlags::all());
+    assert!(image(vk::ExternalMemoryHandleTypeFlags::DmaBufBitExt).is_none());
+
+    let semaphore = instance.get_physical_device_external_semaphore_properties(physical_device, vk::ExternalSemaphoreHandleTypeFlags::SyncFdBit).unwrap();
+    assert_eq!(semaphore.external_semaphore_features, vk::ExternalSemaphoreFeatureFlags::ExportableBit);
+    assert_eq!(semaphore.compatible_handle_types, vk::ExternalSemaphoreHandleTypeFlags::SyncFdBit);
+
+    let fence = instance.get_physical_device_external_fence_properties(physical_device, vk::ExternalFenceHandleTypeFlags::SyncFdBit).unwrap();
+    assert_eq!(fence.external_fence_features, vk::ExternalFenceFeatureFlags::all());
+    let fence = instance.get_physical_device_external_fence_properties(physical_device, vk::ExternalFenceHandleTypeFlags::OpaqueFdBit).unwrap();
+    assert!(fence.external_fence_features.is_empty());
+}
+
+#[test]
+fn queries_tool_properties() {
+    let log = common::call_log("entry_tooling.log");
+    let _config = common::use_config("entry_tooling.yaml", &format!("{}\ncall_log: {}\n", common::TOOLING_CONFIG, log.display()));
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_3).build(&entry).unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let tools = instance.get_physical_device_tool_properties(physical_devices[0]).unwrap();
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools[0].name.to_string_lossy(), "Khronos Validation Layer");
+    assert_eq!(tools[0].purposes, vk::ToolPurposeFlags::ValidationBit | vk::ToolPurposeFlags::DebugReportingBitExt);
+    assert_eq!(tools[0].layer.to_string_lossy(), "VK_LAYER_KHRONOS_validation");
+    assert_eq!(tools[1].layer.to_string_lossy(), "");
+
+    assert!(instance.get_physical_device_tool_properties(physical_devices[1]).unwrap().is_empty());
+    let device = instance.create_device(physical_devices[1], &["VK_EXT_tooling_info"]).unwrap();
+    assert_eq!(instance.get_physical_device_tool_properties(physical_devices[1]).unwrap()[0].version.to_string_lossy(), "1.31");
+    drop(device);
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkCreateDevice\nvkDestroyDevice\n");
+
+    let error = instance.create_device(physical_devices[0], &["VK_EXT_tooling_info"]).err().unwrap();
+    assert_eq!(error.vk_result(), Some(vk::Result::ErrorExtensionNotPresent));
+}
+
+#[test]
+fn queries_format_properties() {
+    let _config = common::use_config("entry_formats.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let properties = instance.get_physical_device_format_properties(physical_devices[0], vk::Format::D32Sfloat);
+    assert_eq!(properties.optimal_tiling_features, vk::FormatFeatureFlags::SampledImageBit | vk::FormatFeatureFlags::DepthStencilAttachmentBit | vk::FormatFeatureFlags::BlitSrcBit);
+    assert!(properties.linear_tiling_features.is_empty());
+
+    let properties = instance.get_physical_device_format_properties(physical_devices[0], vk::Format::Bc7UnormBlock);
+    assert!(properties.optimal_tiling_features.is_empty());
+}
+
+#[test]
+fn queries_image_format_properties() {
+    let _config = common::use_config("entry_image_formats.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+    let query = |tiling, usage| {
+        instance.get_physical_device_image_format_properties(physical_device, vk::Format::R8g8b8a8Unorm, vk::ImageType::Type2d, tiling, usage, vk::ImageCreateFlags::empty())
+    };
+
+    let properties = query(vk::ImageTiling::Optimal, vk::ImageUsageFlags::SampledBit | vk::ImageUsageFlags::StorageBit).unwrap().unwrap();
+    assert_eq!(properties.max_extent.width, 16384);
+    assert_eq!(properties.max_mip_levels, 15);
+    assert_eq!(properties.sample_counts, vk::SampleCountFlags::Count1Bit | vk::SampleCountFlags::Count4Bit);
+
+    assert!(query(vk::ImageTiling::Linear, vk::ImageUsageFlags::SampledBit).unwrap().is_none());
+    assert!(query(vk::ImageTiling::Optimal, vk::ImageUsageFlags::InputAttachmentBit).unwrap().is_none());
+}
+
+#[test]
+fn queries_sparse_image_format_properties() {
+    let _config = common::use_config("entry_sparse_formats.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+    let query = |format, samples| {
+        instance.get_physical_device_sparse_image_format_properties(physical_device, format, vk::ImageType::Type2d, samples, vk::ImageUsageFlags::SampledBit, vk::ImageTiling::Optimal)
+    };
+
+    let properties = query(vk::Format::R8g8b8a8Unorm, vk::SampleCountFlags::Count4Bit);
+    assert_eq!(properties.len(), 1);
+    assert_eq!(properties[0].aspect_mask, vk::ImageAspectFlags::ColorBit);
+    assert_eq!(properties[0].image_granularity.width, 64);
+    assert_eq!(properties[0].flags, vk::SparseImageFormatFlags::SingleMiptailBit);
+
+    assert!(query(vk::Format::R8g8b8a8Unorm, vk::SampleCountFlags::Count2Bit).is_empty());
+    assert!(query(vk::Format::D32Sfloat, vk::SampleCountFlags::Count1Bit).is_empty());
+}
+
+#[test]
+fn image_format_errors_other_than_unsupported_fail() {
+    let config = format!("{}\nfailures: {{vkGetPhysicalDeviceImageFormatProperties: ErrorOutOfHostMemory}}\n", common::STANDARD_CONFIG);
+    let _config = common::use_config("entry_image_formats_fail.yaml", &config);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+
+    let err = instance.get_physical_device_image_format_properties(physical_device, vk::Format::R8g8b8a8Unorm, vk::ImageType::Type2d, vk::ImageTiling::Optimal, vk::ImageUsageFlags::SampledBit, vk::ImageCreateFlags::empty()).err().unwrap();
+    assert_eq!(err.vk_result(), Some(vk::Result::ErrorOutOfHostMemory));
+}
+
+#[test]
+fn enumerates_device_extensions_and_layers() {
+    let _config = common::use_config("entry_device_extensions.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+    let physical_devices = instance.enumerate_physical_devices().unwrap();
+
+    let extensions = instance.enumerate_device_extension_properties(physical_devices[0], None).unwrap();
+    let names: Vec<String> = extensions.iter().map(|extension| extension.extension_name.to_string_lossy()).collect();
+    assert_eq!(names, ["VK_KHR_swapchain", "VK_KHR_maintenance1"]);
+    assert_eq!(extensions[0].spec_version, 70);
+    assert!(instance.has_device_extension(physical_devices[0], "VK_KHR_maintenance1"));
+    assert!(!instance.has_device_extension(physical_devices[0], "VK_EXT_memory_budget"));
+
+    let layers = instance.enumerate_device_layer_properties(physical_devices[0]).unwrap();
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].layer_name.to_string_lossy(), "VK_LAYER_MOCK_validation");
+
+    let layer_extensions = instance.enumerate_device_extension_properties(physical_devices[0], Some("VK_LAYER_MOCK_validation")).unwrap();
+    assert_eq!(layer_extensions[0].extension_name.to_string_lossy(), "VK_EXT_validation_cache");
+
+    let err = instance.enumerate_device_extension_properties(physical_devices[0], Some("VK_LAYER_missing")).err().unwrap();
+    assert_eq!(err.to_string(), "vkEnumerateDeviceExtensionProperties failed: ErrorLayerNotPresent");
+
+    assert!(instance.enumerate_device_extension_properties(physical_devices[1], None).unwrap().is_empty());
+    assert!(instance.enumerate_device_layer_properties(physical_devices[1]).unwrap().is_empty());
+}
+
+#[test]
+fn instance_is_destroyed_when_dropped() {
+    let log = common::call_log("entry_drop.log");
+    let _config = common::use_config("entry_drop.yaml", &format!("call_log: {}", log.display()));
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let instance = entry.create_instance().unwrap();
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\n");
+
+    drop(instance);
+    assert_eq!(common::logged_calls(&log), "vkCreateInstance\nvkDestroyInstance\n");
+}
+
+#[test]
+fn builder_passes_application_info_layers_and_extensions() {
+    let log = common::call_log("entry_builder.log");
+    let config = format!("{}\ncreate_info_log: {}\n", common::STANDARD_CONFIG, log.display());
+    let _config = common::use_config("entry_builder.yaml", &config);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let _instance = InstanceBuilder::new()
+        .application_name("app")
+        .application_version(3)
+        .engine_name("engine")
+        .engine_version(7)
+        .api_version(vk::API_VERSION_1_3)
+        .layer("VK_LAYER_MOCK_validation")
+        .extension("VK_KHR_surface")
+        .extension("VK_EXT_debug_utils")
+        .build(&entry)
+        .unwrap();
+
+    let create_info: CreateInfo = serde_yaml::from_str(&common::logged_calls(&log)).unwrap();
+    assert_eq!(create_info, CreateInfo {
+        application_name: Some("app".to_string()),
+        application_version: 3,
+        engine_name: Some("engine".to_string()),
+        engine_version: 7,
+        api_version: vk::make_api_version(0, 1, 3, 0),
+        layers: vec!["VK_LAYER_MOCK_validation".to_string()],
+        extensions: vec!["VK_KHR_surface".to_string(), "VK_EXT_debug_utils".to_string()],
+    });
+}
+
+#[test]
+fn builder_rejects_unavailable_layers_and_extensions() {
+    let log = common::call_log("entry_builder_missing.log");
+    let config = format!("{}\ncall_log: {}\n", common::STANDARD_CONFIG, log.display());
+    let _config = common::use_config("entry_builder_missing.yaml", &config);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let err = InstanceBuilder::new().layer("VK_LAYER_missing").build(&entry).err().unwrap();
+    assert_eq!(err.to_string(), "instance layer VK_LAYER_missing is not available");
+
+    // Layer extensions only count when the layer is enabled too.
+    let err = InstanceBuilder::new().extension("VK_EXT_debug_utils").build(&entry).err().unwrap();
+    assert_eq!(err.to_string(), "instance extension VK_EXT_debug_utils is not available");
+
+    assert_eq!(common::logged_calls(&log), "");
+}
+
+#[test]
+fn reports_instance_version() {
+    let _config = common::use_config("entry_instance_version.yaml", common::STANDARD_CONFIG);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let version = entry.enumerate_instance_version().unwrap();
+    assert_eq!(version, vk::Version { variant: 0, major: 1, minor: 3, patch: 250 });
+    assert_eq!(version.to_string(), "1.3.250");
+}
+
+#[test]
+fn missing_instance_version_means_1_0() {
+    let _config = common::use_config("entry_instance_version_missing.yaml", "missing_functions: [vkEnumerateInstanceVersion]");
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    assert_eq!(entry.enumerate_instance_version().unwrap(), vk::Version::from(vk::API_VERSION_1_0));
+}
+
+#[test]
+fn builder_lowers_api_version_to_the_loaders() {
+    let log = common::call_log("entry_builder_version.log");
+    let _config = common::use_config("entry_builder_version.yaml", &format!("create_info_log: {}\ninstance_version: {{major: 1, minor: 2, patch: 7}}", log.display()));
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_4).build(&entry).unwrap();
+    let create_info: CreateInfo = serde_yaml::from_str(&common::logged_calls(&log)).unwrap();
+    assert_eq!(create_info.api_version, vk::API_VERSION_1_2);
+    assert_eq!(instance.api_version(), vk::API_VERSION_1_2);
+    drop(instance);
+
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_1).build(&entry).unwrap();
+    assert_eq!(instance.api_version(), vk::API_VERSION_1_1);
+}
+
+#[test]
+fn builder_asks_a_1_0_loader_for_1_0() {
+    let _config = common::use_config("entry_builder_1_0.yaml", "missing_functions: [vkEnumerateInstanceVersion]");
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let instance = InstanceBuilder::new().api_version(vk::API_VERSION_1_3).build(&entry).unwrap();
+    assert_eq!(instance.api_version(), vk::API_VERSION_1_0);
+}
+
+#[test]
+fn failing_call_keeps_function_and_result() {
+    let _config = common::use_config("entry_create_fails.yaml", "failures: {vkCreateInstance: ErrorIncompatibleDriver}");
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    match entry.create_instance().err().unwrap() {
+        Error::Call { function, result } => {
+            assert_eq!(function, "vkCreateInstance");
+            assert_eq!(result, vk::Result::ErrorIncompatibleDriver);
+        }
+        err => panic!("unexpected error {:?}", err),
+    }
+}
+
+#[test]
+fn unknown_enum_values_are_preserved() {
+    let config = "
+failures: {vkEnumeratePhysicalDevices: {Unknown: -1000999000}}
+physical_devices:
+  - properties:
+      device_type: {Unknown: 1000}
+";
+    let _config = common::use_config("entry_unknown_enums.yaml", config);
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+    let instance = entry.create_instance().unwrap();
+
+    let err = instance.enumerate_physical_devices().err().unwrap();
+    assert_eq!(err.vk_result(), Some(vk::Result::Unknown(-1000999000)));
+    assert_eq!(err.to_string(), "vkEnumeratePhysicalDevices failed: Unknown(-1000999000)");
+}
+
+#[test]
+fn raw_enums_round_trip() {
+    assert_eq!(vk::Raw::new(vk::PhysicalDeviceType::Cpu).as_raw(), 4);
+    assert_eq!(vk::Raw::<vk::PhysicalDeviceType>::from_raw(2).get(), vk::PhysicalDeviceType::DiscreteGpu);
+    assert_eq!(vk::Raw::<vk::PhysicalDeviceType>::from_raw(1000).get(), vk::PhysicalDeviceType::Unknown(1000));
+    assert_eq!(vk::Raw::<vk::Result>::from_raw(-1000001004).get(), vk::Result::ErrorOutOfDateKhr);
+    assert_eq!(vk::Raw::new(vk::Result::Unknown(-13)).as_raw(), -13);
+}
+
+#[test]
+fn missing_symbol_is_named() {
+    let _config = common::use_config("entry_missing_symbol.yaml", "missing_functions: [vkGetPhysicalDeviceProperties]");
+    let entry = Entry::with_loader(common::mock_icd_path()).unwrap();
+
+    let err = entry.create_instance().err().unwrap();
+    match err {
+        Error::MissingSymbol(name) => assert_eq!(name, "vkGetPhysicalDeviceProperties"),
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(err.to_string(), "Vulkan loader does not provide vkGetPhysicalDeviceProperties");
+}
@@ -0,0 +1,145 @@
+extern crate serde_yaml;
+extern crate vkinfo;
+
+use vkinfo::backend::{Backend, Replay};
+use vkinfo::report::Report;
+use vkinfo::vk;
+
+const SAVED_REPORT: &str = r#"
+extensions:
+  - extension_name: VK_KHR_surface
+layers:
+  - properties:
+      layer_name: VK_LAYER_saved
+    extensions:
+      - extension_name: VK_EXT_debug_report
+        spec_version: 9
+physical_devices:
+  - properties:
+      device_name: Saved GPU
+      device_type: IntegratedGpu
+    vulkan_12_features:
+      timeline_semaphore: 1
+    vulkan_12_properties:
+      driver_id: IntelOpenSourceMesa
+    id_properties:
+      device_uuid: [134, 128, 154, 70, 12, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0]
+    location:
+      pci_address: "0000:00:02.0"
+      kernel_driver: i915
+    queue_families:
+      - queue_flags: {bits: 1}
+        queue_count: 1
+        global_priorities: [Medium, High]
+    surface:
+      capabilities:
+        min_image_count: 3
+      formats:
+        - format: B8g8r8a8Unorm
+          color_space: SrgbNonlinear
+      present_modes: [Fifo]
+      queue_family_support: [true]
+    displays:
+      displays:
+        - name: eDP-1
+          modes:
+            - parameters: {visible_region: {width: 1920, height: 1200}, refresh_rate: 60000}
+              planes:
+                - plane: 0
+      planes:
+        - current_display: 0
+          supported_displays: [0]
+    video_profiles:
+      - profile: {operation: {bits: 1}, std_profile: 100, chroma_subsampling: {bits: 2}, luma_bit_depth: {bits: 1}, chroma_bit_depth: {bits: 1}}
+        capabilities:
+          capabilities:
+            max_coded_extent: {width: 4096, height: 2304}
+          decode: {flags: {bits: 1}}
+        picture_formats: [G8B8r82plane420Unorm]
+        queue_families: [0]
+    external_handles:
+      buffers:
+        - handle_type: {bits: 512}
+          properties: {external_memory_features: {bits: 6}, compatible_handle_types: {bits: 512}}
+      semaphores:
+        - handle_type: {bits: 16}
+          properties: {external_semaphore_features: {bits: 3}}
+    tools:
+      - name: Saved Profiler
+        purposes: {bits: 2}
+    formats:
+      - format: R16Sfloat
+        properties:
+          optimal_tiling_features: {bits: 1}
+          buffer_features: {bits: 8}
+    sparse_formats:
+      - format: R16Sfloat
+        samples: {bits: 1}
+        properties:
+          - aspect_mask: {bits: 1}
+            image_granularity: {width: 256, height: 128, depth: 1}
+    extensions:
+      - extension_name: VK_KHR_swapchain
+        spec_version: 70
+    layers:
+      - properties:
+          layer_name: VK_LAYER_saved
+        extensions:
+          - extension_name: VK_EXT_tooling_info
+device_groups:
+  - physical_devices:
+      - index: 0
+        device_name: Saved GPU
+"#;
+
+#[test]
+fn replay_answers_from_the_report() {
+    let replay = Replay::new(serde_yaml::from_str(SAVED_REPORT).unwrap());
+
+    assert_eq!(replay.enumerate_instance_layer_properties().unwrap()[0].layer_name.to_string_lossy(), "VK_LAYER_saved");
+    assert_eq!(replay.enumerate_instance_extension_properties(Some("VK_LAYER_saved")).unwrap()[0].spec_version, 9);
+    assert_eq!(replay.enumerate_instance_extension_properties(Some("VK_LAYER_other")).err().unwrap().vk_result(), Some(vk::Result::ErrorLayerNotPresent));
+    assert_eq!(replay.physical_device_count().unwrap(), 1);
+    assert_eq!(replay.enumerate_physical_device_groups().unwrap()[0].physical_devices[0].device_name, "Saved GPU");
+    assert_eq!(replay.get_physical_device_properties(0).device_type, vk::PhysicalDeviceType::IntegratedGpu);
+    assert_eq!(replay.get_physical_device_features2(0).vulkan_12.unwrap().timeline_semaphore, 1);
+    assert!(replay.get_physical_device_features2(0).vulkan_13.is_none());
+    assert_eq!(replay.get_physical_device_properties2(0).vulkan_12.unwrap().driver_id, vk::DriverId::IntelOpenSourceMesa);
+    assert_eq!(replay.get_physical_device_properties2(0).id.unwrap().device_uuid[0], 0x86);
+    assert_eq!(replay.get_physical_device_location(0).unwrap().kernel_driver.as_deref(), Some("i915"));
+    assert_eq!(replay.get_physical_device_queue_family_properties(0)[0].queue_flags, vk::QueueFlags::GraphicsBit);
+    assert_eq!(replay.get_physical_device_queue_family_properties2(0)[0].global_priorities.as_deref(), Some(&[vk::QueueGlobalPriorityKhr::Medium, vk::QueueGlobalPriorityKhr::High][..]));
+    assert_eq!(replay.get_physical_device_surface_support(0).unwrap().unwrap().present_modes, [vk::PresentModeKhr::Fifo]);
+    assert_eq!(replay.get_physical_device_display_support(0).unwrap().unwrap().displays[0].name.as_deref(), Some("eDP-1"));
+    assert_eq!(replay.get_physical_device_video_profiles(0).unwrap().unwrap()[0].capabilities.capabilities.max_coded_extent.height, 2304);
+    let handles = replay.get_physical_device_external_handle_support(0).unwrap().unwrap();
+    assert_eq!(handles.buffers[0].handle_type, vk::ExternalMemoryHandleTypeFlags::DmaBufBitExt);
+    assert_eq!(handles.semaphores[0].properties.as_ref().unwrap().external_semaphore_features, vk::ExternalSemaphoreFeatureFlags::all());
+    assert!(handles.fences.is_empty());
+    assert_eq!(replay.get_physical_device_tool_properties(0).unwrap().unwrap()[0].purposes, vk::ToolPurposeFlags::ProfilingBit);
+    assert_eq!(replay.get_physical_device_format_properties(0, vk::Format::R16Sfloat).buffer_features, vk::FormatFeatureFlags::UniformTexelBufferBit);
+    assert!(replay.get_physical_device_format_properties(0, vk::Format::R16Unorm).buffer_features.is_empty());
+    assert_eq!(replay.get_physical_device_sparse_image_format_properties(0, vk::Format::R16Sfloat, vk::ImageType::Type2d, vk::SampleCountFlags::Count1Bit, vk::ImageUsageFlags::SampledBit, vk::ImageTiling::Optimal)[0].image_granularity.width, 256);
+    assert!(replay.get_physical_device_sparse_image_format_properties(0, vk::Format::R16Sfloat, vk::ImageType::Type3d, vk::SampleCountFlags::Count1Bit, vk::ImageUsageFlags::SampledBit, vk::ImageTiling::Optimal).is_empty());
+    assert_eq!(replay.enumerate_device_extension_properties(0, None).unwrap()[0].spec_version, 70);
+    assert_eq!(replay.enumerate_device_layer_properties(0).unwrap()[0].layer_name.to_string_lossy(), "VK_LAYER_saved");
+    assert_eq!(replay.enumerate_device_extension_properties(0, Some("VK_LAYER_saved")).unwrap()[0].extension_name.to_string_lossy(), "VK_EXT_tooling_info");
+    assert_eq!(replay.enumerate_device_extension_properties(0, Some("VK_LAYER_other")).err().unwrap().vk_result(), Some(vk::Result::ErrorLayerNotPresent));
+}
+
+#[test]
+fn gathering_a_replay_reproduces_the_report() {
+    let report: Report = serde_yaml::from_str(SAVED_REPORT).unwrap();
+    let gathered = Report::gather(&Replay::new(report.clone())).unwrap();
+
+    assert_eq!(serde_yaml::to_string(&gathered).unwrap(), serde_yaml::to_string(&report).unwrap());
+}
+
+#[test]
+fn reports_without_device_groups_get_one_per_device() {
+    let mut report: Report = serde_yaml::from_str(SAVED_REPORT).unwrap();
+    let saved_groups = report.device_groups.clone();
+    report.device_groups.clear();
+
+    assert_eq!(Replay::new(report).enumerate_physical_device_groups().unwrap(), saved_groups);
+}
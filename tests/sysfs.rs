@@ -0,0 +1,68 @@
+#![cfg(unix)]
+
+extern crate vkinfo;
+extern crate vkinfo_mock_icd;
+
+mod common;
+
+use vkinfo::sysfs::{DeviceLocation, Sysfs};
+use vkinfo::vk;
+
+fn pci_bus_info() -> vk::PhysicalDevicePciBusInfoPropertiesExt {
+    vk::PhysicalDevicePciBusInfoPropertiesExt { pci_bus: 3, ..Default::default() }
+}
+
+fn drm() -> vk::PhysicalDeviceDrmPropertiesExt {
+    vk::PhysicalDeviceDrmPropertiesExt {
+        has_primary: 1,
+        has_render: 1,
+        primary_major: 226,
+        primary_minor: 1,
+        render_major: 226,
+        render_minor: 128,
+        ..Default::default()
+    }
+}
+
+fn gpu() -> DeviceLocation {
+    DeviceLocation {
+        pci_address: Some("0000:03:00.0".to_string()),
+        kernel_driver: Some("amdgpu".to_string()),
+        primary_node: Some("/dev/dri/card1".to_string()),
+        render_node: Some("/dev/dri/renderD128".to_string()),
+    }
+}
+
+#[test]
+fn pci_bus_info_finds_driver_and_nodes() {
+    let sysfs = Sysfs::new(common::sysfs_fixture("sysfs_pci"));
+
+    assert_eq!(sysfs.locate(Some(&pci_bus_info()), None), Some(gpu()));
+}
+
+#[test]
+fn drm_nodes_find_the_pci_device() {
+    let sysfs = Sysfs::new(common::sysfs_fixture("sysfs_drm"));
+
+    assert_eq!(sysfs.locate(None, Some(&drm())), Some(gpu()));
+    assert_eq!(sysfs.locate(Some(&pci_bus_info()), Some(&drm())), Some(gpu()));
+
+    let render_only = vk::PhysicalDeviceDrmPropertiesExt { has_primary: 0, ..drm() };
+    assert_eq!(sysfs.locate(None, Some(&render_only)), Some(DeviceLocation { primary_node: None, ..gpu() }));
+}
+
+#[test]
+fn devices_missing_from_sysfs_keep_what_vulkan_reports() {
+    let sysfs = Sysfs::new(common::sysfs_fixture("sysfs_missing"));
+
+    let other_slot = vk::PhysicalDevicePciBusInfoPropertiesExt { pci_domain: 1, pci_bus: 0x41, pci_device: 2, pci_function: 1, ..Default::default() };
+    assert_eq!(sysfs.locate(Some(&other_slot), None), Some(DeviceLocation { pci_address: Some("0001:41:02.1".to_string()), ..DeviceLocation::default() }));
+
+    let no_sysfs = Sysfs::new("/nonexistent");
+    assert_eq!(no_sysfs.locate(Some(&pci_bus_info()), Some(&drm())), Some(DeviceLocation { pci_address: Some("0000:03:00.0".to_string()), ..DeviceLocation::default() }));
+}
+
+#[test]
+fn devices_without_either_extension_have_no_location() {
+    assert_eq!(Sysfs::new(common::sysfs_fixture("sysfs_none")).locate(None, None), None);
+}